@@ -0,0 +1,271 @@
+//! Online defragmentation of thin volumes
+//!
+//! Long-lived volumes accumulate chunks that are mostly zeros (trimmed or
+//! never fully written) yet are stored as full 4 MB EC objects. A defrag
+//! job walks a volume's flushed chunks and:
+//!   - rewrites partially-filled chunks as dense objects that omit the
+//!     trailing zero LBAs (reads pad them back)
+//!   - releases all-zero chunks: the chunk ref is dropped and the object
+//!     deleted, so the chunk reads as sparse again
+//!   - deletes the superseded shards on the OSDs
+//!
+//! Rewrites hold `chunk_write_lock` so they never race a flush of the same
+//! chunk, and chunks with unflushed writes are skipped. Superseded shards
+//! are deleted once the scan finishes, after `SHARD_DELETE_GRACE`, so reads
+//! that fetched the old object meta can still complete.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use objectio_block::chunk::dense_len;
+use objectio_proto::block::{DefragState, DefragStatus};
+use parking_lot::Mutex;
+use tracing::{info, warn};
+
+use crate::ec_io::{
+    ChunkObject, chunk_object, delete_chunk, delete_chunk_shards, read_chunk, write_chunk,
+};
+use crate::service::BlockGatewayState;
+
+/// Default minimum shrink for a rewrite to be worth it.
+pub const DEFAULT_MIN_SAVINGS_PERCENT: u32 = 25;
+
+/// How long superseded objects stay readable before their shards go.
+const SHARD_DELETE_GRACE: Duration = Duration::from_secs(30);
+
+/// Progress of one volume's defrag job.
+pub struct DefragJob {
+    volume_id: String,
+    min_savings_percent: u32,
+    cancelled: AtomicBool,
+    chunks_total: AtomicU64,
+    chunks_scanned: AtomicU64,
+    chunks_rewritten: AtomicU64,
+    chunks_released: AtomicU64,
+    chunks_skipped: AtomicU64,
+    bytes_before: AtomicU64,
+    bytes_after: AtomicU64,
+    started_at: u64,
+    /// `(state, finished_at, error)` once the job stops.
+    outcome: Mutex<Option<(DefragState, u64, String)>>,
+}
+
+impl DefragJob {
+    fn new(volume_id: &str, min_savings_percent: u32) -> Self {
+        Self {
+            volume_id: volume_id.to_string(),
+            min_savings_percent,
+            cancelled: AtomicBool::new(false),
+            chunks_total: AtomicU64::new(0),
+            chunks_scanned: AtomicU64::new(0),
+            chunks_rewritten: AtomicU64::new(0),
+            chunks_released: AtomicU64::new(0),
+            chunks_skipped: AtomicU64::new(0),
+            bytes_before: AtomicU64::new(0),
+            bytes_after: AtomicU64::new(0),
+            started_at: chrono::Utc::now().timestamp() as u64,
+            outcome: Mutex::new(None),
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.outcome.lock().is_none()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    fn finish(&self, state: DefragState, error: String) {
+        let now = chrono::Utc::now().timestamp() as u64;
+        *self.outcome.lock() = Some((state, now, error));
+    }
+
+    pub fn status(&self) -> DefragStatus {
+        let (state, finished_at, error) =
+            self.outcome
+                .lock()
+                .clone()
+                .unwrap_or((DefragState::Running, 0, String::new()));
+        DefragStatus {
+            volume_id: self.volume_id.clone(),
+            state: state.into(),
+            chunks_total: self.chunks_total.load(Ordering::Relaxed),
+            chunks_scanned: self.chunks_scanned.load(Ordering::Relaxed),
+            chunks_rewritten: self.chunks_rewritten.load(Ordering::Relaxed),
+            chunks_released: self.chunks_released.load(Ordering::Relaxed),
+            chunks_skipped: self.chunks_skipped.load(Ordering::Relaxed),
+            bytes_before: self.bytes_before.load(Ordering::Relaxed),
+            bytes_after: self.bytes_after.load(Ordering::Relaxed),
+            started_at: self.started_at,
+            finished_at,
+            error,
+        }
+    }
+}
+
+/// Latest defrag job per volume on this gateway.
+#[derive(Default)]
+pub struct DefragJobs {
+    jobs: Mutex<HashMap<String, Arc<DefragJob>>>,
+}
+
+impl DefragJobs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new job, unless one is already running for the volume.
+    pub fn start(
+        &self,
+        volume_id: &str,
+        min_savings_percent: u32,
+    ) -> Result<Arc<DefragJob>, Arc<DefragJob>> {
+        let mut jobs = self.jobs.lock();
+        if let Some(existing) = jobs.get(volume_id)
+            && existing.is_running()
+        {
+            return Err(Arc::clone(existing));
+        }
+        let job = Arc::new(DefragJob::new(volume_id, min_savings_percent));
+        jobs.insert(volume_id.to_string(), Arc::clone(&job));
+        Ok(job)
+    }
+
+    pub fn get(&self, volume_id: &str) -> Option<Arc<DefragJob>> {
+        self.jobs.lock().get(volume_id).cloned()
+    }
+
+    pub fn remove(&self, volume_id: &str) {
+        if let Some(job) = self.jobs.lock().remove(volume_id) {
+            job.cancel();
+        }
+    }
+}
+
+/// Status for a volume with no job on record.
+pub fn idle_status(volume_id: &str) -> DefragStatus {
+    DefragStatus {
+        volume_id: volume_id.to_string(),
+        state: DefragState::None.into(),
+        ..Default::default()
+    }
+}
+
+/// Run a defrag job to completion.
+pub async fn run_defrag(state: Arc<BlockGatewayState>, job: Arc<DefragJob>) {
+    let vol_id = job.volume_id.clone();
+    let chunks = match state.store.list_chunks(&vol_id) {
+        Ok(c) => c,
+        Err(e) => {
+            job.finish(DefragState::Failed, format!("list chunks: {e}"));
+            return;
+        }
+    };
+    job.chunks_total
+        .store(chunks.len() as u64, Ordering::Relaxed);
+    info!("Defrag started for vol {vol_id}: {} chunks", chunks.len());
+
+    let mut superseded = Vec::new();
+    for (chunk_id, object_key) in chunks {
+        if job.cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        match defrag_chunk(&state, &job, chunk_id, &object_key).await {
+            Ok(Some(old)) => superseded.push(old),
+            Ok(None) => {}
+            Err(e) => {
+                warn!("Defrag skipped chunk {chunk_id} of vol {vol_id}: {e}");
+                job.chunks_skipped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        job.chunks_scanned.fetch_add(1, Ordering::Relaxed);
+    }
+
+    if !superseded.is_empty() {
+        tokio::time::sleep(SHARD_DELETE_GRACE).await;
+        for old in &superseded {
+            delete_chunk_shards(&state.osd_pool, old).await;
+        }
+    }
+
+    let final_state = if job.cancelled.load(Ordering::Relaxed) {
+        DefragState::Cancelled
+    } else {
+        DefragState::Completed
+    };
+    job.finish(final_state, String::new());
+    let status = job.status();
+    info!(
+        "Defrag {} for vol {vol_id}: {} rewritten, {} released, {} skipped, {} -> {} bytes",
+        final_state.as_str_name(),
+        status.chunks_rewritten,
+        status.chunks_released,
+        status.chunks_skipped,
+        status.bytes_before,
+        status.bytes_after
+    );
+}
+
+/// Compact one chunk. Returns the superseded object whose shards should be
+/// deleted, if the chunk was rewritten or released.
+async fn defrag_chunk(
+    state: &BlockGatewayState,
+    job: &DefragJob,
+    chunk_id: u64,
+    object_key: &str,
+) -> anyhow::Result<Option<ChunkObject>> {
+    let vol_id = &job.volume_id;
+    let _guard = state.chunk_write_lock.lock().await;
+
+    // Unflushed writes will replace the object anyway
+    if state.cache.is_dirty(vol_id, chunk_id) {
+        job.chunks_skipped.fetch_add(1, Ordering::Relaxed);
+        return Ok(None);
+    }
+
+    let Some(old) =
+        chunk_object(Arc::clone(&state.meta_client), &state.osd_pool, object_key).await?
+    else {
+        return Ok(None);
+    };
+    let data = read_chunk(
+        Arc::clone(&state.meta_client),
+        &state.osd_pool,
+        object_key,
+        state.ec_k,
+        state.ec_m,
+    )
+    .await?;
+    job.bytes_before.fetch_add(old.size, Ordering::Relaxed);
+
+    let dense = dense_len(&data) as u64;
+    if dense == 0 {
+        state.store.remove_chunk(vol_id, chunk_id)?;
+        delete_chunk(Arc::clone(&state.meta_client), &state.osd_pool, object_key).await?;
+        job.chunks_released.fetch_add(1, Ordering::Relaxed);
+        return Ok(Some(old));
+    }
+
+    let savings = old.size.saturating_sub(dense);
+    if savings * 100 < old.size * u64::from(job.min_savings_percent) || savings == 0 {
+        job.bytes_after.fetch_add(old.size, Ordering::Relaxed);
+        return Ok(None);
+    }
+
+    write_chunk(
+        Arc::clone(&state.meta_client),
+        &state.osd_pool,
+        vol_id,
+        chunk_id,
+        &data[..dense as usize],
+        state.ec_k,
+        state.ec_m,
+    )
+    .await?;
+    job.bytes_after.fetch_add(dense, Ordering::Relaxed);
+    job.chunks_rewritten.fetch_add(1, Ordering::Relaxed);
+    Ok(Some(old))
+}
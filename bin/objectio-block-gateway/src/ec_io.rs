@@ -20,8 +20,8 @@ use tracing::{error, warn};
 use uuid::Uuid;
 
 use crate::osd_pool::{
-    OsdPool, delete_object_meta_from_osd, get_object_meta_from_osd, put_object_meta_to_osd,
-    read_shard_from_osd, write_shard_to_osd,
+    OsdPool, delete_object_meta_from_osd, delete_shard_from_osd, get_object_meta_from_osd,
+    put_object_meta_to_osd, read_shard_from_osd, write_shard_to_osd,
 };
 
 /// Bucket name reserved for all block chunks.
@@ -55,6 +55,7 @@ pub async fn write_chunk(
             key: object_key.clone(),
             size: data.len() as u64,
             storage_class: String::new(),
+            pool: String::new(),
         })
         .await
         .map_err(|e| anyhow!("GetPlacement failed: {e}"))?
@@ -173,7 +174,9 @@ pub async fn write_chunk(
 
 /// Read and reconstruct a chunk from the OSD cluster.
 ///
-/// `object_key` is the value previously returned by `write_chunk`.
+/// `object_key` is the value previously returned by `write_chunk`. Dense
+/// chunks (see `defrag`) come back shorter than the chunk size; callers
+/// pad with zeros.
 pub async fn read_chunk(
     meta_client: Arc<Mutex<MetadataServiceClient<Channel>>>,
     osd_pool: &Arc<OsdPool>,
@@ -190,6 +193,7 @@ pub async fn read_chunk(
             key: object_key.to_string(),
             size: 0,
             storage_class: String::new(),
+            pool: String::new(),
         })
         .await
         .map_err(|e| anyhow!("GetPlacement failed: {e}"))?
@@ -275,8 +279,99 @@ pub async fn read_chunk(
     Ok(decoded)
 }
 
-/// Delete a chunk's object metadata from the OSD (for volume deletion).
-#[allow(dead_code)]
+/// The stored object currently behind a chunk key.
+pub struct ChunkObject {
+    pub object_id: Vec<u8>,
+    /// Stored (pre-EC) size; less than the chunk size for dense chunks
+    pub size: u64,
+    /// Where each shard lives, for deleting them once superseded
+    pub shards: Vec<NodePlacement>,
+}
+
+/// Look up the object currently stored for `object_key`.
+pub async fn chunk_object(
+    meta_client: Arc<Mutex<MetadataServiceClient<Channel>>>,
+    osd_pool: &Arc<OsdPool>,
+    object_key: &str,
+) -> Result<Option<ChunkObject>> {
+    let placement = meta_client
+        .lock()
+        .await
+        .get_placement(GetPlacementRequest {
+            bucket: BLOCK_BUCKET.to_string(),
+            key: object_key.to_string(),
+            size: 0,
+            storage_class: String::new(),
+            pool: String::new(),
+        })
+        .await
+        .map_err(|e| anyhow!("GetPlacement failed: {e}"))?
+        .into_inner();
+
+    let Some(primary) = placement.nodes.first() else {
+        return Err(anyhow!("no placement nodes for {object_key}"));
+    };
+    let Some(object_meta) = get_object_meta_from_osd(osd_pool, primary, BLOCK_BUCKET, object_key)
+        .await
+        .map_err(|e| anyhow!("get_object_meta failed: {e}"))?
+    else {
+        return Ok(None);
+    };
+    let Some(stripe) = object_meta.stripes.first() else {
+        return Ok(None);
+    };
+
+    let addr_map: HashMap<u32, &NodePlacement> =
+        placement.nodes.iter().map(|n| (n.position, n)).collect();
+    let shards = stripe
+        .shards
+        .iter()
+        .filter_map(|loc| {
+            addr_map.get(&loc.position).map(|n| NodePlacement {
+                position: loc.position,
+                node_id: loc.node_id.clone(),
+                node_address: n.node_address.clone(),
+                disk_id: loc.disk_id.clone(),
+                shard_type: loc.shard_type,
+                local_group: loc.local_group,
+            })
+        })
+        .collect();
+
+    Ok(Some(ChunkObject {
+        object_id: if stripe.object_id.is_empty() {
+            object_meta.object_id.clone()
+        } else {
+            stripe.object_id.clone()
+        },
+        size: stripe.data_size,
+        shards,
+    }))
+}
+
+/// Delete a superseded object's shards. Best effort: failures are logged
+/// and counted, not returned.
+pub async fn delete_chunk_shards(osd_pool: &Arc<OsdPool>, object: &ChunkObject) -> usize {
+    let futs = object
+        .shards
+        .iter()
+        .map(|node| delete_shard_from_osd(osd_pool, node, &object.object_id, 0, node.position));
+    let mut failed = 0;
+    for (node, res) in object.shards.iter().zip(join_all(futs).await) {
+        if let Err(e) = res {
+            warn!(
+                "Failed to delete shard {} of {}: {e}",
+                node.position,
+                hex::encode(&object.object_id)
+            );
+            failed += 1;
+        }
+    }
+    failed
+}
+
+/// Delete a chunk's object metadata from the OSD (for volume deletion and
+/// releasing all-zero chunks).
 pub async fn delete_chunk(
     meta_client: Arc<Mutex<MetadataServiceClient<Channel>>>,
     osd_pool: &Arc<OsdPool>,
@@ -290,6 +385,7 @@ pub async fn delete_chunk(
             key: object_key.to_string(),
             size: 0,
             storage_class: String::new(),
+            pool: String::new(),
         })
         .await
         .map_err(|e| anyhow!("GetPlacement failed: {e}"))?
@@ -11,6 +11,7 @@ use crate::service::BlockGatewayState;
 
 /// Flush all dirty chunks for one volume, then persist the chunk refs.
 pub async fn flush_volume(vol_id: &str, state: &BlockGatewayState) {
+    let _guard = state.chunk_write_lock.lock().await;
     let chunks = state.cache.get_chunks_to_flush(vol_id);
     if chunks.is_empty() {
         return;
@@ -58,6 +59,7 @@ pub async fn flush_volume(vol_id: &str, state: &BlockGatewayState) {
 pub async fn flush_volume_all(vol_id: &str, state: &BlockGatewayState) {
     // flush_volume already drains everything age >= max_dirty_age; repeat until clean.
     // For an explicit flush we drain everything immediately via the inner loop.
+    let _guard = state.chunk_write_lock.lock().await;
     let chunks = state.cache.flush_volume(vol_id);
     if chunks.is_empty() {
         return;
@@ -4,6 +4,7 @@
 //! Accepts block I/O over gRPC (BlockService) and NBD, buffers writes in an
 //! in-memory WriteCache, and flushes 4 MB chunks as EC objects to the OSDs.
 
+mod defrag;
 mod ec_io;
 mod flush;
 mod nbd;
@@ -176,6 +177,8 @@ async fn main() -> Result<()> {
         nbd_port,
         ec_k: args.ec_k,
         ec_m: args.ec_m,
+        chunk_write_lock: Mutex::new(()),
+        defrag_jobs: defrag::DefragJobs::new(),
     });
 
     // ── Background flush loop ─────────────────────────────────────────────────
@@ -455,14 +455,17 @@ impl NbdServer {
         let object_key = self.store.get_chunk(vol_id, chunk_id)?;
 
         let chunk_data = if let Some(key) = object_key {
-            read_chunk(
+            // Dense chunks (see defrag) omit their zero tail
+            let mut data = read_chunk(
                 Arc::clone(&self.meta_client),
                 &self.osd_pool,
                 &key,
                 self.ec_k,
                 self.ec_m,
             )
-            .await?
+            .await?;
+            data.resize(chunk_mapper.chunk_size() as usize, 0);
+            data
         } else {
             // Sparse (never written) → return zeros
             vec![0u8; chunk_mapper.chunk_size() as usize]
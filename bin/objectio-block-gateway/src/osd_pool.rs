@@ -266,6 +266,47 @@ pub async fn read_shard_from_osd(
     Ok(response.into_inner().data)
 }
 
+/// Delete a shard from the OSD holding it
+pub async fn delete_shard_from_osd(
+    pool: &OsdPool,
+    placement: &NodePlacement,
+    object_id: &[u8],
+    stripe_id: u64,
+    position: u32,
+) -> Result<(), OsdPoolError> {
+    use objectio_proto::storage::{DeleteShardRequest, ShardId};
+
+    let mut client = pool.get_client_for_placement(placement).await?;
+
+    let request = DeleteShardRequest {
+        shard_id: Some(ShardId {
+            object_id: object_id.to_vec(),
+            stripe_id,
+            position,
+        }),
+    };
+
+    let delete_future = client.delete_shard(request);
+    tokio::time::timeout(std::time::Duration::from_secs(10), delete_future)
+        .await
+        .map_err(|_| {
+            error!(
+                "Timeout deleting shard {} from OSD {}",
+                position, placement.node_address
+            );
+            OsdPoolError::ConnectionFailed("delete timeout".to_string())
+        })?
+        .map_err(|e| {
+            warn!(
+                "Failed to delete shard from OSD {}: {}",
+                placement.node_address, e
+            );
+            OsdPoolError::ConnectionFailed(e.to_string())
+        })?;
+
+    Ok(())
+}
+
 /// Store object metadata on the primary OSD
 pub async fn put_object_meta_to_osd(
     pool: &OsdPool,
@@ -350,7 +391,6 @@ pub async fn get_object_meta_from_osd(
 }
 
 /// Delete object metadata from the primary OSD
-#[allow(dead_code)]
 pub async fn delete_object_meta_from_osd(
     pool: &OsdPool,
     primary_placement: &NodePlacement,
@@ -6,24 +6,26 @@ use objectio_block::volume::VolumeState;
 use objectio_block::{VolumeManager, WriteCache};
 use objectio_proto::block::block_service_server::BlockService;
 use objectio_proto::block::{
-    AttachVolumeRequest, AttachVolumeResponse, Attachment, CloneVolumeRequest, CloneVolumeResponse,
-    CreateSnapshotRequest, CreateSnapshotResponse, CreateVolumeRequest, CreateVolumeResponse,
-    DeleteSnapshotRequest, DeleteSnapshotResponse, DeleteVolumeRequest, DeleteVolumeResponse,
-    DetachVolumeRequest, DetachVolumeResponse, FlushRequest, FlushResponse,
-    GetClusterMetricsRequest, GetClusterMetricsResponse, GetIoTraceRequest, GetIoTraceResponse,
-    GetOsdMetricsRequest, GetOsdMetricsResponse, GetSnapshotRequest, GetSnapshotResponse,
-    GetVolumeRequest, GetVolumeResponse, GetVolumeStatsRequest, GetVolumeStatsResponse,
-    ListAttachmentsRequest, ListAttachmentsResponse, ListOsdMetricsRequest, ListOsdMetricsResponse,
-    ListSnapshotsRequest, ListSnapshotsResponse, ListVolumesRequest, ListVolumesResponse,
-    ReadRequest, ReadResponse, ResizeVolumeRequest, ResizeVolumeResponse,
-    Snapshot as ProtoSnapshot, TargetType, TrimRequest, TrimResponse, UpdateVolumeQosRequest,
-    UpdateVolumeQosResponse, Volume as ProtoVolume, VolumeStats, WriteRequest, WriteResponse,
+    AttachVolumeRequest, AttachVolumeResponse, Attachment, CancelDefragRequest, CloneVolumeRequest,
+    CloneVolumeResponse, CreateSnapshotRequest, CreateSnapshotResponse, CreateVolumeRequest,
+    CreateVolumeResponse, DefragStatus, DeleteSnapshotRequest, DeleteSnapshotResponse,
+    DeleteVolumeRequest, DeleteVolumeResponse, DetachVolumeRequest, DetachVolumeResponse,
+    FlushRequest, FlushResponse, GetClusterMetricsRequest, GetClusterMetricsResponse,
+    GetDefragStatusRequest, GetIoTraceRequest, GetIoTraceResponse, GetOsdMetricsRequest,
+    GetOsdMetricsResponse, GetSnapshotRequest, GetSnapshotResponse, GetVolumeRequest,
+    GetVolumeResponse, GetVolumeStatsRequest, GetVolumeStatsResponse, ListAttachmentsRequest,
+    ListAttachmentsResponse, ListOsdMetricsRequest, ListOsdMetricsResponse, ListSnapshotsRequest,
+    ListSnapshotsResponse, ListVolumesRequest, ListVolumesResponse, ReadRequest, ReadResponse,
+    ResizeVolumeRequest, ResizeVolumeResponse, Snapshot as ProtoSnapshot, StartDefragRequest,
+    TargetType, TrimRequest, TrimResponse, UpdateVolumeQosRequest, UpdateVolumeQosResponse,
+    Volume as ProtoVolume, VolumeStats, WriteRequest, WriteResponse,
 };
 use objectio_proto::metadata::metadata_service_client::MetadataServiceClient;
 use tokio::sync::Mutex;
 use tonic::{Request, Response, Status, transport::Channel};
 use tracing::{info, warn};
 
+use crate::defrag::{DEFAULT_MIN_SAVINGS_PERCENT, DefragJobs, idle_status, run_defrag};
 use crate::ec_io::read_chunk;
 use crate::flush::flush_volume_all;
 use crate::nbd::NbdServer;
@@ -43,6 +45,10 @@ pub struct BlockGatewayState {
     pub nbd_port: u16,
     pub ec_k: u32,
     pub ec_m: u32,
+    /// Held while a chunk object is (re)written so flushes and defrag
+    /// rewrites of the same chunk never interleave.
+    pub chunk_write_lock: Mutex<()>,
+    pub defrag_jobs: DefragJobs,
 }
 
 // ── Service ───────────────────────────────────────────────────────────────────
@@ -150,6 +156,7 @@ impl BlockService for BlockGatewayService {
             .map_err(block_err_to_status)?;
 
         self.state.cache.remove_volume(&req.volume_id);
+        self.state.defrag_jobs.remove(&req.volume_id);
 
         if let Err(e) = self.state.store.delete_volume(&req.volume_id) {
             warn!("Failed to delete volume record {}: {e}", req.volume_id);
@@ -493,8 +500,8 @@ impl BlockService for BlockGatewayService {
                 .map_err(|e| Status::internal(e.to_string()))?;
 
             let chunk_data = if let Some(key) = object_key {
-                // Read from EC storage
-                read_chunk(
+                // Read from EC storage; dense chunks omit their zero tail
+                let mut data = read_chunk(
                     Arc::clone(&self.state.meta_client),
                     &self.state.osd_pool,
                     &key,
@@ -502,7 +509,9 @@ impl BlockService for BlockGatewayService {
                     self.state.ec_m,
                 )
                 .await
-                .map_err(|e| Status::internal(e.to_string()))?
+                .map_err(|e| Status::internal(e.to_string()))?;
+                data.resize(chunk_mapper.chunk_size() as usize, 0);
+                data
             } else {
                 // Chunk never written = sparse zero region
                 vec![0u8; chunk_mapper.chunk_size() as usize]
@@ -570,6 +579,68 @@ impl BlockService for BlockGatewayService {
         Ok(Response::new(TrimResponse { success: true }))
     }
 
+    // ── Defragmentation ───────────────────────────────────────────────────────
+
+    async fn start_defrag(
+        &self,
+        request: Request<StartDefragRequest>,
+    ) -> Result<Response<DefragStatus>, Status> {
+        let req = request.into_inner();
+        self.state
+            .volume_manager
+            .get_volume(&req.volume_id)
+            .map_err(block_err_to_status)?;
+
+        let min_savings = match req.min_savings_percent {
+            0 => DEFAULT_MIN_SAVINGS_PERCENT,
+            p if p <= 100 => p,
+            p => {
+                return Err(Status::invalid_argument(format!(
+                    "min_savings_percent must be 0-100, got {p}"
+                )));
+            }
+        };
+        let job = self
+            .state
+            .defrag_jobs
+            .start(&req.volume_id, min_savings)
+            .map_err(|_| {
+                Status::already_exists(format!(
+                    "defrag already running for volume {}",
+                    req.volume_id
+                ))
+            })?;
+
+        tokio::spawn(run_defrag(Arc::clone(&self.state), Arc::clone(&job)));
+        info!("Started defrag for volume {}", req.volume_id);
+        Ok(Response::new(job.status()))
+    }
+
+    async fn get_defrag_status(
+        &self,
+        request: Request<GetDefragStatusRequest>,
+    ) -> Result<Response<DefragStatus>, Status> {
+        let req = request.into_inner();
+        let status = self
+            .state
+            .defrag_jobs
+            .get(&req.volume_id)
+            .map_or_else(|| idle_status(&req.volume_id), |job| job.status());
+        Ok(Response::new(status))
+    }
+
+    async fn cancel_defrag(
+        &self,
+        request: Request<CancelDefragRequest>,
+    ) -> Result<Response<DefragStatus>, Status> {
+        let req = request.into_inner();
+        let job = self.state.defrag_jobs.get(&req.volume_id).ok_or_else(|| {
+            Status::not_found(format!("no defrag job for volume {}", req.volume_id))
+        })?;
+        job.cancel();
+        Ok(Response::new(job.status()))
+    }
+
     // ── Metrics (stubs) ───────────────────────────────────────────────────────
 
     async fn get_osd_metrics(
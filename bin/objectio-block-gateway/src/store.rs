@@ -180,6 +180,34 @@ impl BlockStore {
         Ok(table.get(key.as_str())?.map(|v| v.value().to_string()))
     }
 
+    /// All flushed chunks of a volume as `(chunk_id, object_key)`, in order.
+    pub fn list_chunks(&self, volume_id: &str) -> Result<Vec<(u64, String)>> {
+        let prefix = format!("{volume_id}\x00");
+        let rtx = self.db.begin_read()?;
+        let table = rtx.open_table(CHUNKS)?;
+
+        let mut chunks = Vec::new();
+        for entry in table.range(prefix.as_str()..)? {
+            let (k, v) = entry?;
+            let Some(hex_id) = k.value().strip_prefix(prefix.as_str()) else {
+                break;
+            };
+            if let Ok(chunk_id) = u64::from_str_radix(hex_id, 16) {
+                chunks.push((chunk_id, v.value().to_string()));
+            }
+        }
+        Ok(chunks)
+    }
+
+    /// Drop one chunk ref; the chunk reads back as zeros (sparse).
+    pub fn remove_chunk(&self, volume_id: &str, chunk_id: u64) -> Result<()> {
+        let key = chunk_db_key(volume_id, chunk_id);
+        let wtx = self.db.begin_write()?;
+        wtx.open_table(CHUNKS)?.remove(key.as_str())?;
+        wtx.commit()?;
+        Ok(())
+    }
+
     /// Delete all chunk refs for a volume (used on volume delete).
     pub fn delete_volume_chunks(&self, volume_id: &str) -> Result<()> {
         let prefix = format!("{volume_id}\x00");
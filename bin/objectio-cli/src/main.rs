@@ -5,9 +5,10 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use objectio_proto::block::{
-    CloneVolumeRequest, CreateSnapshotRequest, CreateVolumeRequest, DeleteSnapshotRequest,
-    DeleteVolumeRequest, GetSnapshotRequest, GetVolumeRequest, ListSnapshotsRequest,
-    ListVolumesRequest, ResizeVolumeRequest, block_service_client::BlockServiceClient,
+    CancelDefragRequest, CloneVolumeRequest, CreateSnapshotRequest, CreateVolumeRequest,
+    DefragState, DefragStatus, DeleteSnapshotRequest, DeleteVolumeRequest, GetDefragStatusRequest,
+    GetSnapshotRequest, GetVolumeRequest, ListSnapshotsRequest, ListVolumesRequest,
+    ResizeVolumeRequest, StartDefragRequest, block_service_client::BlockServiceClient,
 };
 use objectio_proto::metadata::{
     AddUserToGroupRequest, CreateAccessKeyRequest, CreateGroupRequest, CreateTenantRequest,
@@ -98,6 +99,87 @@ enum Commands {
         #[command(subcommand)]
         action: TopologyCommands,
     },
+    /// Metadata store disaster recovery (offline; does not contact meta)
+    Meta {
+        #[command(subcommand)]
+        action: MetaCommands,
+    },
+    /// Storage reclamation advice: lifecycle rules for cold prefixes and
+    /// cheaper EC profiles for cold buckets, with projected savings.
+    /// Served by the gateway's `/_admin/advisor`, not meta.
+    Advisor {
+        /// Only report on this bucket
+        #[arg(long)]
+        bucket: Option<String>,
+        /// Objects untouched for this many days count as cold
+        #[arg(long, default_value_t = 180)]
+        cold_days: u32,
+        /// Per-bucket inventory scan cap
+        #[arg(long, default_value_t = 100_000)]
+        max_objects: u64,
+        /// Gateway admin endpoint
+        #[arg(
+            long,
+            env = "OBJECTIO_ADMIN_ENDPOINT",
+            default_value = "http://localhost:9000"
+        )]
+        admin_endpoint: String,
+        /// SigV4 region
+        #[arg(long, env = "AWS_REGION", default_value = "us-east-1")]
+        region: String,
+        /// System admin access key
+        #[arg(long, env = "AWS_ACCESS_KEY_ID", default_value = "")]
+        access_key: String,
+        /// Secret for `--access-key`
+        #[arg(
+            long,
+            env = "AWS_SECRET_ACCESS_KEY",
+            default_value = "",
+            hide_env_values = true
+        )]
+        secret_key: String,
+        /// Print the raw JSON report
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum MetaCommands {
+    /// Rebuild a meta data dir from an off-node archive written by the
+    /// meta archiver (`archive/*` config keys). Run with every meta node
+    /// stopped, then start this node and POST /init on its admin port.
+    Restore {
+        /// Archive location: `s3://bucket/prefix` (uses the `latest`
+        /// pointer), `s3://bucket/prefix/meta-....oioarchive`, or a local
+        /// file path
+        #[arg(long)]
+        from: String,
+        /// Meta data dir to restore into; `meta.redb` must not exist yet
+        #[arg(long, default_value = "/var/lib/objectio/meta")]
+        data_dir: std::path::PathBuf,
+        /// S3 endpoint holding the archive bucket
+        #[arg(
+            long,
+            env = "AWS_ENDPOINT_URL",
+            default_value = "http://localhost:9000"
+        )]
+        s3_endpoint: String,
+        /// SigV4 region
+        #[arg(long, env = "AWS_REGION", default_value = "us-east-1")]
+        region: String,
+        /// Access key with s3:GetObject on the archive prefix
+        #[arg(long, env = "AWS_ACCESS_KEY_ID", default_value = "")]
+        access_key: String,
+        /// Secret for `--access-key`
+        #[arg(
+            long,
+            env = "AWS_SECRET_ACCESS_KEY",
+            default_value = "",
+            hide_env_values = true
+        )]
+        secret_key: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -358,6 +440,26 @@ enum VolumeCommands {
         #[arg(short, long)]
         force: bool,
     },
+    /// Start online defragmentation: rewrite partially-filled chunks
+    /// densely and release all-zero ones (block gateway endpoint)
+    Defrag {
+        /// Volume ID
+        volume_id: String,
+        /// Rewrite a chunk only if it shrinks by at least this percent
+        /// (0 = gateway default)
+        #[arg(long, default_value_t = 0)]
+        min_savings_percent: u32,
+    },
+    /// Show defragmentation progress
+    DefragStatus {
+        /// Volume ID
+        volume_id: String,
+    },
+    /// Cancel a running defragmentation
+    DefragCancel {
+        /// Volume ID
+        volume_id: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -449,6 +551,33 @@ fn format_volume_state(state: i32) -> &'static str {
     }
 }
 
+fn print_defrag_status(status: &DefragStatus) {
+    let state = DefragState::try_from(status.state).map_or("UNKNOWN", |s| {
+        s.as_str_name().trim_start_matches("DEFRAG_STATE_")
+    });
+    println!("Volume:    {}", status.volume_id);
+    println!("State:     {state}");
+    if status.state == DefragState::None as i32 {
+        return;
+    }
+    println!(
+        "Chunks:    {}/{} scanned, {} rewritten, {} released, {} skipped",
+        status.chunks_scanned,
+        status.chunks_total,
+        status.chunks_rewritten,
+        status.chunks_released,
+        status.chunks_skipped
+    );
+    println!(
+        "Stored:    {} -> {}",
+        format_size(status.bytes_before),
+        format_size(status.bytes_after)
+    );
+    if !status.error.is_empty() {
+        println!("Error:     {}", status.error);
+    }
+}
+
 fn format_snapshot_state(state: i32) -> &'static str {
     match state {
         0 => "Unknown",
@@ -460,6 +589,84 @@ fn format_snapshot_state(state: i32) -> &'static str {
     }
 }
 
+/// SigV4-signed GET against a gateway admin endpoint, returning JSON.
+async fn admin_get_json(
+    endpoint: &str,
+    region: &str,
+    access_key: &str,
+    secret_key: &str,
+    path_and_query: &str,
+) -> Result<serde_json::Value> {
+    let endpoint = endpoint.trim_end_matches('/');
+    let host = endpoint
+        .split_once("://")
+        .map_or(endpoint, |(_, rest)| rest)
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    let creds = objectio_auth::presign::SigningCredentials {
+        access_key_id: access_key,
+        secret_access_key: secret_key,
+        session_token: None,
+    };
+    let signed = objectio_auth::presign::sign_request_headers(
+        "GET",
+        host,
+        path_and_query,
+        "s3",
+        region,
+        &creds,
+        &[],
+        b"",
+    );
+    let mut req = reqwest::Client::new().get(format!("{endpoint}{path_and_query}"));
+    for (k, v) in signed {
+        req = req.header(k, v);
+    }
+    let resp = req
+        .send()
+        .await
+        .with_context(|| format!("GET {endpoint}{path_and_query}"))?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        anyhow::bail!("GET {path_and_query} returned {status}: {text}");
+    }
+    Ok(resp.json().await?)
+}
+
+/// Fetch one object with a presigned GET.
+async fn fetch_s3_object(
+    http: &reqwest::Client,
+    endpoint: &str,
+    region: &str,
+    access_key: &str,
+    secret_key: &str,
+    bucket: &str,
+    key: &str,
+) -> Result<Vec<u8>> {
+    let url = objectio_auth::presign::presign_get(
+        endpoint,
+        region,
+        access_key,
+        secret_key,
+        bucket,
+        key,
+        std::time::Duration::from_mins(15),
+    );
+    let resp = http
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("GET s3://{bucket}/{key}"))?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        anyhow::bail!("GET s3://{bucket}/{key} returned {status}: {text}");
+    }
+    Ok(resp.bytes().await?.to_vec())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse command line arguments
@@ -928,6 +1135,33 @@ async fn main() -> Result<()> {
 
                     println!("Volume '{}' deleted successfully", volume_id);
                 }
+                VolumeCommands::Defrag {
+                    volume_id,
+                    min_savings_percent,
+                } => {
+                    let status = client
+                        .start_defrag(StartDefragRequest {
+                            volume_id,
+                            min_savings_percent,
+                        })
+                        .await?
+                        .into_inner();
+                    print_defrag_status(&status);
+                }
+                VolumeCommands::DefragStatus { volume_id } => {
+                    let status = client
+                        .get_defrag_status(GetDefragStatusRequest { volume_id })
+                        .await?
+                        .into_inner();
+                    print_defrag_status(&status);
+                }
+                VolumeCommands::DefragCancel { volume_id } => {
+                    let status = client
+                        .cancel_defrag(CancelDefragRequest { volume_id })
+                        .await?
+                        .into_inner();
+                    print_defrag_status(&status);
+                }
             }
         }
         Commands::Snapshot { action } => {
@@ -1510,6 +1744,151 @@ async fn main() -> Result<()> {
                 }
             }
         }
+        Commands::Meta { action } => match action {
+            MetaCommands::Restore {
+                from,
+                data_dir,
+                s3_endpoint,
+                region,
+                access_key,
+                secret_key,
+            } => {
+                use objectio_meta_store::archive::{LATEST_POINTER, join_key, parse_s3_url};
+
+                let target = data_dir.join("meta.redb");
+                if target.exists() {
+                    anyhow::bail!(
+                        "{} already exists; stop meta and move it aside before restoring",
+                        target.display()
+                    );
+                }
+
+                let (source, bytes) = if let Some((bucket, key)) = parse_s3_url(&from) {
+                    let http = reqwest::Client::new();
+                    let key = if key.ends_with(".oioarchive") {
+                        key
+                    } else {
+                        let pointer = fetch_s3_object(
+                            &http,
+                            &s3_endpoint,
+                            &region,
+                            &access_key,
+                            &secret_key,
+                            &bucket,
+                            &join_key(&key, LATEST_POINTER),
+                        )
+                        .await
+                        .context("reading latest-archive pointer")?;
+                        String::from_utf8(pointer)
+                            .context("latest-archive pointer is not UTF-8")?
+                            .trim()
+                            .to_string()
+                    };
+                    let bytes = fetch_s3_object(
+                        &http,
+                        &s3_endpoint,
+                        &region,
+                        &access_key,
+                        &secret_key,
+                        &bucket,
+                        &key,
+                    )
+                    .await?;
+                    (format!("s3://{bucket}/{key}"), bytes)
+                } else {
+                    let bytes = std::fs::read(&from).with_context(|| format!("reading {from}"))?;
+                    (from.clone(), bytes)
+                };
+
+                let archive = objectio_meta_store::MetaArchive::decode(&bytes)
+                    .with_context(|| format!("decoding {source}"))?;
+                archive
+                    .restore_to(&target)
+                    .with_context(|| format!("writing {}", target.display()))?;
+
+                println!("Restored meta store from {source}");
+                println!("  archive format:  v{}", archive.format_version);
+                println!("  created at:      {} (unix)", archive.created_at);
+                println!("  raft index:      {}", archive.last_applied_index);
+                println!("  tables:          {}", archive.tables.len());
+                println!("  rows:            {}", archive.row_count());
+                println!("  written to:      {}", target.display());
+                println!();
+                println!("Next steps:");
+                println!(
+                    "  1. Start objectio-meta on this node with --data-dir {}",
+                    data_dir.display()
+                );
+                println!(
+                    "  2. POST http://<node>:<admin-port>/init to bootstrap a single-voter cluster"
+                );
+                println!("  3. Join the remaining meta nodes (empty data dirs) as learners");
+            }
+        },
+        Commands::Advisor {
+            bucket,
+            cold_days,
+            max_objects,
+            admin_endpoint,
+            region,
+            access_key,
+            secret_key,
+            json,
+        } => {
+            let mut path =
+                format!("/_admin/advisor?cold_days={cold_days}&max_objects={max_objects}");
+            if let Some(b) = &bucket {
+                path.push_str(&format!("&bucket={b}"));
+            }
+            let report =
+                admin_get_json(&admin_endpoint, &region, &access_key, &secret_key, &path).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+
+            let u64_of = |v: &serde_json::Value, k: &str| v[k].as_u64().unwrap_or(0);
+            println!(
+                "Access observed since {} (unix); cold = untouched {cold_days}+ days",
+                u64_of(&report, "observed_since")
+            );
+            for b in report["buckets"].as_array().into_iter().flatten() {
+                println!();
+                println!(
+                    "{}  pool={} ({:.2}x)  objects={}  logical={}  raw={}  cold={}{}",
+                    b["bucket"].as_str().unwrap_or_default(),
+                    b["pool"].as_str().unwrap_or_default(),
+                    b["ec_overhead"].as_f64().unwrap_or(0.0),
+                    u64_of(b, "objects"),
+                    format_size(u64_of(b, "logical_bytes")),
+                    format_size(u64_of(b, "raw_bytes")),
+                    format_size(u64_of(b, "cold_bytes")),
+                    if b["truncated"].as_bool().unwrap_or(false) {
+                        "  (scan truncated)"
+                    } else {
+                        ""
+                    }
+                );
+                let recs = b["recommendations"].as_array().cloned().unwrap_or_default();
+                if recs.is_empty() {
+                    println!("  no recommendations");
+                }
+                for r in &recs {
+                    println!(
+                        "  [{}] {} — saves {} ({} confidence)",
+                        r["kind"].as_str().unwrap_or_default(),
+                        r["summary"].as_str().unwrap_or_default(),
+                        format_size(u64_of(r, "projected_savings_bytes")),
+                        r["confidence"].as_str().unwrap_or_default()
+                    );
+                }
+            }
+            println!();
+            println!(
+                "Total projected savings: {}",
+                format_size(u64_of(&report, "total_projected_savings_bytes"))
+            );
+        }
     }
 
     Ok(())
@@ -0,0 +1,335 @@
+//! S3 access control lists.
+//!
+//! Bucket ACLs are stored on `BucketMeta` in Meta; object ACLs on
+//! `ObjectMeta` on the OSDs. Both use the proto `AccessControlPolicy`,
+//! whose grantees are user ARNs or the two S3 groups (`AllUsers`,
+//! `AuthenticatedUsers`). The owner always holds implicit FULL_CONTROL.
+//!
+//! ACLs are consulted after bucket policy: an explicit policy Deny or
+//! Allow decides first, and the ACL settles everything the policy leaves
+//! implicit (see `s3::check_bucket_policy`). Buckets without an ACL —
+//! created before ACL support — keep the legacy "any authenticated
+//! caller" behavior, and objects without one follow their bucket's ACL.
+
+use objectio_proto::metadata::{AccessControlPolicy, AclGrant, AclPermission};
+use serde::{Deserialize, Serialize};
+
+/// Group URI matching every caller, signed or not.
+pub const ALL_USERS_URI: &str = "http://acs.amazonaws.com/groups/global/AllUsers";
+/// Group URI matching any signed caller.
+pub const AUTHENTICATED_USERS_URI: &str =
+    "http://acs.amazonaws.com/groups/global/AuthenticatedUsers";
+
+const S3_XMLNS: &str = "http://s3.amazonaws.com/doc/2006-03-01/";
+const XSI_XMLNS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// What an action's ACL permission is checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AclTarget {
+    Bucket,
+    Object,
+}
+
+/// ACL permission an S3 action needs, or `None` for owner-only actions
+/// (policy, lifecycle, versioning, ...), which no grant can confer.
+pub fn required_permission(action: &str) -> Option<(AclTarget, AclPermission)> {
+    Some(match action {
+        "s3:ListBucket" | "s3:ListBucketVersions" | "s3:ListBucketMultipartUploads" => {
+            (AclTarget::Bucket, AclPermission::Read)
+        }
+        "s3:PutObject"
+        | "s3:DeleteObject"
+        | "s3:DeleteObjectVersion"
+        | "s3:AbortMultipartUpload" => (AclTarget::Bucket, AclPermission::Write),
+        "s3:GetBucketAcl" => (AclTarget::Bucket, AclPermission::ReadAcp),
+        "s3:PutBucketAcl" => (AclTarget::Bucket, AclPermission::WriteAcp),
+        "s3:GetObject" | "s3:GetObjectVersion" => (AclTarget::Object, AclPermission::Read),
+        "s3:GetObjectAcl" => (AclTarget::Object, AclPermission::ReadAcp),
+        "s3:PutObjectAcl" => (AclTarget::Object, AclPermission::WriteAcp),
+        _ => return None,
+    })
+}
+
+/// Whether `acl` grants `permission` to `caller` (a user ARN; `None` for
+/// anonymous requests).
+pub fn allows(acl: &AccessControlPolicy, caller: Option<&str>, permission: AclPermission) -> bool {
+    if caller.is_some_and(|c| !acl.owner_id.is_empty() && c == acl.owner_id) {
+        return true;
+    }
+    acl.grants.iter().any(|g| {
+        let grantee_matches = if g.grantee_uri.is_empty() {
+            caller.is_some_and(|c| c == g.grantee_id)
+        } else {
+            g.grantee_uri == ALL_USERS_URI
+                || (g.grantee_uri == AUTHENTICATED_USERS_URI && caller.is_some())
+        };
+        grantee_matches
+            && (g.permission() == permission || g.permission() == AclPermission::FullControl)
+    })
+}
+
+/// Build the ACL for a canned ACL name (`x-amz-acl`).
+pub fn canned(name: &str, owner_id: &str) -> Result<AccessControlPolicy, String> {
+    let group = |uri: &str, permission: AclPermission| AclGrant {
+        grantee_uri: uri.to_string(),
+        permission: permission.into(),
+        ..Default::default()
+    };
+    let mut grants = vec![AclGrant {
+        grantee_id: owner_id.to_string(),
+        grantee_display_name: display_name(owner_id).to_string(),
+        permission: AclPermission::FullControl.into(),
+        ..Default::default()
+    }];
+    match name {
+        "private" => {}
+        "public-read" => grants.push(group(ALL_USERS_URI, AclPermission::Read)),
+        "public-read-write" => {
+            grants.push(group(ALL_USERS_URI, AclPermission::Read));
+            grants.push(group(ALL_USERS_URI, AclPermission::Write));
+        }
+        "authenticated-read" => grants.push(group(AUTHENTICATED_USERS_URI, AclPermission::Read)),
+        other => return Err(format!("unsupported canned ACL '{other}'")),
+    }
+    Ok(AccessControlPolicy {
+        owner_id: owner_id.to_string(),
+        owner_display_name: display_name(owner_id).to_string(),
+        grants,
+    })
+}
+
+/// ACL reported for a bucket or object that has none recorded: the owner
+/// with FULL_CONTROL, i.e. the `private` canned ACL.
+pub fn implicit(owner_id: &str) -> AccessControlPolicy {
+    canned("private", owner_id).unwrap_or_default()
+}
+
+/// Last path segment of a user ARN, used as the display name.
+fn display_name(arn: &str) -> &str {
+    arn.rsplit('/').next().unwrap_or(arn)
+}
+
+fn permission_name(p: AclPermission) -> &'static str {
+    match p {
+        AclPermission::Read => "READ",
+        AclPermission::Write => "WRITE",
+        AclPermission::ReadAcp => "READ_ACP",
+        AclPermission::WriteAcp => "WRITE_ACP",
+        AclPermission::FullControl | AclPermission::Unspecified => "FULL_CONTROL",
+    }
+}
+
+fn parse_permission(s: &str) -> Option<AclPermission> {
+    Some(match s {
+        "READ" => AclPermission::Read,
+        "WRITE" => AclPermission::Write,
+        "READ_ACP" => AclPermission::ReadAcp,
+        "WRITE_ACP" => AclPermission::WriteAcp,
+        "FULL_CONTROL" => AclPermission::FullControl,
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename = "AccessControlPolicy")]
+struct AccessControlPolicyXml {
+    #[serde(rename = "@xmlns", default, skip_deserializing)]
+    xmlns: &'static str,
+    #[serde(rename = "Owner", default)]
+    owner: Option<OwnerXml>,
+    #[serde(rename = "AccessControlList", default)]
+    access_control_list: AccessControlListXml,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OwnerXml {
+    #[serde(rename = "ID", default)]
+    id: String,
+    #[serde(rename = "DisplayName", default)]
+    display_name: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AccessControlListXml {
+    #[serde(rename = "Grant", default)]
+    grants: Vec<GrantXml>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GrantXml {
+    #[serde(rename = "Grantee")]
+    grantee: GranteeXml,
+    #[serde(rename = "Permission")]
+    permission: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GranteeXml {
+    #[serde(rename = "@xmlns:xsi", default, skip_deserializing)]
+    xmlns_xsi: &'static str,
+    #[serde(rename = "@xsi:type", default)]
+    kind: String,
+    #[serde(rename = "ID", default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(
+        rename = "DisplayName",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    display_name: Option<String>,
+    #[serde(rename = "URI", default, skip_serializing_if = "Option::is_none")]
+    uri: Option<String>,
+    #[serde(
+        rename = "EmailAddress",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    email_address: Option<String>,
+}
+
+/// Render an ACL as an S3 `AccessControlPolicy` document.
+pub fn to_xml(acl: &AccessControlPolicy) -> String {
+    let doc = AccessControlPolicyXml {
+        xmlns: S3_XMLNS,
+        owner: Some(OwnerXml {
+            id: acl.owner_id.clone(),
+            display_name: acl.owner_display_name.clone(),
+        }),
+        access_control_list: AccessControlListXml {
+            grants: acl
+                .grants
+                .iter()
+                .map(|g| GrantXml {
+                    grantee: if g.grantee_uri.is_empty() {
+                        GranteeXml {
+                            xmlns_xsi: XSI_XMLNS,
+                            kind: "CanonicalUser".to_string(),
+                            id: Some(g.grantee_id.clone()),
+                            display_name: Some(g.grantee_display_name.clone()),
+                            ..Default::default()
+                        }
+                    } else {
+                        GranteeXml {
+                            xmlns_xsi: XSI_XMLNS,
+                            kind: "Group".to_string(),
+                            uri: Some(g.grantee_uri.clone()),
+                            ..Default::default()
+                        }
+                    },
+                    permission: permission_name(g.permission()).to_string(),
+                })
+                .collect(),
+        },
+    };
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}",
+        quick_xml::se::to_string(&doc).unwrap_or_default()
+    )
+}
+
+/// Parse an `AccessControlPolicy` request body. The owner is not taken
+/// from the body: ownership can't be transferred through an ACL.
+pub fn from_xml(body: &[u8], owner_id: &str) -> Result<AccessControlPolicy, String> {
+    let doc: AccessControlPolicyXml =
+        quick_xml::de::from_reader(body).map_err(|e| format!("invalid ACL XML: {e}"))?;
+    let mut grants = Vec::with_capacity(doc.access_control_list.grants.len());
+    for g in doc.access_control_list.grants {
+        let permission = parse_permission(&g.permission)
+            .ok_or_else(|| format!("invalid permission '{}'", g.permission))?;
+        let grant = match (g.grantee.id, g.grantee.uri, g.grantee.email_address) {
+            (Some(id), None, None) => AclGrant {
+                grantee_display_name: g
+                    .grantee
+                    .display_name
+                    .unwrap_or_else(|| display_name(&id).to_string()),
+                grantee_id: id,
+                ..Default::default()
+            },
+            (None, Some(uri), None) if uri == ALL_USERS_URI || uri == AUTHENTICATED_USERS_URI => {
+                AclGrant {
+                    grantee_uri: uri,
+                    ..Default::default()
+                }
+            }
+            (None, Some(uri), None) => return Err(format!("unsupported grantee group '{uri}'")),
+            (None, None, Some(_)) => {
+                return Err("grants by email address are not supported".to_string());
+            }
+            _ => return Err("each grantee needs exactly one of ID or URI".to_string()),
+        };
+        grants.push(AclGrant {
+            permission: permission.into(),
+            ..grant
+        });
+    }
+    Ok(AccessControlPolicy {
+        owner_id: owner_id.to_string(),
+        owner_display_name: display_name(owner_id).to_string(),
+        grants,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: &str = "arn:obio:iam::objectio:user/alice";
+    const OTHER: &str = "arn:obio:iam::objectio:user/bob";
+
+    #[test]
+    fn test_canned_acls() {
+        let private = canned("private", OWNER).unwrap();
+        assert!(allows(&private, Some(OWNER), AclPermission::WriteAcp));
+        assert!(!allows(&private, Some(OTHER), AclPermission::Read));
+        assert!(!allows(&private, None, AclPermission::Read));
+
+        let public = canned("public-read", OWNER).unwrap();
+        assert!(allows(&public, None, AclPermission::Read));
+        assert!(!allows(&public, Some(OTHER), AclPermission::Write));
+
+        let authed = canned("authenticated-read", OWNER).unwrap();
+        assert!(allows(&authed, Some(OTHER), AclPermission::Read));
+        assert!(!allows(&authed, None, AclPermission::Read));
+
+        assert!(canned("log-delivery-write", OWNER).is_err());
+    }
+
+    #[test]
+    fn test_xml_roundtrip() {
+        let mut acl = canned("public-read", OWNER).unwrap();
+        acl.grants.push(AclGrant {
+            grantee_id: OTHER.to_string(),
+            grantee_display_name: "bob".to_string(),
+            permission: AclPermission::Write.into(),
+            ..Default::default()
+        });
+        let xml = to_xml(&acl);
+        assert!(xml.contains("xsi:type=\"Group\""));
+        assert!(xml.contains("<Permission>FULL_CONTROL</Permission>"));
+
+        let parsed = from_xml(xml.as_bytes(), OWNER).unwrap();
+        assert_eq!(parsed, acl);
+        assert!(allows(&parsed, Some(OTHER), AclPermission::Write));
+    }
+
+    #[test]
+    fn test_from_xml_rejects_email_grantee() {
+        let body = r#"<AccessControlPolicy><AccessControlList><Grant>
+            <Grantee><EmailAddress>a@b.c</EmailAddress></Grantee>
+            <Permission>READ</Permission></Grant></AccessControlList></AccessControlPolicy>"#;
+        assert!(from_xml(body.as_bytes(), OWNER).is_err());
+    }
+
+    #[test]
+    fn test_required_permission() {
+        assert_eq!(
+            required_permission("s3:GetObject"),
+            Some((AclTarget::Object, AclPermission::Read))
+        );
+        assert_eq!(
+            required_permission("s3:PutObject"),
+            Some((AclTarget::Bucket, AclPermission::Write))
+        );
+        assert_eq!(required_permission("s3:PutBucketPolicy"), None);
+    }
+}
@@ -18,12 +18,13 @@ use axum::{
 use bytes::Bytes;
 use objectio_auth::AuthResult;
 use objectio_proto::metadata::{
-    CreatePoolRequest, CreateTenantRequest, DeleteConfigRequest, DeletePoolRequest,
-    DeleteTenantRequest, GetConfigRequest, GetDrainStatusRequest, GetListingNodesRequest,
-    GetPoolRequest, GetRebalanceStatusRequest, GetTenantRequest, ListConfigRequest,
-    ListPoolsRequest, ListTenantsRequest, OsdAdminState as ProtoOsdAdminState, PoolConfig,
-    SetConfigRequest, SetOsdAdminStateRequest, TenantConfig, UpdatePoolRequest,
-    UpdateTenantRequest,
+    CancelEcMigrationRequest, ComponentVersion, CreatePoolRequest, CreateTenantRequest,
+    DeleteConfigRequest, DeletePoolRequest, DeleteTenantRequest, EcMigrationJob, EcMigrationState,
+    GetConfigRequest, GetDrainStatusRequest, GetEcMigrationStatusRequest, GetListingNodesRequest,
+    GetPoolRequest, GetRebalanceStatusRequest, GetTenantRequest, GetVersionInfoRequest,
+    ListConfigRequest, ListPoolsRequest, ListTenantsRequest, OsdAdminState as ProtoOsdAdminState,
+    PoolConfig, SetConfigRequest, SetOsdAdminStateRequest, StartEcMigrationRequest, TenantConfig,
+    UpdatePoolRequest, UpdateTenantRequest,
 };
 use objectio_proto::storage::storage_service_client::StorageServiceClient;
 
@@ -1237,6 +1238,7 @@ pub async fn admin_create_bucket(
             storage_class: "STANDARD".to_string(),
             region: String::new(),
             tenant,
+            acl: None,
         })
         .await
     {
@@ -2400,6 +2402,162 @@ async fn admin_rebalance_set_paused(
     }
 }
 
+// ============================================================================
+// EC profile migration — /_admin/ec-migrations
+// ============================================================================
+
+fn ec_migration_json(j: &EcMigrationJob) -> serde_json::Value {
+    let state = match j.state() {
+        EcMigrationState::EcMigrationRunning => "running",
+        EcMigrationState::EcMigrationCompleted => "completed",
+        EcMigrationState::EcMigrationCancelled => "cancelled",
+    };
+    serde_json::json!({
+        "bucket": j.bucket,
+        "source_pool": j.source_pool,
+        "target_pool": j.target_pool,
+        "state": state,
+        "objects_scanned": j.objects_scanned,
+        "objects_migrated": j.objects_migrated,
+        "objects_skipped": j.objects_skipped,
+        "objects_failed": j.objects_failed,
+        "bytes_migrated": j.bytes_migrated,
+        "started_at": j.started_at,
+        "updated_at": j.updated_at,
+        "finished_at": j.finished_at,
+        "last_error": j.last_error,
+        "requested_by": j.requested_by,
+    })
+}
+
+/// NotFound → 404, InvalidArgument → 400, AlreadyExists → 409 (another
+/// migration owns the bucket), FailedPrecondition → 503 (meta not
+/// leader; retry).
+fn ec_migration_error(e: &tonic::Status) -> Response {
+    let code = match e.code() {
+        tonic::Code::NotFound => StatusCode::NOT_FOUND,
+        tonic::Code::InvalidArgument => StatusCode::BAD_REQUEST,
+        tonic::Code::AlreadyExists => StatusCode::CONFLICT,
+        tonic::Code::FailedPrecondition => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (code, e.message().to_string()).into_response()
+}
+
+/// `GET /_admin/ec-migrations` — every EC profile migration job,
+/// finished ones included.
+pub async fn admin_list_ec_migrations(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(deny) = require_system_admin(&auth, &headers) {
+        return deny;
+    }
+    let mut meta = state.meta_client.clone();
+    match meta
+        .get_ec_migration_status(GetEcMigrationStatusRequest {
+            bucket: String::new(),
+        })
+        .await
+    {
+        Ok(r) => {
+            let jobs: Vec<serde_json::Value> =
+                r.into_inner().jobs.iter().map(ec_migration_json).collect();
+            Json(serde_json::json!({ "migrations": jobs })).into_response()
+        }
+        Err(e) => ec_migration_error(&e),
+    }
+}
+
+/// `GET /_admin/ec-migrations/{bucket}` — one bucket's migration job.
+pub async fn admin_get_ec_migration(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+    Path(bucket): Path<String>,
+) -> Response {
+    if let Some(deny) = require_system_admin(&auth, &headers) {
+        return deny;
+    }
+    let mut meta = state.meta_client.clone();
+    match meta
+        .get_ec_migration_status(GetEcMigrationStatusRequest { bucket })
+        .await
+    {
+        Ok(r) => match r.into_inner().jobs.first() {
+            Some(j) => Json(ec_migration_json(j)).into_response(),
+            None => (StatusCode::NOT_FOUND, "no EC migration for bucket").into_response(),
+        },
+        Err(e) => ec_migration_error(&e),
+    }
+}
+
+/// Body: `{"target_pool":"ec-8-3"}`
+#[derive(Debug, serde::Deserialize)]
+pub struct StartEcMigrationPayload {
+    pub target_pool: String,
+}
+
+/// `POST /_admin/ec-migrations/{bucket}` — move the bucket to
+/// `target_pool` and re-encode its existing objects in the background.
+/// Repeating the call resumes a cancelled job.
+pub async fn admin_start_ec_migration(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+    Path(bucket): Path<String>,
+    Json(body): Json<StartEcMigrationPayload>,
+) -> Response {
+    if let Some(deny) = require_system_admin(&auth, &headers) {
+        return deny;
+    }
+    let requested_by = match &auth {
+        Some(Extension(a)) => a.user_id.clone(),
+        None => "console".to_string(),
+    };
+    let mut meta = state.meta_client.clone();
+    match meta
+        .start_ec_migration(StartEcMigrationRequest {
+            bucket,
+            target_pool: body.target_pool,
+            requested_by,
+        })
+        .await
+    {
+        Ok(r) => match r.into_inner().job {
+            Some(j) => Json(ec_migration_json(&j)).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        },
+        Err(e) => ec_migration_error(&e),
+    }
+}
+
+/// `POST /_admin/ec-migrations/{bucket}/cancel` — stop a running job.
+/// Objects already migrated stay on the new profile; new writes keep
+/// using the target pool.
+pub async fn admin_cancel_ec_migration(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+    Path(bucket): Path<String>,
+) -> Response {
+    if let Some(deny) = require_system_admin(&auth, &headers) {
+        return deny;
+    }
+    let mut meta = state.meta_client.clone();
+    match meta
+        .cancel_ec_migration(CancelEcMigrationRequest { bucket })
+        .await
+    {
+        Ok(r) => match r.into_inner().job {
+            Some(j) => Json(ec_migration_json(&j)).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        },
+        Err(e) => ec_migration_error(&e),
+    }
+}
+
 /// `GET /_admin/host-provider`
 ///
 /// Returns the provider name — used by the console to decide whether
@@ -2670,6 +2828,103 @@ pub async fn admin_cluster_info(
     .into_response()
 }
 
+// ============================================================================
+// Build fingerprint
+// ============================================================================
+
+/// This gateway's own entry for the version report.
+fn gateway_version() -> ComponentVersion {
+    use objectio_common::build_info;
+    use objectio_erasure::BackendFactory;
+
+    let mut features = Vec::new();
+    if BackendFactory::available_backends()
+        .iter()
+        .any(|c| c.name == "isal")
+    {
+        features.push("isal".to_string());
+    }
+    features.extend(
+        objectio_auth::enabled_features()
+            .into_iter()
+            .map(str::to_string),
+    );
+    if cfg!(feature = "pcre2") {
+        features.push("pcre2".to_string());
+    }
+    if cfg!(feature = "hyperscan") {
+        features.push("hyperscan".to_string());
+    }
+    ComponentVersion {
+        component: "gateway".to_string(),
+        version: build_info::VERSION.to_string(),
+        git_hash: build_info::GIT_HASH.to_string(),
+        features,
+        ec_backend: BackendFactory::current_backend().name().to_string(),
+        storage_protocol: build_info::STORAGE_PROTOCOL_VERSION,
+        metadata_protocol: build_info::METADATA_PROTOCOL_VERSION,
+        ..Default::default()
+    }
+}
+
+/// GET /_admin/version — build fingerprint (version, git hash, features,
+/// EC backend, protocol revisions) of this gateway, the meta service and
+/// every OSD it knows about. `mixed_versions` and `protocol_mismatch`
+/// flag clusters mid-upgrade; OSDs meta couldn't reach carry an `error`
+/// and are left out of both checks.
+pub async fn admin_version(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(deny) = require_admin_or_session(&auth, &headers) {
+        return deny;
+    }
+    let mut components = vec![gateway_version()];
+    let mut client = state.meta_client.clone();
+    match client.get_version_info(GetVersionInfoRequest {}).await {
+        Ok(resp) => components.extend(resp.into_inner().components),
+        Err(e) => components.push(ComponentVersion {
+            component: "meta".to_string(),
+            error: e.message().to_string(),
+            ..Default::default()
+        }),
+    }
+
+    let reachable = || components.iter().filter(|c| c.error.is_empty());
+    let builds: std::collections::HashSet<_> = reachable()
+        .map(|c| (c.version.as_str(), c.git_hash.as_str()))
+        .collect();
+    let protocols: std::collections::HashSet<_> = reachable()
+        .map(|c| (c.storage_protocol, c.metadata_protocol))
+        .collect();
+
+    let entries: Vec<serde_json::Value> = components
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "component": c.component,
+                "node_id": c.node_id,
+                "address": c.address,
+                "version": c.version,
+                "git_hash": c.git_hash,
+                "features": c.features,
+                "ec_backend": c.ec_backend,
+                "storage_protocol": c.storage_protocol,
+                "metadata_protocol": c.metadata_protocol,
+                "error": c.error,
+            })
+        })
+        .collect();
+
+    Json(serde_json::json!({
+        "components": entries,
+        "mixed_versions": builds.len() > 1,
+        "protocol_mismatch": protocols.len() > 1,
+    }))
+    .into_response()
+}
+
 // ============================================================================
 // Topology + placement validation
 // ============================================================================
@@ -0,0 +1,490 @@
+//! Storage reclamation advisor.
+//!
+//! `GET /_admin/advisor` walks each bucket's inventory (Meta's listing
+//! index), groups it by top-level prefix, and combines it with:
+//!
+//! - **access times** — the last GET/HEAD this gateway served per
+//!   `(bucket, top-level prefix)`, tracked in memory since startup
+//! - **EC overhead** — raw/logical ratio of the bucket's pool versus the
+//!   cheapest enabled pool
+//! - **lifecycle** — rules already on the bucket, so covered prefixes
+//!   aren't recommended twice
+//!
+//! and recommends lifecycle expiration rules for cold prefixes and EC
+//! profile migrations (`/_admin/ec-migrations`) for cold buckets, each
+//! with projected raw-capacity savings.
+//!
+//! Access times are per gateway and reset on restart: each response
+//! reports `observed_since`, and recommendations built on a short
+//! observation window are marked low confidence.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use objectio_auth::AuthResult;
+use objectio_proto::metadata::{
+    BucketMeta, ErasureType, GetBucketLifecycleRequest, GetBucketRequest, LifecycleRule,
+    ListBucketsRequest, ListObjectsRequest, ListPoolsRequest, PoolConfig,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::admin::require_system_admin;
+use crate::s3::AppState;
+
+/// Prefixes tracked before new ones are ignored.
+const MAX_TRACKED_PREFIXES: usize = 100_000;
+
+/// Observation window below which recommendations are low confidence.
+const MIN_CONFIDENT_OBSERVATION_SECS: u64 = 7 * 86_400;
+
+const DAY_SECS: u64 = 86_400;
+
+#[derive(Default)]
+struct AccessRecord {
+    last_read_at: AtomicU64,
+    reads: AtomicU64,
+}
+
+/// Last-read tracking per `(bucket, top-level prefix)`.
+pub struct AccessTracker {
+    started_at: u64,
+    prefixes: RwLock<HashMap<(String, String), Arc<AccessRecord>>>,
+}
+
+impl Default for AccessTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccessTracker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            started_at: now_secs(),
+            prefixes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record a read of `bucket/key`.
+    pub fn record(&self, bucket: &str, key: &str) {
+        let id = (bucket.to_string(), top_level_prefix(key).to_string());
+        let record = self.prefixes.read().get(&id).cloned();
+        let record = match record {
+            Some(r) => r,
+            None => {
+                let mut prefixes = self.prefixes.write();
+                if prefixes.len() >= MAX_TRACKED_PREFIXES && !prefixes.contains_key(&id) {
+                    return;
+                }
+                Arc::clone(prefixes.entry(id).or_default())
+            }
+        };
+        record.last_read_at.store(now_secs(), Ordering::Relaxed);
+        record.reads.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// `(last_read_at, reads)` for a prefix, if it was read since startup.
+    fn get(&self, bucket: &str, prefix: &str) -> Option<(u64, u64)> {
+        self.prefixes
+            .read()
+            .get(&(bucket.to_string(), prefix.to_string()))
+            .map(|r| {
+                (
+                    r.last_read_at.load(Ordering::Relaxed),
+                    r.reads.load(Ordering::Relaxed),
+                )
+            })
+    }
+}
+
+/// Key up to and including the first `/`; empty for root-level keys.
+fn top_level_prefix(key: &str) -> &str {
+    key.find('/').map_or("", |i| &key[..=i])
+}
+
+/// Raw bytes stored per logical byte for a pool's data protection.
+fn overhead(pool: &PoolConfig) -> f64 {
+    match pool.ec_type() {
+        ErasureType::ErasureReplication => f64::from(pool.replication_count.max(1)),
+        ErasureType::ErasureLrc => {
+            f64::from(pool.ec_k + pool.ec_local_parity + pool.ec_global_parity)
+                / f64::from(pool.ec_k.max(1))
+        }
+        ErasureType::ErasureMds => f64::from(pool.ec_k + pool.ec_m) / f64::from(pool.ec_k.max(1)),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdvisorParams {
+    /// Limit the report to one bucket.
+    #[serde(default)]
+    pub bucket: Option<String>,
+    /// Objects untouched for this many days count as cold. Default 180.
+    #[serde(default)]
+    pub cold_days: Option<u32>,
+    /// Per-bucket inventory scan cap. Default 100k.
+    #[serde(default)]
+    pub max_objects: Option<u64>,
+}
+
+/// Per-prefix inventory accumulated during the scan.
+#[derive(Default)]
+struct PrefixInventory {
+    objects: u64,
+    bytes: u64,
+    cold_bytes: u64,
+    newest_modified_at: u64,
+}
+
+#[derive(Serialize)]
+struct Recommendation {
+    kind: &'static str,
+    summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lifecycle_rule: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_pool: Option<String>,
+    projected_savings_bytes: u64,
+    confidence: &'static str,
+}
+
+#[derive(Serialize)]
+struct BucketReport {
+    bucket: String,
+    pool: String,
+    objects: u64,
+    logical_bytes: u64,
+    raw_bytes: u64,
+    ec_overhead: f64,
+    cold_bytes: u64,
+    /// Inventory scan hit `max_objects`; figures cover a prefix of the
+    /// keyspace only.
+    truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_read_at: Option<u64>,
+    recommendations: Vec<Recommendation>,
+}
+
+/// `GET /_admin/advisor[?bucket=&cold_days=&max_objects=]`
+pub async fn admin_advisor(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+    Query(params): Query<AdvisorParams>,
+) -> Response {
+    if let Some(deny) = require_system_admin(&auth, &headers) {
+        return deny;
+    }
+    let cold_days = params.cold_days.unwrap_or(180).max(1);
+    let max_objects = params.max_objects.unwrap_or(100_000).max(1);
+    let mut meta = state.meta_client.clone();
+
+    let buckets: Vec<BucketMeta> = match &params.bucket {
+        Some(name) => match meta
+            .get_bucket(GetBucketRequest { name: name.clone() })
+            .await
+        {
+            Ok(r) => r.into_inner().bucket.into_iter().collect(),
+            Err(e) if e.code() == tonic::Code::NotFound => {
+                return (StatusCode::NOT_FOUND, "bucket not found").into_response();
+            }
+            Err(e) => {
+                return (StatusCode::INTERNAL_SERVER_ERROR, e.message().to_string())
+                    .into_response();
+            }
+        },
+        None => match meta
+            .list_buckets(ListBucketsRequest {
+                owner: String::new(),
+                tenant: String::new(),
+            })
+            .await
+        {
+            Ok(r) => r.into_inner().buckets,
+            Err(e) => {
+                return (StatusCode::INTERNAL_SERVER_ERROR, e.message().to_string())
+                    .into_response();
+            }
+        },
+    };
+
+    let pools: HashMap<String, PoolConfig> = match meta.list_pools(ListPoolsRequest {}).await {
+        Ok(r) => r
+            .into_inner()
+            .pools
+            .into_iter()
+            .map(|p| (p.name.clone(), p))
+            .collect(),
+        Err(e) => {
+            return (StatusCode::INTERNAL_SERVER_ERROR, e.message().to_string()).into_response();
+        }
+    };
+    let cheapest_pool = pools
+        .values()
+        .filter(|p| p.enabled)
+        .min_by(|a, b| overhead(a).total_cmp(&overhead(b)));
+
+    let now = now_secs();
+    let observed_secs = now.saturating_sub(state.access_tracker.started_at);
+    let confidence = if observed_secs >= MIN_CONFIDENT_OBSERVATION_SECS {
+        "high"
+    } else {
+        "low"
+    };
+    let cold_before = now.saturating_sub(u64::from(cold_days) * DAY_SECS);
+
+    let mut reports = Vec::with_capacity(buckets.len());
+    for bucket in buckets {
+        let pool_name = if bucket.pool.is_empty() {
+            "default".to_string()
+        } else {
+            bucket.pool.clone()
+        };
+        let pool_overhead = pools.get(&pool_name).map_or_else(
+            || f64::from(state.ec_k + state.ec_m) / f64::from(state.ec_k.max(1)),
+            overhead,
+        );
+
+        let (prefixes, truncated) =
+            match scan_inventory(&state, &bucket.name, cold_before, max_objects).await {
+                Ok(r) => r,
+                Err(e) => {
+                    return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response();
+                }
+            };
+        let rules = meta
+            .get_bucket_lifecycle(GetBucketLifecycleRequest {
+                bucket: bucket.name.clone(),
+            })
+            .await
+            .map(|r| r.into_inner().config.unwrap_or_default().rules)
+            .unwrap_or_default();
+
+        let mut report = BucketReport {
+            bucket: bucket.name.clone(),
+            pool: pool_name.clone(),
+            objects: 0,
+            logical_bytes: 0,
+            raw_bytes: 0,
+            ec_overhead: pool_overhead,
+            cold_bytes: 0,
+            truncated,
+            last_read_at: None,
+            recommendations: Vec::new(),
+        };
+
+        let mut sorted: Vec<_> = prefixes.into_iter().collect();
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+        for (prefix, inv) in sorted {
+            report.objects += inv.objects;
+            report.logical_bytes += inv.bytes;
+            report.cold_bytes += inv.cold_bytes;
+
+            let access = state.access_tracker.get(&bucket.name, &prefix);
+            if let Some((last, _)) = access {
+                report.last_read_at = report.last_read_at.max(Some(last));
+            }
+
+            // A prefix is a candidate for expiry when nothing under it was
+            // written within the cold window and nothing was read while
+            // we've been watching.
+            let cold_prefix = inv.newest_modified_at < cold_before && access.is_none();
+            if cold_prefix && inv.cold_bytes > 0 && !covered_by_rule(&rules, &prefix) {
+                let label = if prefix.is_empty() {
+                    "root-level keys".to_string()
+                } else {
+                    format!("prefix '{prefix}'")
+                };
+                report.recommendations.push(Recommendation {
+                    kind: "lifecycle-expiration",
+                    summary: format!(
+                        "{label}: {} objects not written in {cold_days}+ days and not read \
+                         since {}; expire after {cold_days} days",
+                        inv.objects, state.access_tracker.started_at
+                    ),
+                    prefix: Some(prefix.clone()),
+                    lifecycle_rule: Some(serde_json::json!({
+                        "id": format!("advisor-expire-{}", prefix.trim_end_matches('/')),
+                        "enabled": true,
+                        "prefix": prefix,
+                        "expiration_days": cold_days,
+                    })),
+                    target_pool: None,
+                    projected_savings_bytes: raw(inv.cold_bytes, pool_overhead),
+                    confidence,
+                });
+            }
+        }
+        report.raw_bytes = raw(report.logical_bytes, pool_overhead);
+
+        // Mostly-cold buckets on an expensive profile should move to the
+        // cheapest pool. Bytes already slated for expiry aren't counted.
+        if let Some(target) = cheapest_pool
+            && target.name != pool_name
+            && overhead(target) < pool_overhead
+            && report.logical_bytes > 0
+            && report.cold_bytes * 5 >= report.logical_bytes * 4
+        {
+            let expiring: u64 = report
+                .recommendations
+                .iter()
+                .map(|r| r.projected_savings_bytes)
+                .sum();
+            let remaining = report.raw_bytes.saturating_sub(expiring);
+            let savings =
+                remaining.saturating_sub(raw(remaining, overhead(target) / pool_overhead));
+            report.recommendations.push(Recommendation {
+                kind: "ec-profile",
+                summary: format!(
+                    "{}% of bytes are cold; migrate from pool '{pool_name}' ({pool_overhead:.2}x) \
+                     to '{}' ({:.2}x) via POST /_admin/ec-migrations/{}",
+                    report.cold_bytes * 100 / report.logical_bytes,
+                    target.name,
+                    overhead(target),
+                    bucket.name
+                ),
+                prefix: None,
+                lifecycle_rule: None,
+                target_pool: Some(target.name.clone()),
+                projected_savings_bytes: savings,
+                confidence,
+            });
+        }
+
+        reports.push(report);
+    }
+
+    let total_savings: u64 = reports
+        .iter()
+        .flat_map(|r| &r.recommendations)
+        .map(|r| r.projected_savings_bytes)
+        .sum();
+    Json(serde_json::json!({
+        "generated_at": now,
+        "observed_since": state.access_tracker.started_at,
+        "cold_days": cold_days,
+        "buckets": reports,
+        "total_projected_savings_bytes": total_savings,
+    }))
+    .into_response()
+}
+
+/// Page through the bucket's listing index, grouped by top-level prefix.
+async fn scan_inventory(
+    state: &AppState,
+    bucket: &str,
+    cold_before: u64,
+    max_objects: u64,
+) -> Result<(HashMap<String, PrefixInventory>, bool), String> {
+    let mut meta = state.meta_client.clone();
+    let mut prefixes: HashMap<String, PrefixInventory> = HashMap::new();
+    let mut scanned = 0u64;
+    let mut token = String::new();
+    loop {
+        let resp = meta
+            .list_objects(ListObjectsRequest {
+                bucket: bucket.to_string(),
+                prefix: String::new(),
+                delimiter: String::new(),
+                start_after: String::new(),
+                continuation_token: token.clone(),
+                max_keys: 1000,
+                include_versions: false,
+            })
+            .await
+            .map_err(|e| format!("list_objects {bucket}: {}", e.message()))?
+            .into_inner();
+        for entry in &resp.entries {
+            if entry.is_delete_marker {
+                continue;
+            }
+            let inv = prefixes
+                .entry(top_level_prefix(&entry.key).to_string())
+                .or_default();
+            inv.objects += 1;
+            inv.bytes += entry.size;
+            if entry.modified_at < cold_before {
+                inv.cold_bytes += entry.size;
+            }
+            inv.newest_modified_at = inv.newest_modified_at.max(entry.modified_at);
+            scanned += 1;
+        }
+        if !resp.is_truncated || resp.next_continuation_token.is_empty() {
+            return Ok((prefixes, false));
+        }
+        if scanned >= max_objects {
+            return Ok((prefixes, true));
+        }
+        token = resp.next_continuation_token;
+    }
+}
+
+/// Whether an enabled expiration rule already covers `prefix`.
+fn covered_by_rule(rules: &[LifecycleRule], prefix: &str) -> bool {
+    rules.iter().any(|r| {
+        r.enabled
+            && (r.expiration_days > 0 || r.expiration_date > 0)
+            && prefix.starts_with(r.prefix.as_str())
+    })
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn raw(logical: u64, overhead: f64) -> u64 {
+    (logical as f64 * overhead) as u64
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_level_prefix() {
+        assert_eq!(top_level_prefix("logs/2024/a.gz"), "logs/");
+        assert_eq!(top_level_prefix("readme.txt"), "");
+    }
+
+    #[test]
+    fn test_overhead() {
+        let mds = PoolConfig {
+            ec_type: ErasureType::ErasureMds.into(),
+            ec_k: 4,
+            ec_m: 2,
+            ..Default::default()
+        };
+        assert!((overhead(&mds) - 1.5).abs() < f64::EPSILON);
+        let repl = PoolConfig {
+            ec_type: ErasureType::ErasureReplication.into(),
+            replication_count: 3,
+            ..Default::default()
+        };
+        assert!((overhead(&repl) - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_access_tracker() {
+        let tracker = AccessTracker::new();
+        assert!(tracker.get("b", "logs/").is_none());
+        tracker.record("b", "logs/a");
+        tracker.record("b", "logs/b");
+        assert_eq!(tracker.get("b", "logs/").unwrap().1, 2);
+        assert!(tracker.get("b", "").is_none());
+    }
+}
@@ -0,0 +1,270 @@
+//! HTTP conditional requests (RFC 7232) on objects.
+//!
+//! Preconditions are checked against the ETag and `modified_at` already
+//! stored on `ObjectMeta`:
+//!   - GET / HEAD: `If-Match`, `If-Unmodified-Since` (412 on failure) and
+//!     `If-None-Match`, `If-Modified-Since` (304 on failure)
+//!   - CopyObject source: the `x-amz-copy-source-if-*` family, where every
+//!     failure is a 412
+//!   - PUT / CopyObject destination: `If-None-Match: *` (create only) and
+//!     `If-Match` (replace only that version)
+//!
+//! Order follows RFC 7232 §6: `If-Match` makes `If-Unmodified-Since`
+//! irrelevant, and `If-None-Match` makes `If-Modified-Since` irrelevant.
+//! Unparseable dates are ignored, as the RFC requires.
+
+use axum::http::{HeaderMap, header};
+
+/// Copy-source precondition headers, checked against the source object.
+pub const COPY_SOURCE_IF_MATCH: &str = "x-amz-copy-source-if-match";
+pub const COPY_SOURCE_IF_NONE_MATCH: &str = "x-amz-copy-source-if-none-match";
+pub const COPY_SOURCE_IF_MODIFIED_SINCE: &str = "x-amz-copy-source-if-modified-since";
+pub const COPY_SOURCE_IF_UNMODIFIED_SINCE: &str = "x-amz-copy-source-if-unmodified-since";
+
+/// Result of evaluating a request's preconditions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// No precondition failed; serve the request.
+    Proceed,
+    /// 304 Not Modified.
+    NotModified,
+    /// 412 Precondition Failed.
+    PreconditionFailed,
+}
+
+/// Why a conditional write was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteConflict {
+    /// `If-Match` on a key that doesn't exist (S3 answers 404 NoSuchKey).
+    NoSuchKey,
+    /// `If-Match` didn't match, or `If-None-Match` found an object.
+    PreconditionFailed,
+}
+
+struct Conditions<'a> {
+    if_match: Option<&'a str>,
+    if_none_match: Option<&'a str>,
+    if_modified_since: Option<&'a str>,
+    if_unmodified_since: Option<&'a str>,
+}
+
+fn header_str(headers: &HeaderMap, name: impl header::AsHeaderName) -> Option<&str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+/// Strip quotes and a weak `W/` prefix so stored and requested ETags
+/// compare equal however either side was written.
+fn opaque_tag(tag: &str) -> &str {
+    let tag = tag.trim();
+    let tag = tag.strip_prefix("W/").unwrap_or(tag);
+    tag.trim_matches('"')
+}
+
+/// Whether an `If-Match` / `If-None-Match` value matches `etag`.
+fn etag_matches(list: &str, etag: &str) -> bool {
+    let etag = opaque_tag(etag);
+    list.split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || opaque_tag(candidate) == etag)
+}
+
+/// Parse an HTTP date (IMF-fixdate or RFC 850) to Unix seconds.
+fn parse_http_date(value: &str) -> Option<u64> {
+    use chrono::{DateTime, NaiveDateTime};
+    let value = value.trim();
+    let secs = DateTime::parse_from_rfc2822(value)
+        .map(|dt| dt.timestamp())
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(value, "%A, %d-%b-%y %H:%M:%S GMT")
+                .map(|dt| dt.and_utc().timestamp())
+        })
+        .ok()?;
+    u64::try_from(secs).ok()
+}
+
+fn evaluate(conditions: &Conditions<'_>, etag: &str, modified_at: u64) -> Outcome {
+    if let Some(list) = conditions.if_match {
+        if !etag_matches(list, etag) {
+            return Outcome::PreconditionFailed;
+        }
+    } else if let Some(since) = conditions.if_unmodified_since.and_then(parse_http_date)
+        && modified_at > since
+    {
+        return Outcome::PreconditionFailed;
+    }
+
+    if let Some(list) = conditions.if_none_match {
+        if etag_matches(list, etag) {
+            return Outcome::NotModified;
+        }
+    } else if let Some(since) = conditions.if_modified_since.and_then(parse_http_date)
+        && modified_at <= since
+    {
+        return Outcome::NotModified;
+    }
+
+    Outcome::Proceed
+}
+
+/// Evaluate GET / HEAD preconditions against an object.
+pub fn evaluate_read(headers: &HeaderMap, etag: &str, modified_at: u64) -> Outcome {
+    let conditions = Conditions {
+        if_match: header_str(headers, header::IF_MATCH),
+        if_none_match: header_str(headers, header::IF_NONE_MATCH),
+        if_modified_since: header_str(headers, header::IF_MODIFIED_SINCE),
+        if_unmodified_since: header_str(headers, header::IF_UNMODIFIED_SINCE),
+    };
+    evaluate(&conditions, etag, modified_at)
+}
+
+/// Evaluate CopyObject's `x-amz-copy-source-if-*` headers against the
+/// source object. A copy has nothing to answer 304 with, so every failed
+/// condition is a 412.
+pub fn evaluate_copy_source(headers: &HeaderMap, etag: &str, modified_at: u64) -> Outcome {
+    let conditions = Conditions {
+        if_match: header_str(headers, COPY_SOURCE_IF_MATCH),
+        if_none_match: header_str(headers, COPY_SOURCE_IF_NONE_MATCH),
+        if_modified_since: header_str(headers, COPY_SOURCE_IF_MODIFIED_SINCE),
+        if_unmodified_since: header_str(headers, COPY_SOURCE_IF_UNMODIFIED_SINCE),
+    };
+    match evaluate(&conditions, etag, modified_at) {
+        Outcome::NotModified => Outcome::PreconditionFailed,
+        outcome => outcome,
+    }
+}
+
+/// Whether a PUT carries `If-Match` / `If-None-Match`, i.e. whether the
+/// handler has to look up the current object before writing.
+pub fn has_write_conditions(headers: &HeaderMap) -> bool {
+    headers.contains_key(header::IF_MATCH) || headers.contains_key(header::IF_NONE_MATCH)
+}
+
+/// Evaluate PUT preconditions against the current object's ETag (`None`
+/// when the key doesn't exist).
+pub fn evaluate_write(
+    headers: &HeaderMap,
+    current_etag: Option<&str>,
+) -> Result<(), WriteConflict> {
+    if let Some(list) = header_str(headers, header::IF_MATCH) {
+        match current_etag {
+            None => return Err(WriteConflict::NoSuchKey),
+            Some(etag) if !etag_matches(list, etag) => {
+                return Err(WriteConflict::PreconditionFailed);
+            }
+            Some(_) => {}
+        }
+    }
+    if let Some(list) = header_str(headers, header::IF_NONE_MATCH)
+        && current_etag.is_some_and(|etag| etag_matches(list, etag))
+    {
+        return Err(WriteConflict::PreconditionFailed);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ETAG: &str = "\"9bb58f26192e4ba00f01e2e7b136bbd8\"";
+    // Sun, 06 Nov 1994 08:49:37 GMT
+    const MODIFIED: u64 = 784_111_777;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_read_preconditions() {
+        let read = |pairs| evaluate_read(&headers(pairs), ETAG, MODIFIED);
+        assert_eq!(read(&[]), Outcome::Proceed);
+        assert_eq!(
+            read(&[(
+                "if-match",
+                "\"other\", \"9bb58f26192e4ba00f01e2e7b136bbd8\""
+            )]),
+            Outcome::Proceed
+        );
+        assert_eq!(
+            read(&[("if-match", "\"other\"")]),
+            Outcome::PreconditionFailed
+        );
+        assert_eq!(read(&[("if-none-match", "*")]), Outcome::NotModified);
+        assert_eq!(
+            read(&[("if-none-match", "W/\"9bb58f26192e4ba00f01e2e7b136bbd8\"")]),
+            Outcome::NotModified
+        );
+        assert_eq!(
+            read(&[("if-modified-since", "Sun, 06 Nov 1994 08:49:37 GMT")]),
+            Outcome::NotModified
+        );
+        assert_eq!(
+            read(&[("if-modified-since", "Sunday, 06-Nov-94 08:49:36 GMT")]),
+            Outcome::Proceed
+        );
+        assert_eq!(
+            read(&[("if-unmodified-since", "Sat, 05 Nov 1994 00:00:00 GMT")]),
+            Outcome::PreconditionFailed
+        );
+        assert_eq!(
+            read(&[("if-unmodified-since", "yesterday")]),
+            Outcome::Proceed
+        );
+    }
+
+    #[test]
+    fn test_etag_headers_take_precedence_over_dates() {
+        // If-Match holds, so the failing If-Unmodified-Since is ignored
+        let pairs = [
+            ("if-match", ETAG),
+            ("if-unmodified-since", "Sat, 05 Nov 1994 00:00:00 GMT"),
+        ];
+        assert_eq!(
+            evaluate_read(&headers(&pairs), ETAG, MODIFIED),
+            Outcome::Proceed
+        );
+
+        // If-None-Match fails to match, so If-Modified-Since is ignored
+        let pairs = [
+            ("if-none-match", "\"other\""),
+            ("if-modified-since", "Mon, 07 Nov 1994 00:00:00 GMT"),
+        ];
+        assert_eq!(
+            evaluate_read(&headers(&pairs), ETAG, MODIFIED),
+            Outcome::Proceed
+        );
+    }
+
+    #[test]
+    fn test_copy_source_and_write_preconditions() {
+        let copy = |pairs| evaluate_copy_source(&headers(pairs), ETAG, MODIFIED);
+        assert_eq!(
+            copy(&[(COPY_SOURCE_IF_NONE_MATCH, ETAG)]),
+            Outcome::PreconditionFailed
+        );
+        assert_eq!(copy(&[(COPY_SOURCE_IF_MATCH, ETAG)]), Outcome::Proceed);
+
+        let create_only = headers(&[("if-none-match", "*")]);
+        assert!(has_write_conditions(&create_only));
+        assert_eq!(evaluate_write(&create_only, None), Ok(()));
+        assert_eq!(
+            evaluate_write(&create_only, Some(ETAG)),
+            Err(WriteConflict::PreconditionFailed)
+        );
+
+        let replace = headers(&[("if-match", ETAG)]);
+        assert_eq!(evaluate_write(&replace, Some(ETAG)), Ok(()));
+        assert_eq!(
+            evaluate_write(&replace, Some("\"other\"")),
+            Err(WriteConflict::PreconditionFailed)
+        );
+        assert_eq!(
+            evaluate_write(&replace, None),
+            Err(WriteConflict::NoSuchKey)
+        );
+    }
+}
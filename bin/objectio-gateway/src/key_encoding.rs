@@ -0,0 +1,132 @@
+//! Object key encoding rules shared by the listing and copy paths.
+//!
+//! S3 keys are arbitrary UTF-8 up to 1024 bytes, including spaces, `+`,
+//! and control characters. Control characters can't appear in an XML 1.0
+//! document, so clients ask for `encoding-type=url` and get every key,
+//! prefix, delimiter and marker back percent-encoded. `x-amz-copy-source`
+//! arrives percent-encoded too (with an optional `?versionId=`), and `+`
+//! there is a literal plus, not a space.
+
+/// Maximum key length in bytes.
+pub const MAX_KEY_LEN: usize = 1024;
+
+/// Validate an `encoding-type` query parameter. Returns whether keys
+/// should be URL-encoded; `url` is the only value S3 defines.
+pub fn parse_encoding_type(encoding_type: Option<&str>) -> Result<bool, String> {
+    match encoding_type {
+        None => Ok(false),
+        Some(t) if t.eq_ignore_ascii_case("url") => Ok(true),
+        Some(t) => Err(format!("Invalid Encoding Method specified in Request: {t}")),
+    }
+}
+
+/// URL-encode a key for an `encoding-type=url` listing. `/` is left as is,
+/// matching S3, so prefixes stay readable.
+pub fn encode_key(key: String, url_encode: bool) -> String {
+    if url_encode {
+        urlencoding::encode(&key).replace("%2F", "/")
+    } else {
+        key
+    }
+}
+
+/// Check a key against S3's limits: non-empty and at most 1024 bytes.
+pub fn validate_key(key: &str) -> Result<(), (&'static str, &'static str)> {
+    if key.is_empty() {
+        return Err(("InvalidArgument", "Object key must not be empty"));
+    }
+    if key.len() > MAX_KEY_LEN {
+        return Err(("KeyTooLongError", "Your key is too long"));
+    }
+    Ok(())
+}
+
+/// A parsed `x-amz-copy-source` header.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CopySource {
+    pub bucket: String,
+    pub key: String,
+    pub version_id: Option<String>,
+}
+
+/// Parse `x-amz-copy-source` (`[/]bucket/key[?versionId=id]`, with the key
+/// percent-encoded). The query is split off before decoding so a key
+/// containing an encoded `?` survives.
+pub fn parse_copy_source(raw: &str) -> Result<CopySource, String> {
+    let (path, query) = raw.split_once('?').unwrap_or((raw, ""));
+    let version_id = query
+        .split('&')
+        .find_map(|kv| kv.strip_prefix("versionId="))
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            urlencoding::decode(v)
+                .map(std::borrow::Cow::into_owned)
+                .map_err(|_| "Invalid versionId in x-amz-copy-source".to_string())
+        })
+        .transpose()?;
+    let decoded = urlencoding::decode(path)
+        .map_err(|_| "x-amz-copy-source is not valid UTF-8 once decoded".to_string())?;
+    let (bucket, key) = decoded
+        .trim_start_matches('/')
+        .split_once('/')
+        .ok_or_else(|| "Invalid x-amz-copy-source format".to_string())?;
+    if bucket.is_empty() || key.is_empty() {
+        return Err("Invalid x-amz-copy-source format".to_string());
+    }
+    Ok(CopySource {
+        bucket: bucket.to_string(),
+        key: key.to_string(),
+        version_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_key_edge_cases() {
+        assert_eq!(encode_key("a b/c+d".into(), true), "a%20b/c%2Bd");
+        assert_eq!(encode_key("tab\there".into(), true), "tab%09here");
+        assert_eq!(encode_key("ctl\u{1}".into(), true), "ctl%01");
+        assert_eq!(
+            encode_key("日本/語".into(), true),
+            "%E6%97%A5%E6%9C%AC/%E8%AA%9E"
+        );
+        assert_eq!(encode_key("a b".into(), false), "a b");
+    }
+
+    #[test]
+    fn test_parse_encoding_type() {
+        assert_eq!(parse_encoding_type(None), Ok(false));
+        assert_eq!(parse_encoding_type(Some("url")), Ok(true));
+        assert!(parse_encoding_type(Some("base64")).is_err());
+    }
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("a").is_ok());
+        assert!(validate_key(&"k".repeat(MAX_KEY_LEN)).is_ok());
+        assert_eq!(
+            validate_key(&"k".repeat(MAX_KEY_LEN + 1)).unwrap_err().0,
+            "KeyTooLongError"
+        );
+        assert!(validate_key("").is_err());
+    }
+
+    #[test]
+    fn test_parse_copy_source() {
+        let src = parse_copy_source("/bkt/dir/a%20b%2Bc.txt").unwrap();
+        assert_eq!(src.bucket, "bkt");
+        assert_eq!(src.key, "dir/a b+c.txt");
+        assert_eq!(src.version_id, None);
+
+        // '+' is literal and an encoded '?' belongs to the key
+        let src = parse_copy_source("bkt/q%3F+x?versionId=v1").unwrap();
+        assert_eq!(src.key, "q?+x");
+        assert_eq!(src.version_id.as_deref(), Some("v1"));
+
+        assert!(parse_copy_source("bkt").is_err());
+        assert!(parse_copy_source("bkt/%FF%FE").is_err());
+    }
+}
@@ -12,6 +12,11 @@
 //! 3. The per-object DEK is bound to the caller-supplied encryption context
 //!    (AES-GCM associated data), so a blob wrapped with one context cannot
 //!    be unwrapped with a different context.
+//!
+//! External backends implement the same [`KmsProvider`] trait: Vault
+//! Transit, AWS KMS, and a static key file. The gateway's SSE-KMS path only
+//! sees the trait, and records the key id in object metadata so GET can
+//! ask the same backend to unwrap.
 
 use objectio_kms::{
     DEK_LEN, GeneratedDataKey, KmsError, KmsProvider, MASTER_KEY_LEN, MasterKey,
@@ -339,6 +344,294 @@ impl objectio_kms::KmsProvider for VaultKmsProvider {
     }
 }
 
+// ============================================================================
+// AWS KMS provider
+// ============================================================================
+
+/// AWS KMS backend. Talks to the KMS JSON API (`TrentService.*`) with
+/// SigV4-signed requests; DEKs are generated and unwrapped by AWS, so key
+/// material never leaves KMS.
+///
+/// The encryption context is passed through as KMS `EncryptionContext`, so
+/// AWS enforces the same AAD binding as the local backend and it shows up
+/// in `CloudTrail`. The wrapped DEK stored in object metadata is the raw
+/// `CiphertextBlob`.
+pub struct AwsKmsProvider {
+    client: reqwest::Client,
+    /// `https://kms.{region}.amazonaws.com` unless overridden (VPC
+    /// endpoints, LocalStack).
+    endpoint: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: String,
+}
+
+impl AwsKmsProvider {
+    /// Build from explicit parts. An empty `endpoint` selects the public
+    /// regional endpoint.
+    #[must_use]
+    pub fn from_parts(
+        client: reqwest::Client,
+        region: String,
+        endpoint: String,
+        access_key_id: String,
+        secret_access_key: String,
+        session_token: String,
+    ) -> Self {
+        let endpoint = if endpoint.is_empty() {
+            format!("https://kms.{region}.amazonaws.com")
+        } else {
+            endpoint.trim_end_matches('/').to_string()
+        };
+        Self {
+            client,
+            endpoint,
+            region,
+            access_key_id,
+            secret_access_key,
+            session_token,
+        }
+    }
+
+    /// POST one `TrentService` action. Returns the parsed JSON body, or
+    /// the AWS error type (`NotFoundException`, ...) and message.
+    async fn call(
+        &self,
+        action: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, (String, String)> {
+        let payload = serde_json::to_vec(body).unwrap_or_default();
+        let host = self
+            .endpoint
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        let target = format!("TrentService.{action}");
+        let creds = objectio_auth::presign::SigningCredentials {
+            access_key_id: &self.access_key_id,
+            secret_access_key: &self.secret_access_key,
+            session_token: (!self.session_token.is_empty()).then_some(self.session_token.as_str()),
+        };
+        let signed = objectio_auth::presign::sign_request_headers(
+            "POST",
+            host,
+            "/",
+            "kms",
+            &self.region,
+            &creds,
+            &[
+                ("content-type", "application/x-amz-json-1.1"),
+                ("x-amz-target", &target),
+            ],
+            &payload,
+        );
+
+        let mut req = self
+            .client
+            .post(format!("{}/", self.endpoint))
+            .header("content-type", "application/x-amz-json-1.1")
+            .header("x-amz-target", &target);
+        for (name, value) in signed {
+            req = req.header(name, value);
+        }
+        let resp = req
+            .body(payload)
+            .send()
+            .await
+            .map_err(|e| ("Transport".to_string(), e.to_string()))?;
+        let status = resp.status();
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| ("Transport".to_string(), e.to_string()))?;
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap_or_default();
+        if !status.is_success() {
+            // `__type` is either `NotFoundException` or a namespaced
+            // `com.amazonaws.kms#NotFoundException`.
+            let kind = json["__type"]
+                .as_str()
+                .unwrap_or("Unknown")
+                .rsplit('#')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let message = json["message"]
+                .as_str()
+                .or_else(|| json["Message"].as_str())
+                .unwrap_or(&text)
+                .to_string();
+            return Err((kind, format!("{status}: {message}")));
+        }
+        Ok(json)
+    }
+
+    fn map_error(key_id: &str, (kind, message): (String, String)) -> objectio_kms::KmsError {
+        match kind.as_str() {
+            "NotFoundException" => objectio_kms::KmsError::KeyNotFound(key_id.to_string()),
+            "InvalidCiphertextException" | "IncorrectKeyException" => {
+                objectio_kms::KmsError::InvalidWrap(format!("aws kms: {message}"))
+            }
+            _ => objectio_kms::KmsError::ProviderError(format!("aws kms {kind}: {message}")),
+        }
+    }
+
+    fn decode_dek(json: &serde_json::Value) -> Result<[u8; DEK_LEN], objectio_kms::KmsError> {
+        use base64::Engine;
+        let plaintext = json["Plaintext"]
+            .as_str()
+            .ok_or_else(|| objectio_kms::KmsError::ProviderError("aws kms: no Plaintext".into()))?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(plaintext)
+            .map_err(|e| objectio_kms::KmsError::ProviderError(format!("aws kms b64: {e}")))?;
+        bytes.try_into().map_err(|b: Vec<u8>| {
+            objectio_kms::KmsError::ProviderError(format!(
+                "aws kms returned {}-byte DEK, expected {DEK_LEN}",
+                b.len()
+            ))
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl objectio_kms::KmsProvider for AwsKmsProvider {
+    async fn generate_data_key(
+        &self,
+        key_id: &str,
+        encryption_context: &std::collections::HashMap<String, String>,
+    ) -> Result<objectio_kms::GeneratedDataKey, objectio_kms::KmsError> {
+        use base64::Engine;
+        let mut body = serde_json::json!({ "KeyId": key_id, "KeySpec": "AES_256" });
+        if !encryption_context.is_empty() {
+            body["EncryptionContext"] = serde_json::json!(encryption_context);
+        }
+        let json = self
+            .call("GenerateDataKey", &body)
+            .await
+            .map_err(|e| Self::map_error(key_id, e))?;
+        let plaintext_dek = Self::decode_dek(&json)?;
+        let blob = json["CiphertextBlob"].as_str().ok_or_else(|| {
+            objectio_kms::KmsError::ProviderError("aws kms: no CiphertextBlob".into())
+        })?;
+        let wrapped_dek = base64::engine::general_purpose::STANDARD
+            .decode(blob)
+            .map_err(|e| objectio_kms::KmsError::ProviderError(format!("aws kms b64: {e}")))?;
+        Ok(objectio_kms::GeneratedDataKey {
+            plaintext_dek,
+            wrapped_dek,
+        })
+    }
+
+    async fn decrypt(
+        &self,
+        key_id: &str,
+        wrapped_dek: &[u8],
+        encryption_context: &std::collections::HashMap<String, String>,
+    ) -> Result<[u8; DEK_LEN], objectio_kms::KmsError> {
+        use base64::Engine;
+        let mut body = serde_json::json!({
+            "KeyId": key_id,
+            "CiphertextBlob": base64::engine::general_purpose::STANDARD.encode(wrapped_dek),
+        });
+        if !encryption_context.is_empty() {
+            body["EncryptionContext"] = serde_json::json!(encryption_context);
+        }
+        let json = self
+            .call("Decrypt", &body)
+            .await
+            .map_err(|e| Self::map_error(key_id, e))?;
+        Self::decode_dek(&json)
+    }
+
+    async fn key_exists(&self, key_id: &str) -> Result<bool, objectio_kms::KmsError> {
+        match self
+            .call("DescribeKey", &serde_json::json!({ "KeyId": key_id }))
+            .await
+        {
+            Ok(json) => Ok(json["KeyMetadata"]["Enabled"].as_bool().unwrap_or(false)),
+            Err((kind, _)) if kind == "NotFoundException" => Ok(false),
+            Err(e) => Err(Self::map_error(key_id, e)),
+        }
+    }
+}
+
+// ============================================================================
+// Static key-file provider
+// ============================================================================
+
+/// KMS backend backed by a JSON file of pre-provisioned keys:
+/// `{"key-id": "<base64 32-byte key>", ...}`.
+///
+/// For air-gapped clusters where keys are distributed out of band (config
+/// management, a mounted Kubernetes secret). Keys are loaded once at
+/// construction; DEKs are wrapped with AES-256-GCM bound to the encryption
+/// context, same as the local backend. Rotating a key means adding a new
+/// id — removing an id makes every object under it unreadable.
+pub struct StaticKmsProvider {
+    keys: HashMap<String, [u8; MASTER_KEY_LEN]>,
+}
+
+impl StaticKmsProvider {
+    /// Load keys from `path`.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| format!("read key file {path}: {e}"))?;
+        Self::parse(&text)
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        use base64::Engine;
+        let raw: HashMap<String, String> =
+            serde_json::from_str(text).map_err(|e| format!("key file is not a JSON map: {e}"))?;
+        let mut keys = HashMap::with_capacity(raw.len());
+        for (id, b64) in raw {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(b64.trim())
+                .map_err(|e| format!("key {id}: invalid base64: {e}"))?;
+            let key: [u8; MASTER_KEY_LEN] = bytes.try_into().map_err(|b: Vec<u8>| {
+                format!("key {id}: {} bytes, expected {MASTER_KEY_LEN}", b.len())
+            })?;
+            keys.insert(id, key);
+        }
+        Ok(Self { keys })
+    }
+
+    fn key(&self, key_id: &str) -> Result<&[u8; MASTER_KEY_LEN], KmsError> {
+        self.keys
+            .get(key_id)
+            .ok_or_else(|| KmsError::KeyNotFound(key_id.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl KmsProvider for StaticKmsProvider {
+    async fn generate_data_key(
+        &self,
+        key_id: &str,
+        encryption_context: &HashMap<String, String>,
+    ) -> Result<GeneratedDataKey, KmsError> {
+        let kek = self.key(key_id)?;
+        let plaintext_dek = objectio_kms::generate_dek();
+        let wrapped_dek = wrap_dek_with_context(kek, &plaintext_dek, encryption_context);
+        Ok(GeneratedDataKey {
+            plaintext_dek,
+            wrapped_dek,
+        })
+    }
+
+    async fn decrypt(
+        &self,
+        key_id: &str,
+        wrapped_dek: &[u8],
+        encryption_context: &HashMap<String, String>,
+    ) -> Result<[u8; DEK_LEN], KmsError> {
+        unwrap_dek_with_context(self.key(key_id)?, wrapped_dek, encryption_context)
+    }
+
+    async fn key_exists(&self, key_id: &str) -> Result<bool, KmsError> {
+        Ok(self.keys.contains_key(key_id))
+    }
+}
+
 // ============================================================================
 // Backend selection + provider construction
 // ============================================================================
@@ -362,6 +655,22 @@ pub enum KmsBackendConfig {
         /// Raw token — persisted in meta. `GET /_admin/kms/config` redacts it.
         token: String,
     },
+    /// AWS KMS. Empty credentials fall back to the `AWS_*` env vars.
+    Aws {
+        region: String,
+        /// Override for VPC endpoints / LocalStack; empty = public endpoint.
+        #[serde(default)]
+        endpoint: String,
+        #[serde(default)]
+        access_key_id: String,
+        /// Persisted in meta; `GET /_admin/kms/config` redacts it.
+        #[serde(default)]
+        secret_access_key: String,
+        #[serde(default)]
+        session_token: String,
+    },
+    /// Pre-provisioned keys from a JSON file on the gateway's filesystem.
+    Static { path: String },
 }
 
 fn default_transit_path() -> String {
@@ -390,6 +699,26 @@ impl KmsBackendConfig {
                     }
                 }
             }
+            "aws" => {
+                let region = std::env::var("AWS_REGION")
+                    .or_else(|_| std::env::var("AWS_DEFAULT_REGION"))
+                    .unwrap_or_default();
+                if region.is_empty() {
+                    Self::Disabled
+                } else {
+                    Self::Aws {
+                        region,
+                        endpoint: std::env::var("AWS_KMS_ENDPOINT").unwrap_or_default(),
+                        access_key_id: String::new(),
+                        secret_access_key: String::new(),
+                        session_token: String::new(),
+                    }
+                }
+            }
+            "static" => match std::env::var("OBJECTIO_KMS_KEY_FILE") {
+                Ok(path) if !path.is_empty() => Self::Static { path },
+                _ => Self::Disabled,
+            },
             _ => Self::Disabled,
         }
     }
@@ -401,6 +730,8 @@ impl KmsBackendConfig {
             Self::Disabled => "disabled",
             Self::Local => "local",
             Self::Vault { .. } => "vault",
+            Self::Aws { .. } => "aws",
+            Self::Static { .. } => "static",
         }
     }
 }
@@ -507,6 +838,62 @@ pub fn build_kms_provider(
                 Some(Arc::new(provider) as Arc<dyn objectio_kms::KmsProvider>),
             )
         }
+        KmsBackendConfig::Aws {
+            region,
+            endpoint,
+            access_key_id,
+            secret_access_key,
+            session_token,
+        } => {
+            let env = |name: &str, value: &str| {
+                if value.is_empty() {
+                    std::env::var(name).unwrap_or_default()
+                } else {
+                    value.to_string()
+                }
+            };
+            let access_key_id = env("AWS_ACCESS_KEY_ID", access_key_id);
+            let secret_access_key = env("AWS_SECRET_ACCESS_KEY", secret_access_key);
+            if region.is_empty() || access_key_id.is_empty() || secret_access_key.is_empty() {
+                tracing::warn!("KMS backend=aws but region/credentials missing; SSE-KMS disabled");
+                return (None, None);
+            }
+            let client = match reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+            {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::error!("failed to build aws kms reqwest client: {e}");
+                    return (None, None);
+                }
+            };
+            let provider = AwsKmsProvider::from_parts(
+                client,
+                region.clone(),
+                endpoint.clone(),
+                access_key_id,
+                secret_access_key,
+                env("AWS_SESSION_TOKEN", session_token),
+            );
+            (
+                None,
+                Some(Arc::new(provider) as Arc<dyn objectio_kms::KmsProvider>),
+            )
+        }
+        KmsBackendConfig::Static { path } => match StaticKmsProvider::load(path) {
+            Ok(provider) => {
+                tracing::info!("KMS backend=static loaded {} key(s)", provider.keys.len());
+                (
+                    None,
+                    Some(Arc::new(provider) as Arc<dyn objectio_kms::KmsProvider>),
+                )
+            }
+            Err(e) => {
+                tracing::error!("KMS backend=static: {e}; SSE-KMS disabled");
+                (None, None)
+            }
+        },
     }
 }
 
@@ -1017,6 +1404,16 @@ enum KmsConfigView {
         /// raw token bytes never leave the gateway process.
         token_set: bool,
     },
+    Aws {
+        region: String,
+        endpoint: String,
+        access_key_id: String,
+        /// `true` when a secret key is persisted (redacted in the view).
+        secret_set: bool,
+    },
+    Static {
+        path: String,
+    },
 }
 
 fn redact_config(cfg: &KmsBackendConfig) -> KmsConfigView {
@@ -1032,6 +1429,19 @@ fn redact_config(cfg: &KmsBackendConfig) -> KmsConfigView {
             transit_path: transit_path.clone(),
             token_set: !token.is_empty(),
         },
+        KmsBackendConfig::Aws {
+            region,
+            endpoint,
+            access_key_id,
+            secret_access_key,
+            ..
+        } => KmsConfigView::Aws {
+            region: region.clone(),
+            endpoint: endpoint.clone(),
+            access_key_id: access_key_id.clone(),
+            secret_set: !secret_access_key.is_empty(),
+        },
+        KmsBackendConfig::Static { path } => KmsConfigView::Static { path: path.clone() },
     }
 }
 
@@ -1086,11 +1496,14 @@ pub async fn admin_kms_put_config(
             );
         }
     };
-    // External KMS backends (Vault) require an Enterprise license. The
-    // built-in Local backend is always available because it's what backs
-    // SSE-S3 on Community too.
-    if matches!(new_cfg, KmsBackendConfig::Vault { .. })
-        && let Err(r) = crate::license_gate::require_feature(&state, objectio_license::Feature::Kms)
+    // External KMS backends (Vault, AWS, static key file) require an
+    // Enterprise license. The built-in Local backend is always available
+    // because it's what backs SSE-S3 on Community too.
+    if !matches!(
+        new_cfg,
+        KmsBackendConfig::Disabled | KmsBackendConfig::Local
+    ) && let Err(r) =
+        crate::license_gate::require_feature(&state, objectio_license::Feature::Kms)
     {
         return r;
     }
@@ -1103,6 +1516,27 @@ pub async fn admin_kms_put_config(
             *token = old;
         }
     }
+    // Same for the AWS secret key (and its session token).
+    if let KmsBackendConfig::Aws {
+        secret_access_key,
+        session_token,
+        ..
+    } = &mut new_cfg
+        && secret_access_key.is_empty()
+    {
+        let existing = load_backend_config_from_meta(state.meta_client.clone()).await;
+        if let Some(KmsBackendConfig::Aws {
+            secret_access_key: old_secret,
+            session_token: old_token,
+            ..
+        }) = existing
+        {
+            *secret_access_key = old_secret;
+            if session_token.is_empty() {
+                *session_token = old_token;
+            }
+        }
+    }
     let bytes = serde_json::to_vec(&new_cfg).unwrap_or_default();
     let actor = auth
         .as_ref()
@@ -1240,3 +1674,43 @@ pub async fn admin_kms_test(
         .body(axum::body::Body::from(result.to_string()))
         .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+
+    #[tokio::test]
+    async fn test_static_provider_round_trip() {
+        let key = base64::engine::general_purpose::STANDARD.encode([7u8; MASTER_KEY_LEN]);
+        let provider = StaticKmsProvider::parse(&format!(r#"{{"app-key": "{key}"}}"#)).unwrap();
+        let context = HashMap::from([("bucket".to_string(), "b".to_string())]);
+
+        let generated = provider
+            .generate_data_key("app-key", &context)
+            .await
+            .unwrap();
+        let dek = provider
+            .decrypt("app-key", &generated.wrapped_dek, &context)
+            .await
+            .unwrap();
+        assert_eq!(dek, generated.plaintext_dek);
+
+        assert!(
+            provider
+                .decrypt("app-key", &generated.wrapped_dek, &HashMap::new())
+                .await
+                .is_err()
+        );
+        assert!(matches!(
+            provider.generate_data_key("other", &context).await,
+            Err(KmsError::KeyNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_static_provider_rejects_short_key() {
+        let key = base64::engine::general_purpose::STANDARD.encode([7u8; 16]);
+        assert!(StaticKmsProvider::parse(&format!(r#"{{"k": "{key}"}}"#)).is_err());
+    }
+}
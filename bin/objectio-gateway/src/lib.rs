@@ -3,21 +3,32 @@
 //! This binary provides the S3-compatible HTTP API.
 //! Credentials are managed by the metadata service for persistence.
 
+pub mod acl;
 pub mod admin;
+pub mod advisor;
 pub mod auth_middleware;
 pub mod chunked_decode;
+pub mod conditional;
 pub mod console_auth;
 pub mod grep;
 pub mod grep_engine;
 pub mod host_provider;
 pub mod iceberg_auth;
+pub mod key_encoding;
 pub mod kms;
 pub mod license_gate;
 pub mod lifecycle;
 pub mod metrics_middleware;
+pub mod object_lock;
 pub mod osd_pool;
+pub mod prefetch;
+pub mod prefix_delete;
+pub mod request_shaping;
 pub mod s3;
 pub mod scatter_gather;
+pub mod select;
+pub mod select_engine;
+pub mod sftp;
 
 use anyhow::Result;
 use auth_middleware::{AuthState, auth_layer, optional_auth_layer};
@@ -228,9 +239,12 @@ pub struct Args {
     pub master_key_env: String,
 
     /// KMS backend used for SSE-KMS operations: `local` (keys stored in meta,
-    /// wrapped by the service master key) or `vault` (HashiCorp Vault Transit
-    /// engine). Vault reads config from `VAULT_ADDR` / `VAULT_TOKEN` /
-    /// `VAULT_TRANSIT_PATH` env vars. SSE-S3 works regardless of this flag
+    /// wrapped by the service master key), `vault` (HashiCorp Vault Transit
+    /// engine), `aws` (AWS KMS) or `static` (keys from a JSON file). Vault
+    /// reads config from `VAULT_ADDR` / `VAULT_TOKEN` / `VAULT_TRANSIT_PATH`;
+    /// AWS from `AWS_REGION` / `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY`
+    /// / `AWS_SESSION_TOKEN` (optionally `AWS_KMS_ENDPOINT`); static from
+    /// `OBJECTIO_KMS_KEY_FILE`. SSE-S3 works regardless of this flag
     /// and always uses the service master key.
     #[arg(long, default_value = "local")]
     pub kms_backend: String,
@@ -347,6 +361,76 @@ pub struct Args {
     )]
     pub host_provider_osd_sts: String,
 
+    /// Total concurrent S3 requests the gateway admits, split into
+    /// per-operation-class pools (read / write / list / delete) by
+    /// `--shaping-shares`. Requests beyond their class's pool queue, then
+    /// get 503 SlowDown. 0 (default) disables request shaping.
+    #[arg(long, default_value = "0")]
+    pub shaping_max_concurrency: usize,
+
+    /// Relative pool weights per operation class, e.g.
+    /// `read=40,write=40,list=10,delete=10`. Omitted classes get 1.
+    #[arg(long, default_value = "read=40,write=40,list=10,delete=10")]
+    pub shaping_shares: String,
+
+    /// How long a request waits for a slot in its class pool before the
+    /// gateway answers 503 SlowDown.
+    #[arg(long, default_value = "2000")]
+    pub shaping_queue_timeout_ms: u64,
+
+    /// Decoded stripe cache size in MiB (0 disables). Serves repeat GETs
+    /// of hot objects from memory; `/_admin/prefetch` warms it ahead of
+    /// load.
+    #[arg(long, default_value_t = 256)]
+    pub stripe_cache_mb: u64,
+
+    /// Proxy for internal gRPC connections to Meta and OSDs:
+    /// `http://[user:pass@]host:port` (CONNECT), `socks5://…` or
+    /// `socks5h://…` (proxy resolves names).
+    #[arg(long, env = "OBJECTIO_RPC_PROXY")]
+    pub rpc_proxy: Option<objectio_client::Proxy>,
+
+    /// Internal endpoints that bypass `--rpc-proxy`. Comma-separated
+    /// hosts, `host:port`, `.domain` suffixes or `*`.
+    #[arg(long, env = "OBJECTIO_RPC_NO_PROXY", value_delimiter = ',')]
+    pub rpc_no_proxy: Vec<String>,
+
+    /// Per-endpoint override, `PATTERN=direct` or `PATTERN=<proxy url>`,
+    /// checked in order before `--rpc-proxy`. Repeatable.
+    #[arg(long)]
+    pub rpc_route: Vec<objectio_client::Route>,
+
+    /// Static address for an internal host name, `HOST=IP[,IP...]`.
+    /// Repeatable; takes precedence over DNS.
+    #[arg(long, value_parser = objectio_client::net::parse_host_override)]
+    pub rpc_resolve: Vec<(String, Vec<std::net::IpAddr>)>,
+
+    /// DNS server (`ip:port`) for resolving internal host names instead of
+    /// the system resolver. Repeatable; tried in order.
+    #[arg(long)]
+    pub rpc_dns_server: Vec<std::net::SocketAddr>,
+
+    /// How often to open connections to newly listed OSDs ahead of
+    /// traffic, in seconds (0 disables the pre-warmer).
+    #[arg(long, default_value_t = 300)]
+    pub osd_prewarm_interval_secs: u64,
+
+    /// Listen address for the SFTP frontend (disabled when unset). Users
+    /// log in with access key ID / secret key as user name / password.
+    #[arg(long)]
+    pub sftp_listen: Option<SocketAddr>,
+
+    /// SSH host key for the SFTP frontend; an Ed25519 key is generated
+    /// here on first start.
+    #[arg(long, default_value = "/var/lib/objectio/sftp_host_ed25519_key")]
+    pub sftp_host_key: std::path::PathBuf,
+
+    /// Confine an SFTP user to a bucket or prefix, `USER=bucket[/prefix]`
+    /// where USER is an access key ID or user ID. Repeatable; unmapped
+    /// users see their buckets at `/`.
+    #[arg(long)]
+    pub sftp_home: Vec<sftp::SftpHome>,
+
     /// Log level
     #[arg(long, default_value = "info")]
     pub log_level: String,
@@ -423,16 +507,40 @@ pub async fn run(
     };
     s3_metrics().set_protection_config(protection_config);
 
+    // Proxy / resolver settings shared by the Meta client and the OSD pool
+    let net_config = objectio_client::NetConfig {
+        proxy: args.rpc_proxy.clone(),
+        no_proxy: args.rpc_no_proxy.clone(),
+        routes: args.rpc_route.clone(),
+        hosts: args.rpc_resolve.iter().cloned().collect(),
+        nameservers: args.rpc_dns_server.clone(),
+        ..Default::default()
+    };
+    if !net_config.is_default() {
+        info!(
+            "Internal RPC network: proxy={}, {} route(s), {} host override(s), {} DNS server(s)",
+            args.rpc_proxy.is_some(),
+            args.rpc_route.len(),
+            args.rpc_resolve.len(),
+            args.rpc_dns_server.len()
+        );
+    }
+
     // Connect to metadata service
-    let meta_client = MetadataServiceClient::connect(args.meta_endpoint.clone())
-        .await
-        .map_err(|e| anyhow::anyhow!("Failed to connect to metadata service: {}", e))?;
+    let meta_endpoint = tonic::transport::Endpoint::new(args.meta_endpoint.clone())
+        .map_err(|e| anyhow::anyhow!("Invalid --meta-endpoint: {}", e))?;
+    let meta_client = MetadataServiceClient::new(
+        net_config
+            .connect(meta_endpoint)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to connect to metadata service: {}", e))?,
+    );
 
     info!("Connected to metadata service");
     info!("Credentials are managed by the metadata service");
 
     // Create OSD connection pool
-    let osd_pool = Arc::new(OsdPool::new());
+    let osd_pool = Arc::new(OsdPool::new().with_net_config(net_config));
 
     // Connect to initial OSD (more will be discovered via placement)
     // Generate a temporary node ID for the initial OSD
@@ -653,6 +761,15 @@ pub async fn run(
         )
     };
 
+    // Open connections to listed OSDs ahead of the first request
+    if args.osd_prewarm_interval_secs > 0 {
+        osd_pool::spawn_prewarmer(
+            meta_client.clone(),
+            Arc::clone(&osd_pool),
+            std::time::Duration::from_secs(args.osd_prewarm_interval_secs),
+        );
+    }
+
     // Start lifecycle background worker
     lifecycle::spawn_lifecycle_worker(
         meta_client.clone(),
@@ -808,6 +925,10 @@ pub async fn run(
         license: parking_lot::RwLock::new(Arc::new(license)),
         self_topology,
         host_provider,
+        prefix_delete_jobs: prefix_delete::PrefixDeleteJobs::new(),
+        access_tracker: advisor::AccessTracker::new(),
+        stripe_cache: objectio_common::ByteCache::new(args.stripe_cache_mb * 1024 * 1024),
+        prefetch_jobs: prefetch::PrefetchJobs::new(),
     });
 
     // Build router
@@ -915,7 +1036,28 @@ pub async fn run(
             "/_admin/rebalance/resume",
             post(admin::admin_rebalance_resume),
         )
+        .route(
+            "/_admin/ec-migrations",
+            get(admin::admin_list_ec_migrations),
+        )
+        .route(
+            "/_admin/ec-migrations/{bucket}",
+            get(admin::admin_get_ec_migration).post(admin::admin_start_ec_migration),
+        )
+        .route(
+            "/_admin/ec-migrations/{bucket}/cancel",
+            post(admin::admin_cancel_ec_migration),
+        )
+        .route("/_admin/advisor", get(advisor::admin_advisor))
         .route("/_admin/cluster-info", get(admin::admin_cluster_info))
+        .route("/_admin/version", get(admin::admin_version))
+        .route("/_admin/prefetch", get(prefetch::admin_list_prefetch))
+        .route("/_admin/prefetch", post(prefetch::admin_start_prefetch))
+        .route("/_admin/prefetch/{id}", get(prefetch::admin_get_prefetch))
+        .route(
+            "/_admin/prefetch/{id}",
+            delete(prefetch::admin_cancel_prefetch),
+        )
         .route("/_admin/topology", get(admin::admin_get_topology))
         .route(
             "/_admin/placement/validate",
@@ -1107,19 +1249,38 @@ pub async fn run(
             .fallback(tower_http::services::ServeFile::new(format!("{dir}/index.html")))
     };
 
-    // S3-side layer stack (chunked-decode + body limit + optional SigV4 auth).
+    // Per-operation-class concurrency pools, shared by every listener
+    // that serves S3 so the budget is gateway-wide.
+    let shaper = request_shaping::RequestShaper::new(&request_shaping::ShapingConfig {
+        max_concurrency: args.shaping_max_concurrency,
+        shares: request_shaping::ShapingConfig::parse_shares(&args.shaping_shares)
+            .map_err(|e| anyhow::anyhow!("Invalid --shaping-shares: {e}"))?,
+        queue_timeout: std::time::Duration::from_millis(args.shaping_queue_timeout_ms),
+    });
+
+    // S3-side layer stack (chunked-decode + body limit + optional SigV4
+    // auth + optional request shaping).
     let build_s3_protected = || {
         let r = Router::new()
             .merge(s3_routes.clone())
             .layer(middleware::from_fn(chunked_decode::s3_chunked_decode_layer))
             .layer(body_limit);
-        if args.no_auth {
+        let r = if args.no_auth {
             r
         } else {
             r.layer(middleware::from_fn_with_state(
                 Arc::clone(&auth_state),
                 auth_layer,
             ))
+        };
+        // Shaping wraps auth so unauthenticated floods queue in their
+        // class pool too instead of hammering meta for credentials.
+        match &shaper {
+            Some(shaper) => r.layer(middleware::from_fn_with_state(
+                Arc::clone(shaper),
+                request_shaping::shaping_layer,
+            )),
+            None => r,
         }
     };
 
@@ -1243,6 +1404,15 @@ pub async fn run(
         }
     }
 
+    // Load the SFTP host key before anything starts serving so a bad key
+    // path fails startup cleanly.
+    let sftp_config = match args.sftp_listen {
+        Some(_) => Some(Arc::new(sftp::server_config(sftp::load_or_create_host_key(
+            &args.sftp_host_key,
+        )?))),
+        None => None,
+    };
+
     // Bind all listeners and serve concurrently. A shared broadcast channel
     // fans the user's shutdown future out to every axum::serve.
     let (shutdown_tx, _) = tokio::sync::broadcast::channel::<()>(listeners.len().max(1));
@@ -1265,6 +1435,20 @@ pub async fn run(
                 .await
         }));
     }
+    if let (Some(addr), Some(config)) = (args.sftp_listen, sftp_config) {
+        let mut server =
+            sftp::SftpServer::new(Arc::clone(&state), Arc::clone(&auth_state), args.sftp_home);
+        let listener = TcpListener::bind(addr).await?;
+        info!("Listener: sftp on {addr}");
+        let mut rx = shutdown_tx.subscribe();
+        tasks.push(tokio::spawn(async move {
+            use russh::server::Server as _;
+            tokio::select! {
+                result = server.run_on_socket(config, &listener) => result,
+                _ = rx.recv() => Ok(()),
+            }
+        }));
+    }
 
     // Wait for caller-provided shutdown future, then fan out to all listeners.
     shutdown.await;
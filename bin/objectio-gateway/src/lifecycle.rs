@@ -178,6 +178,17 @@ async fn run_lifecycle_scan(
                         if obj.is_delete_marker && !versioning_enabled {
                             continue;
                         }
+                        // Object Lock: lifecycle never removes retained or held objects
+                        if crate::object_lock::check_removal(
+                            obj.retention.as_ref(),
+                            obj.legal_hold.as_ref(),
+                            false,
+                            now,
+                        )
+                        .is_err()
+                        {
+                            continue;
+                        }
 
                         match osd_client
                             .delete_object_meta(DeleteObjectMetaRequest {
@@ -237,6 +248,7 @@ async fn run_lifecycle_scan(
                         max_uploads: 1000,
                         key_marker: String::new(),
                         upload_id_marker: String::new(),
+                        delimiter: String::new(),
                     })
                     .await
             {
@@ -0,0 +1,335 @@
+//! S3 Object Lock (WORM) rules.
+//!
+//! Bucket lock configuration and per-object retention/legal hold are stored
+//! in Meta and on `ObjectMeta`; this module holds the pure decisions the S3
+//! handlers make with them:
+//!   - which retention and legal hold a new object gets, from the
+//!     `x-amz-object-lock-*` PUT headers or the bucket's default retention
+//!   - whether a version may be removed (delete, or an overwrite that
+//!     replaces it in place)
+//!   - whether a retention update is allowed (COMPLIANCE can only be
+//!     extended; weakening GOVERNANCE needs the bypass header)
+
+use axum::http::HeaderMap;
+use objectio_proto::metadata::{
+    LegalHold, ObjectLockConfiguration, ObjectRetention, RetentionMode,
+};
+
+/// Header that lets a caller override GOVERNANCE retention.
+pub const BYPASS_GOVERNANCE_HEADER: &str = "x-amz-bypass-governance-retention";
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Why an Object Lock request was refused: S3 error code and message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockError {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl LockError {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Whether the request carries `x-amz-bypass-governance-retention: true`.
+pub fn bypass_governance(headers: &HeaderMap) -> bool {
+    headers
+        .get(BYPASS_GOVERNANCE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+fn parse_mode(mode: &str) -> Option<RetentionMode> {
+    match mode {
+        "GOVERNANCE" => Some(RetentionMode::RetentionGovernance),
+        "COMPLIANCE" => Some(RetentionMode::RetentionCompliance),
+        _ => None,
+    }
+}
+
+/// S3 name of a retention mode.
+pub const fn mode_name(mode: RetentionMode) -> &'static str {
+    match mode {
+        RetentionMode::RetentionGovernance => "GOVERNANCE",
+        RetentionMode::RetentionCompliance | RetentionMode::RetentionNone => "COMPLIANCE",
+    }
+}
+
+/// Parse an ISO 8601 retain-until date into a unix timestamp.
+pub fn parse_retain_until(date: &str) -> Result<u64, LockError> {
+    chrono::DateTime::parse_from_rfc3339(date)
+        .map(|dt| dt.timestamp().max(0) as u64)
+        .map_err(|_| {
+            LockError::new(
+                "InvalidArgument",
+                format!("invalid retain-until date '{date}'"),
+            )
+        })
+}
+
+/// Retention and legal hold for a new object.
+///
+/// Explicit `x-amz-object-lock-mode` / `-retain-until-date` headers win
+/// (both or neither); otherwise the bucket's default retention applies.
+/// Lock headers on a bucket without Object Lock enabled are rejected.
+pub fn for_put(
+    headers: &HeaderMap,
+    config: Option<&ObjectLockConfiguration>,
+    now: u64,
+) -> Result<(Option<ObjectRetention>, Option<LegalHold>), LockError> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    let mode = header("x-amz-object-lock-mode");
+    let until = header("x-amz-object-lock-retain-until-date");
+    let hold = header("x-amz-object-lock-legal-hold");
+    let enabled = config.is_some_and(|c| c.enabled);
+
+    if !enabled {
+        if mode.is_some() || until.is_some() || hold.is_some() {
+            return Err(LockError::new(
+                "InvalidRequest",
+                "Bucket is missing Object Lock Configuration",
+            ));
+        }
+        return Ok((None, None));
+    }
+
+    let retention = match (mode, until) {
+        (Some(mode), Some(until)) => {
+            let mode = parse_mode(mode).ok_or_else(|| {
+                LockError::new(
+                    "InvalidArgument",
+                    format!("invalid object lock mode '{mode}'"),
+                )
+            })?;
+            let retain_until_date = parse_retain_until(until)?;
+            if retain_until_date <= now {
+                return Err(LockError::new(
+                    "InvalidArgument",
+                    "The retain until date must be in the future",
+                ));
+            }
+            Some(ObjectRetention {
+                mode: mode.into(),
+                retain_until_date,
+            })
+        }
+        (None, None) => config
+            .and_then(|c| c.default_retention.as_ref())
+            .and_then(|rule| {
+                let days = u64::from(rule.days) + u64::from(rule.years) * 365;
+                (days > 0).then(|| ObjectRetention {
+                    mode: rule.mode,
+                    retain_until_date: now + days * SECS_PER_DAY,
+                })
+            }),
+        _ => {
+            return Err(LockError::new(
+                "InvalidArgument",
+                "x-amz-object-lock-mode and x-amz-object-lock-retain-until-date must both be supplied",
+            ));
+        }
+    };
+
+    let legal_hold = match hold {
+        None => None,
+        Some("ON") => Some(LegalHold { status: true }),
+        Some("OFF") => Some(LegalHold { status: false }),
+        Some(other) => {
+            return Err(LockError::new(
+                "InvalidArgument",
+                format!("invalid legal hold status '{other}'"),
+            ));
+        }
+    };
+
+    Ok((retention, legal_hold))
+}
+
+/// Whether a version with this retention/legal hold may be permanently
+/// removed.
+pub fn check_removal(
+    retention: Option<&ObjectRetention>,
+    legal_hold: Option<&LegalHold>,
+    bypass_governance: bool,
+    now: u64,
+) -> Result<(), LockError> {
+    if legal_hold.is_some_and(|lh| lh.status) {
+        return Err(LockError::new(
+            "AccessDenied",
+            "Object is under legal hold and cannot be deleted",
+        ));
+    }
+    let Some(retention) = retention.filter(|r| r.retain_until_date > now) else {
+        return Ok(());
+    };
+    match retention.mode() {
+        RetentionMode::RetentionCompliance => Err(LockError::new(
+            "AccessDenied",
+            "Object is under compliance retention and cannot be deleted",
+        )),
+        RetentionMode::RetentionGovernance if !bypass_governance => Err(LockError::new(
+            "AccessDenied",
+            "Object is under governance retention. Use x-amz-bypass-governance-retention header to override",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Whether an object's active retention may be replaced by `new`.
+///
+/// Extending a retention is always allowed, including moving GOVERNANCE
+/// to COMPLIANCE. COMPLIANCE can't be shortened or downgraded; GOVERNANCE
+/// can only be shortened with the bypass header.
+pub fn check_retention_update(
+    current: Option<&ObjectRetention>,
+    new: &ObjectRetention,
+    bypass_governance: bool,
+    now: u64,
+) -> Result<(), LockError> {
+    let Some(current) = current.filter(|r| r.retain_until_date > now) else {
+        return Ok(());
+    };
+    let extends = new.retain_until_date >= current.retain_until_date;
+    match current.mode() {
+        RetentionMode::RetentionCompliance
+            if !extends || new.mode() != RetentionMode::RetentionCompliance =>
+        {
+            Err(LockError::new(
+                "AccessDenied",
+                "Compliance retention can only be extended",
+            ))
+        }
+        RetentionMode::RetentionGovernance if !extends && !bypass_governance => {
+            Err(LockError::new(
+                "AccessDenied",
+                "Shortening governance retention requires x-amz-bypass-governance-retention",
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// `x-amz-object-lock-*` response headers for GET/HEAD.
+pub fn response_headers(
+    retention: Option<&ObjectRetention>,
+    legal_hold: Option<&LegalHold>,
+) -> Vec<(&'static str, String)> {
+    let mut out = Vec::new();
+    if let Some(r) = retention.filter(|r| r.retain_until_date > 0) {
+        out.push(("x-amz-object-lock-mode", mode_name(r.mode()).to_string()));
+        if let Some(dt) = chrono::DateTime::from_timestamp(r.retain_until_date as i64, 0) {
+            out.push((
+                "x-amz-object-lock-retain-until-date",
+                dt.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            ));
+        }
+    }
+    if let Some(lh) = legal_hold {
+        out.push((
+            "x-amz-object-lock-legal-hold",
+            if lh.status { "ON" } else { "OFF" }.to_string(),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use objectio_proto::metadata::RetentionRule;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn enabled(default: Option<RetentionRule>) -> ObjectLockConfiguration {
+        ObjectLockConfiguration {
+            enabled: true,
+            default_retention: default,
+        }
+    }
+
+    fn retention(mode: RetentionMode, until: u64) -> ObjectRetention {
+        ObjectRetention {
+            mode: mode.into(),
+            retain_until_date: until,
+        }
+    }
+
+    #[test]
+    fn test_put_headers_require_lock_enabled() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(for_put(&headers, None, NOW), Ok((None, None)));
+        headers.insert("x-amz-object-lock-legal-hold", "ON".parse().unwrap());
+        assert_eq!(
+            for_put(&headers, None, NOW).unwrap_err().code,
+            "InvalidRequest"
+        );
+        let (_, hold) = for_put(&headers, Some(&enabled(None)), NOW).unwrap();
+        assert_eq!(hold, Some(LegalHold { status: true }));
+    }
+
+    #[test]
+    fn test_put_explicit_and_default_retention() {
+        let config = enabled(Some(RetentionRule {
+            mode: RetentionMode::RetentionGovernance.into(),
+            days: 2,
+            years: 0,
+        }));
+        let (r, _) = for_put(&HeaderMap::new(), Some(&config), NOW).unwrap();
+        assert_eq!(
+            r,
+            Some(retention(
+                RetentionMode::RetentionGovernance,
+                NOW + 2 * SECS_PER_DAY
+            ))
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-amz-object-lock-mode", "COMPLIANCE".parse().unwrap());
+        assert!(for_put(&headers, Some(&config), NOW).is_err());
+        headers.insert(
+            "x-amz-object-lock-retain-until-date",
+            "2099-01-01T00:00:00Z".parse().unwrap(),
+        );
+        let (r, _) = for_put(&headers, Some(&config), NOW).unwrap();
+        assert_eq!(r.unwrap().mode(), RetentionMode::RetentionCompliance);
+
+        headers.insert(
+            "x-amz-object-lock-retain-until-date",
+            "2001-01-01T00:00:00Z".parse().unwrap(),
+        );
+        assert!(for_put(&headers, Some(&config), NOW).is_err());
+    }
+
+    #[test]
+    fn test_check_removal() {
+        let gov = retention(RetentionMode::RetentionGovernance, NOW + 10);
+        let comp = retention(RetentionMode::RetentionCompliance, NOW + 10);
+        assert!(check_removal(Some(&gov), None, false, NOW).is_err());
+        assert!(check_removal(Some(&gov), None, true, NOW).is_ok());
+        assert!(check_removal(Some(&comp), None, true, NOW).is_err());
+        assert!(check_removal(Some(&comp), None, false, NOW + 10).is_ok());
+        assert!(check_removal(None, Some(&LegalHold { status: true }), true, NOW).is_err());
+        assert!(check_removal(None, Some(&LegalHold { status: false }), false, NOW).is_ok());
+    }
+
+    #[test]
+    fn test_check_retention_update() {
+        let comp = retention(RetentionMode::RetentionCompliance, NOW + 100);
+        let gov = retention(RetentionMode::RetentionGovernance, NOW + 100);
+        let longer_comp = retention(RetentionMode::RetentionCompliance, NOW + 200);
+        let shorter_gov = retention(RetentionMode::RetentionGovernance, NOW + 50);
+
+        assert!(check_retention_update(Some(&comp), &longer_comp, false, NOW).is_ok());
+        assert!(check_retention_update(Some(&comp), &gov, true, NOW).is_err());
+        assert!(check_retention_update(Some(&gov), &longer_comp, false, NOW).is_ok());
+        assert!(check_retention_update(Some(&gov), &shorter_gov, false, NOW).is_err());
+        assert!(check_retention_update(Some(&gov), &shorter_gov, true, NOW).is_ok());
+        // Expired retention no longer constrains anything
+        assert!(check_retention_update(Some(&comp), &shorter_gov, false, NOW + 100).is_ok());
+    }
+}
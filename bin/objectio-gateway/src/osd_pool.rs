@@ -2,9 +2,15 @@
 //!
 //! Manages connections to multiple OSD nodes for distributed storage operations.
 
-use objectio_proto::metadata::NodePlacement;
+use objectio_client::NetConfig;
+use objectio_proto::metadata::{
+    GetListingNodesRequest, GetPlacementResponse, ListingNode, NodePlacement,
+    metadata_service_client::MetadataServiceClient,
+};
 use objectio_proto::storage::storage_service_client::StorageServiceClient;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tonic::transport::Channel;
 use tracing::{error, info, warn};
@@ -69,6 +75,8 @@ pub struct OsdPool {
     nodes: RwLock<HashMap<NodeId, OsdNode>>,
     /// Address to node_id mapping for deduplication
     address_map: RwLock<HashMap<String, NodeId>>,
+    /// Proxy / resolver settings used when dialing OSDs
+    net: NetConfig,
 }
 
 impl OsdPool {
@@ -77,9 +85,17 @@ impl OsdPool {
         Self {
             nodes: RwLock::new(HashMap::new()),
             address_map: RwLock::new(HashMap::new()),
+            net: NetConfig::default(),
         }
     }
 
+    /// Dial OSDs through `net` (proxies, per-endpoint routes, custom DNS).
+    #[must_use]
+    pub fn with_net_config(mut self, net: NetConfig) -> Self {
+        self.net = net;
+        self
+    }
+
     /// Connect to an OSD node and add it to the pool
     pub async fn connect(&self, node_id: NodeId, address: &str) -> Result<(), OsdPoolError> {
         // Take the write lock immediately to avoid race conditions
@@ -113,9 +129,11 @@ impl OsdPool {
 
         // Connect with increased message size limit (100MB for large objects)
         let max_message_size = 100 * 1024 * 1024; // 100 MB
-        let channel = tonic::transport::Endpoint::new(address.to_string())
-            .map_err(|e| OsdPoolError::ConnectionFailed(e.to_string()))?
-            .connect()
+        let endpoint = tonic::transport::Endpoint::new(address.to_string())
+            .map_err(|e| OsdPoolError::ConnectionFailed(e.to_string()))?;
+        let channel = self
+            .net
+            .connect(endpoint)
             .await
             .map_err(|e| OsdPoolError::ConnectionFailed(e.to_string()))?;
 
@@ -210,6 +228,32 @@ impl OsdPool {
         self.nodes.read().await.len()
     }
 
+    /// Connect to every listed node not already in the pool, concurrently,
+    /// so the first request to each OSD doesn't pay for the TCP (and proxy)
+    /// handshake. Returns how many new connections succeeded.
+    pub async fn prewarm(&self, nodes: &[ListingNode]) -> usize {
+        let mut futs = Vec::new();
+        for node in nodes {
+            let Some(id) = NodeId::from_bytes(&node.node_id) else {
+                continue;
+            };
+            if node.address.is_empty() || self.nodes.read().await.contains_key(&id) {
+                continue;
+            }
+            futs.push(async move {
+                self.connect(id, &node.address)
+                    .await
+                    .inspect_err(|e| warn!("Pre-warm connect to OSD {} failed: {e}", node.address))
+                    .is_ok()
+            });
+        }
+        futures::future::join_all(futs)
+            .await
+            .into_iter()
+            .filter(|ok| *ok)
+            .count()
+    }
+
     /// Get a client for a node placement
     pub async fn get_client_for_placement(
         &self,
@@ -226,6 +270,40 @@ impl Default for OsdPool {
     }
 }
 
+/// Start the OSD connection pre-warmer: on startup and then every
+/// `interval`, fetch the active OSD list from meta and open connections to
+/// any OSD the pool hasn't reached yet.
+pub fn spawn_prewarmer(
+    meta_client: MetadataServiceClient<Channel>,
+    pool: Arc<OsdPool>,
+    interval: Duration,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let mut client = meta_client.clone();
+            let nodes = match client
+                .get_listing_nodes(GetListingNodesRequest {
+                    bucket: String::new(),
+                    include_all_states: false,
+                })
+                .await
+            {
+                Ok(resp) => resp.into_inner().nodes,
+                Err(e) => {
+                    warn!("OSD pre-warm: failed to list nodes: {e}");
+                    continue;
+                }
+            };
+            let connected = pool.prewarm(&nodes).await;
+            if connected > 0 {
+                info!("OSD pre-warm: opened {connected} new connection(s)");
+            }
+        }
+    });
+}
+
 /// Helper to write a shard to the appropriate OSD
 #[allow(clippy::too_many_arguments)]
 pub async fn write_shard_to_osd(
@@ -352,6 +430,20 @@ fn unique_node_placements(placements: &[NodePlacement]) -> Vec<NodePlacement> {
     out
 }
 
+/// Nodes that may hold an object's ObjectMeta: the bucket's current
+/// placement, then the pre-migration placement while the bucket is being
+/// moved to another EC profile. Reads and deletes must cover both or a
+/// not-yet-migrated object looks missing (and a delete leaves it behind).
+#[must_use]
+pub fn meta_lookup_nodes(placement: &GetPlacementResponse) -> Vec<NodePlacement> {
+    placement
+        .nodes
+        .iter()
+        .chain(&placement.fallback_nodes)
+        .cloned()
+        .collect()
+}
+
 /// Write ObjectMeta to every shard-carrying OSD in parallel. Requires all
 /// replicas to accept — any failure fails the PUT and the caller surfaces a
 /// retryable error to the S3 client.
@@ -416,6 +508,17 @@ pub async fn get_object_meta_from_any(
     placements: &[NodePlacement],
     bucket: &str,
     key: &str,
+) -> Result<Option<objectio_proto::metadata::ObjectMeta>, OsdPoolError> {
+    get_object_version_meta_from_any(pool, placements, bucket, key, "").await
+}
+
+/// [`get_object_meta_from_any`] for a specific version (`""` = latest).
+pub async fn get_object_version_meta_from_any(
+    pool: &OsdPool,
+    placements: &[NodePlacement],
+    bucket: &str,
+    key: &str,
+    version_id: &str,
 ) -> Result<Option<objectio_proto::metadata::ObjectMeta>, OsdPoolError> {
     use objectio_proto::storage::GetObjectMetaRequest;
 
@@ -430,7 +533,7 @@ pub async fn get_object_meta_from_any(
         let req = GetObjectMetaRequest {
             bucket: bucket.to_string(),
             key: key.to_string(),
-            version_id: String::new(),
+            version_id: version_id.to_string(),
         };
         let client_res = pool.get_client_for_placement(placement).await;
         let mut client = match client_res {
@@ -0,0 +1,505 @@
+//! Cache warm-up for known-hot objects.
+//!
+//! Ahead of anticipated load (a game patch going live, a model rollout)
+//! an operator can have the gateway read the objects that are about to
+//! get hammered, so the first wave of client GETs is served from memory:
+//!
+//! - `POST /_admin/prefetch` with a list of bucket/prefix (or bucket/key)
+//!   targets starts a job and answers `202 Accepted` with its id
+//! - `GET /_admin/prefetch` lists this gateway's jobs and its stripe
+//!   cache usage
+//! - `GET /_admin/prefetch/{id}` reports one job's progress
+//! - `DELETE /_admin/prefetch/{id}` cancels a running job
+//!
+//! Each object goes through the regular GET path, which fills this
+//! gateway's stripe cache and the shard caches of the OSDs it read from.
+//! Objects larger than the stripe cache still warm the OSDs. SSE-C
+//! objects can't be read without the customer key and count as failures.
+//!
+//! Like prefix-delete jobs, prefetch jobs run on the gateway that
+//! accepted them and are held in memory. Only that gateway's cache is
+//! warmed, so behind a load balancer each gateway needs its own POST.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use futures::StreamExt;
+use objectio_auth::AuthResult;
+use objectio_proto::metadata::GetBucketRequest;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::admin::require_system_admin;
+use crate::prefix_delete::{JobState, list_page};
+use crate::s3::{AppState, get_object};
+
+/// Objects read in parallel when the request doesn't say.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Upper bound on per-job parallelism.
+const MAX_CONCURRENCY: usize = 32;
+
+/// Targets accepted in one request.
+const MAX_TARGETS: usize = 1000;
+
+/// Finished jobs kept around for status queries.
+const MAX_FINISHED_JOBS: usize = 100;
+
+/// One bucket/prefix (or single key) to warm.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PrefetchTarget {
+    pub bucket: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub prefix: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+/// `POST /_admin/prefetch` body.
+#[derive(Debug, Deserialize)]
+pub struct PrefetchRequest {
+    pub targets: Vec<PrefetchTarget>,
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+}
+
+impl PrefetchRequest {
+    /// Check the targets and resolve the effective concurrency.
+    fn validate(&self) -> Result<usize, String> {
+        if self.targets.is_empty() {
+            return Err("targets must not be empty".to_string());
+        }
+        if self.targets.len() > MAX_TARGETS {
+            return Err(format!("at most {MAX_TARGETS} targets per request"));
+        }
+        for t in &self.targets {
+            if t.bucket.is_empty() {
+                return Err("every target needs a bucket".to_string());
+            }
+            if t.key.is_some() && !t.prefix.is_empty() {
+                return Err(format!(
+                    "target in bucket '{}' sets both key and prefix",
+                    t.bucket
+                ));
+            }
+        }
+        Ok(self
+            .concurrency
+            .unwrap_or(DEFAULT_CONCURRENCY)
+            .clamp(1, MAX_CONCURRENCY))
+    }
+}
+
+/// One prefetch job and its live progress counters.
+#[derive(Debug)]
+pub struct PrefetchJob {
+    pub id: String,
+    pub targets: Vec<PrefetchTarget>,
+    pub concurrency: usize,
+    pub started_at: u64,
+    targets_done: AtomicU64,
+    listed: AtomicU64,
+    warmed: AtomicU64,
+    failed: AtomicU64,
+    bytes: AtomicU64,
+    cancel: AtomicBool,
+    /// `(state, finished_at, error)`
+    outcome: Mutex<(JobState, u64, String)>,
+}
+
+impl PrefetchJob {
+    fn new(targets: Vec<PrefetchTarget>, concurrency: usize) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            targets,
+            concurrency,
+            started_at: now_secs(),
+            targets_done: AtomicU64::new(0),
+            listed: AtomicU64::new(0),
+            warmed: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            cancel: AtomicBool::new(false),
+            outcome: Mutex::new((JobState::Running, 0, String::new())),
+        }
+    }
+
+    #[must_use]
+    pub fn state(&self) -> JobState {
+        self.outcome.lock().0
+    }
+
+    fn finish(&self, state: JobState, error: String) {
+        *self.outcome.lock() = (state, now_secs(), error);
+    }
+
+    fn to_status(&self) -> PrefetchJobStatus {
+        let (state, finished_at, error) = self.outcome.lock().clone();
+        PrefetchJobStatus {
+            job_id: self.id.clone(),
+            state: state.as_str().to_string(),
+            targets: self.targets.clone(),
+            concurrency: self.concurrency,
+            targets_done: self.targets_done.load(Ordering::Relaxed),
+            objects_listed: self.listed.load(Ordering::Relaxed),
+            objects_warmed: self.warmed.load(Ordering::Relaxed),
+            objects_failed: self.failed.load(Ordering::Relaxed),
+            bytes_warmed: self.bytes.load(Ordering::Relaxed),
+            started_at: self.started_at,
+            finished_at: (finished_at > 0).then_some(finished_at),
+            error: (!error.is_empty()).then_some(error),
+        }
+    }
+}
+
+/// JSON view of a job.
+#[derive(Serialize)]
+pub struct PrefetchJobStatus {
+    pub job_id: String,
+    pub state: String,
+    pub targets: Vec<PrefetchTarget>,
+    pub concurrency: usize,
+    pub targets_done: u64,
+    pub objects_listed: u64,
+    pub objects_warmed: u64,
+    pub objects_failed: u64,
+    pub bytes_warmed: u64,
+    pub started_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Registry of this gateway's prefetch jobs.
+#[derive(Default)]
+pub struct PrefetchJobs {
+    jobs: RwLock<HashMap<String, Arc<PrefetchJob>>>,
+}
+
+impl PrefetchJobs {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn start(&self, targets: Vec<PrefetchTarget>, concurrency: usize) -> Arc<PrefetchJob> {
+        let mut jobs = self.jobs.write();
+
+        // Prune the oldest finished jobs.
+        let mut finished: Vec<(u64, String)> = jobs
+            .values()
+            .filter(|j| j.state() != JobState::Running)
+            .map(|j| (j.started_at, j.id.clone()))
+            .collect();
+        if finished.len() >= MAX_FINISHED_JOBS {
+            finished.sort_unstable();
+            for (_, id) in &finished[..=finished.len() - MAX_FINISHED_JOBS] {
+                jobs.remove(id);
+            }
+        }
+
+        let job = Arc::new(PrefetchJob::new(targets, concurrency));
+        jobs.insert(job.id.clone(), Arc::clone(&job));
+        job
+    }
+
+    fn get(&self, id: &str) -> Option<Arc<PrefetchJob>> {
+        self.jobs.read().get(id).cloned()
+    }
+
+    fn list(&self) -> Vec<Arc<PrefetchJob>> {
+        let mut jobs: Vec<_> = self.jobs.read().values().cloned().collect();
+        jobs.sort_by_key(|j| std::cmp::Reverse(j.started_at));
+        jobs
+    }
+}
+
+/// POST /_admin/prefetch
+pub async fn admin_start_prefetch(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+    Json(body): Json<PrefetchRequest>,
+) -> Response {
+    if let Some(deny) = require_system_admin(&auth, &headers) {
+        return deny;
+    }
+    let concurrency = match body.validate() {
+        Ok(c) => c,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    let mut buckets: Vec<&str> = body.targets.iter().map(|t| t.bucket.as_str()).collect();
+    buckets.sort_unstable();
+    buckets.dedup();
+    let mut meta_client = state.meta_client.clone();
+    for bucket in buckets {
+        if let Err(e) = meta_client
+            .get_bucket(GetBucketRequest {
+                name: bucket.to_string(),
+            })
+            .await
+        {
+            return if e.code() == tonic::Code::NotFound {
+                (
+                    StatusCode::NOT_FOUND,
+                    format!("bucket '{bucket}' does not exist"),
+                )
+                    .into_response()
+            } else {
+                (StatusCode::INTERNAL_SERVER_ERROR, e.message().to_string()).into_response()
+            };
+        }
+    }
+
+    let job = state.prefetch_jobs.start(body.targets, concurrency);
+    info!(
+        "prefetch {}: started, {} targets, concurrency={}",
+        job.id,
+        job.targets.len(),
+        concurrency
+    );
+    tokio::spawn(run_job(Arc::clone(&state), Arc::clone(&job)));
+
+    (StatusCode::ACCEPTED, Json(job.to_status())).into_response()
+}
+
+/// GET /_admin/prefetch
+pub async fn admin_list_prefetch(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(deny) = require_system_admin(&auth, &headers) {
+        return deny;
+    }
+    let cache = state.stripe_cache.stats();
+    let jobs: Vec<PrefetchJobStatus> = state
+        .prefetch_jobs
+        .list()
+        .iter()
+        .map(|j| j.to_status())
+        .collect();
+    Json(serde_json::json!({
+        "jobs": jobs,
+        "stripe_cache": {
+            "capacity_bytes": cache.capacity_bytes,
+            "bytes": cache.bytes,
+            "entries": cache.entries,
+            "hits": cache.hits,
+            "misses": cache.misses,
+            "evictions": cache.evictions,
+        },
+    }))
+    .into_response()
+}
+
+/// GET /_admin/prefetch/{id}
+pub async fn admin_get_prefetch(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    if let Some(deny) = require_system_admin(&auth, &headers) {
+        return deny;
+    }
+    match state.prefetch_jobs.get(&id) {
+        Some(job) => Json(job.to_status()).into_response(),
+        None => no_such_job(&id),
+    }
+}
+
+/// DELETE /_admin/prefetch/{id}
+pub async fn admin_cancel_prefetch(
+    State(state): State<Arc<AppState>>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Response {
+    if let Some(deny) = require_system_admin(&auth, &headers) {
+        return deny;
+    }
+    let Some(job) = state.prefetch_jobs.get(&id) else {
+        return no_such_job(&id);
+    };
+    job.cancel.store(true, Ordering::Relaxed);
+    info!("prefetch {}: cancel requested", id);
+    Json(job.to_status()).into_response()
+}
+
+/// Walk every target and read each object through the GET path.
+async fn run_job(state: Arc<AppState>, job: Arc<PrefetchJob>) {
+    for target in &job.targets {
+        if job.cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        if let Some(key) = &target.key {
+            job.listed.fetch_add(1, Ordering::Relaxed);
+            warm(&state, &job, &target.bucket, key.clone()).await;
+        } else if let Err(e) = warm_prefix(&state, &job, target).await {
+            warn!("prefetch {}: listing failed: {}", job.id, e);
+            job.finish(JobState::Failed, e);
+            return;
+        }
+        job.targets_done.fetch_add(1, Ordering::Relaxed);
+    }
+
+    if job.cancel.load(Ordering::Relaxed) {
+        info!("prefetch {}: cancelled", job.id);
+        job.finish(JobState::Cancelled, String::new());
+        return;
+    }
+    info!(
+        "prefetch {}: completed, warmed={} failed={} bytes={}",
+        job.id,
+        job.warmed.load(Ordering::Relaxed),
+        job.failed.load(Ordering::Relaxed),
+        job.bytes.load(Ordering::Relaxed)
+    );
+    job.finish(JobState::Completed, String::new());
+}
+
+/// Page through a prefix, reading up to `job.concurrency` objects at once.
+async fn warm_prefix(
+    state: &Arc<AppState>,
+    job: &PrefetchJob,
+    target: &PrefetchTarget,
+) -> Result<(), String> {
+    let mut start_after = String::new();
+    let mut continuation_token: Option<String> = None;
+    loop {
+        if job.cancel.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let (keys, next_token) = list_page(
+            state,
+            &target.bucket,
+            &target.prefix,
+            &start_after,
+            continuation_token.as_deref(),
+        )
+        .await?;
+        let Some(last) = keys.last().cloned() else {
+            return Ok(());
+        };
+        job.listed.fetch_add(keys.len() as u64, Ordering::Relaxed);
+
+        futures::stream::iter(keys)
+            .for_each_concurrent(job.concurrency, |key| async move {
+                if !job.cancel.load(Ordering::Relaxed) {
+                    warm(state, job, &target.bucket, key).await;
+                }
+            })
+            .await;
+
+        start_after = last;
+        continuation_token = next_token;
+    }
+}
+
+/// Read one object and discard the body; the caches keep the data.
+async fn warm(state: &Arc<AppState>, job: &PrefetchJob, bucket: &str, key: String) {
+    let response = get_object(
+        State(Arc::clone(state)),
+        Path((bucket.to_string(), key)),
+        None,
+        HeaderMap::new(),
+    )
+    .await;
+    if response.status().is_success() {
+        let size = response
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(0);
+        job.warmed.fetch_add(1, Ordering::Relaxed);
+        job.bytes.fetch_add(size, Ordering::Relaxed);
+    } else {
+        job.failed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn no_such_job(id: &str) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        format!("prefetch job '{id}' not found on this gateway"),
+    )
+        .into_response()
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(bucket: &str, prefix: &str, key: Option<&str>) -> PrefetchTarget {
+        PrefetchTarget {
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+            key: key.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_validate_request() {
+        let req: PrefetchRequest = serde_json::from_str(
+            r#"{"targets":[{"bucket":"assets","prefix":"patch/"},{"bucket":"assets","key":"m.json"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(req.validate(), Ok(DEFAULT_CONCURRENCY));
+        assert_eq!(req.targets[1], target("assets", "", Some("m.json")));
+
+        let clamp = |concurrency| PrefetchRequest {
+            targets: vec![target("b", "", None)],
+            concurrency: Some(concurrency),
+        };
+        assert_eq!(clamp(0).validate(), Ok(1));
+        assert_eq!(clamp(1000).validate(), Ok(MAX_CONCURRENCY));
+
+        let bad = |targets| PrefetchRequest {
+            targets,
+            concurrency: None,
+        };
+        assert!(bad(vec![]).validate().is_err());
+        assert!(bad(vec![target("", "p/", None)]).validate().is_err());
+        assert!(bad(vec![target("b", "p/", Some("k"))]).validate().is_err());
+    }
+
+    #[test]
+    fn test_finished_jobs_pruned() {
+        let jobs = PrefetchJobs::new();
+        let running = jobs.start(vec![target("b", "", None)], 1);
+        for _ in 0..MAX_FINISHED_JOBS + 5 {
+            jobs.start(vec![target("b", "", None)], 1)
+                .finish(JobState::Completed, String::new());
+        }
+        jobs.start(vec![target("b", "", None)], 1);
+        let listed = jobs.list();
+        assert!(listed.iter().any(|j| j.id == running.id));
+        assert!(
+            listed
+                .iter()
+                .filter(|j| j.state() != JobState::Running)
+                .count()
+                < MAX_FINISHED_JOBS
+        );
+    }
+}
@@ -0,0 +1,523 @@
+//! Server-side recursive prefix delete.
+//!
+//! ObjectIO extension for clearing out large "directories" without the
+//! client issuing one `DeleteObjects` call per thousand keys:
+//!
+//! - `POST /{bucket}?prefix-delete&prefix=logs/2024/` starts a job and
+//!   answers `202 Accepted` with its id
+//! - `GET /{bucket}?prefix-delete` lists this gateway's jobs for the bucket
+//! - `GET /{bucket}?prefix-delete&job-id=...` reports one job's progress
+//! - `DELETE /{bucket}?prefix-delete&job-id=...` cancels a running job
+//!
+//! Each key goes through the regular `DeleteObject` path, so bucket
+//! policy, object lock and versioning (delete markers) behave exactly as
+//! for individual deletes. Keys that can't be deleted are counted as
+//! failures and skipped; the job keeps going.
+//!
+//! Jobs run on the gateway that accepted them and are held in memory:
+//! progress must be polled on the same gateway, and a gateway restart
+//! drops its jobs (re-issuing the POST resumes, since deleted keys are
+//! gone from the listing).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use axum::{
+    Extension,
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::Response,
+};
+use objectio_auth::AuthResult;
+use objectio_proto::metadata::{GetBucketRequest, ListObjectsRequest};
+use parking_lot::{Mutex, RwLock};
+use quick_xml::se::to_string as to_xml;
+use serde::Serialize;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::s3::{AppState, S3Error, build_s3_arn, check_bucket_policy, delete_object};
+
+/// Keys listed per page while walking the prefix.
+const LIST_PAGE_SIZE: u32 = 1000;
+
+/// Finished jobs kept around for status queries.
+const MAX_FINISHED_JOBS: usize = 100;
+
+/// Lifecycle of a prefix-delete job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobState {
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+impl JobState {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "Running",
+            Self::Completed => "Completed",
+            Self::Cancelled => "Cancelled",
+            Self::Failed => "Failed",
+        }
+    }
+}
+
+/// One prefix-delete job and its live progress counters.
+#[derive(Debug)]
+pub struct PrefixDeleteJob {
+    pub id: String,
+    pub bucket: String,
+    pub prefix: String,
+    pub started_at: u64,
+    listed: AtomicU64,
+    deleted: AtomicU64,
+    failed: AtomicU64,
+    cancel: AtomicBool,
+    /// `(state, finished_at, error)`
+    outcome: Mutex<(JobState, u64, String)>,
+}
+
+impl PrefixDeleteJob {
+    fn new(bucket: String, prefix: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            bucket,
+            prefix,
+            started_at: now_secs(),
+            listed: AtomicU64::new(0),
+            deleted: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+            cancel: AtomicBool::new(false),
+            outcome: Mutex::new((JobState::Running, 0, String::new())),
+        }
+    }
+
+    #[must_use]
+    pub fn state(&self) -> JobState {
+        self.outcome.lock().0
+    }
+
+    fn finish(&self, state: JobState, error: String) {
+        *self.outcome.lock() = (state, now_secs(), error);
+    }
+
+    fn to_status(&self) -> PrefixDeleteJobStatus {
+        let (state, finished_at, error) = self.outcome.lock().clone();
+        PrefixDeleteJobStatus {
+            job_id: self.id.clone(),
+            bucket: self.bucket.clone(),
+            prefix: self.prefix.clone(),
+            state: state.as_str().to_string(),
+            keys_listed: self.listed.load(Ordering::Relaxed),
+            keys_deleted: self.deleted.load(Ordering::Relaxed),
+            keys_failed: self.failed.load(Ordering::Relaxed),
+            started_at: self.started_at,
+            finished_at: (finished_at > 0).then_some(finished_at),
+            error: (!error.is_empty()).then_some(error),
+        }
+    }
+}
+
+/// Registry of this gateway's prefix-delete jobs.
+#[derive(Default)]
+pub struct PrefixDeleteJobs {
+    jobs: RwLock<HashMap<String, Arc<PrefixDeleteJob>>>,
+}
+
+impl PrefixDeleteJobs {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new job, or return the running job that already
+    /// covers an overlapping prefix in the same bucket.
+    fn start(
+        &self,
+        bucket: &str,
+        prefix: &str,
+    ) -> Result<Arc<PrefixDeleteJob>, Arc<PrefixDeleteJob>> {
+        let mut jobs = self.jobs.write();
+        if let Some(existing) = jobs.values().find(|j| {
+            j.bucket == bucket
+                && j.state() == JobState::Running
+                && (j.prefix.starts_with(prefix) || prefix.starts_with(j.prefix.as_str()))
+        }) {
+            return Err(Arc::clone(existing));
+        }
+
+        // Prune the oldest finished jobs.
+        let mut finished: Vec<(u64, String)> = jobs
+            .values()
+            .filter(|j| j.state() != JobState::Running)
+            .map(|j| (j.started_at, j.id.clone()))
+            .collect();
+        if finished.len() >= MAX_FINISHED_JOBS {
+            finished.sort_unstable();
+            for (_, id) in &finished[..=finished.len() - MAX_FINISHED_JOBS] {
+                jobs.remove(id);
+            }
+        }
+
+        let job = Arc::new(PrefixDeleteJob::new(bucket.to_string(), prefix.to_string()));
+        jobs.insert(job.id.clone(), Arc::clone(&job));
+        Ok(job)
+    }
+
+    fn get(&self, bucket: &str, id: &str) -> Option<Arc<PrefixDeleteJob>> {
+        self.jobs
+            .read()
+            .get(id)
+            .filter(|j| j.bucket == bucket)
+            .cloned()
+    }
+
+    fn list(&self, bucket: &str) -> Vec<Arc<PrefixDeleteJob>> {
+        let mut jobs: Vec<_> = self
+            .jobs
+            .read()
+            .values()
+            .filter(|j| j.bucket == bucket)
+            .cloned()
+            .collect();
+        jobs.sort_by_key(|j| std::cmp::Reverse(j.started_at));
+        jobs
+    }
+}
+
+/// XML view of a job.
+#[derive(Serialize)]
+#[serde(rename = "PrefixDeleteJob")]
+pub struct PrefixDeleteJobStatus {
+    #[serde(rename = "JobId")]
+    pub job_id: String,
+    #[serde(rename = "Bucket")]
+    pub bucket: String,
+    #[serde(rename = "Prefix")]
+    pub prefix: String,
+    #[serde(rename = "State")]
+    pub state: String,
+    #[serde(rename = "KeysListed")]
+    pub keys_listed: u64,
+    #[serde(rename = "KeysDeleted")]
+    pub keys_deleted: u64,
+    #[serde(rename = "KeysFailed")]
+    pub keys_failed: u64,
+    #[serde(rename = "StartedAt")]
+    pub started_at: u64,
+    #[serde(rename = "FinishedAt", skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<u64>,
+    #[serde(rename = "Error", skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "ListPrefixDeleteJobsResult")]
+struct ListPrefixDeleteJobsResult {
+    #[serde(rename = "PrefixDeleteJob", skip_serializing_if = "Vec::is_empty")]
+    jobs: Vec<PrefixDeleteJobStatus>,
+}
+
+/// `POST /{bucket}?prefix-delete&prefix=...`
+pub async fn start_prefix_delete(
+    state: Arc<AppState>,
+    bucket: String,
+    prefix: Option<String>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+) -> Response {
+    let prefix = prefix.unwrap_or_default();
+    if prefix.is_empty() {
+        return S3Error::xml_response(
+            "InvalidArgument",
+            "prefix-delete requires a non-empty prefix parameter",
+            StatusCode::BAD_REQUEST,
+        );
+    }
+
+    if let Some(Extension(auth_result)) = &auth {
+        let resource = build_s3_arn(&bucket, Some(&format!("{prefix}*")));
+        if let Some(deny_response) = check_bucket_policy(
+            &state,
+            &bucket,
+            &auth_result.user_arn,
+            "s3:DeleteObject",
+            &resource,
+            Some(&headers),
+            auth_result.auth_mode,
+        )
+        .await
+        {
+            return deny_response;
+        }
+    }
+
+    let mut meta_client = state.meta_client.clone();
+    if let Err(e) = meta_client
+        .get_bucket(GetBucketRequest {
+            name: bucket.clone(),
+        })
+        .await
+    {
+        return if e.code() == tonic::Code::NotFound {
+            S3Error::xml_response(
+                "NoSuchBucket",
+                "The specified bucket does not exist",
+                StatusCode::NOT_FOUND,
+            )
+        } else {
+            S3Error::xml_response(
+                "InternalError",
+                &e.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        };
+    }
+
+    let job = match state.prefix_delete_jobs.start(&bucket, &prefix) {
+        Ok(job) => job,
+        Err(existing) => {
+            return S3Error::xml_response(
+                "OperationAborted",
+                &format!(
+                    "prefix-delete job {} is already running on overlapping prefix '{}'",
+                    existing.id, existing.prefix
+                ),
+                StatusCode::CONFLICT,
+            );
+        }
+    };
+
+    info!(
+        "prefix-delete {}: started bucket={} prefix={:?}",
+        job.id, bucket, prefix
+    );
+    tokio::spawn(run_job(Arc::clone(&state), Arc::clone(&job), auth, headers));
+
+    job_response(&job.to_status(), StatusCode::ACCEPTED)
+}
+
+/// `GET /{bucket}?prefix-delete[&job-id=...]`
+pub fn get_prefix_delete(state: &AppState, bucket: &str, job_id: Option<&str>) -> Response {
+    if let Some(id) = job_id {
+        return match state.prefix_delete_jobs.get(bucket, id) {
+            Some(job) => job_response(&job.to_status(), StatusCode::OK),
+            None => no_such_job(id),
+        };
+    }
+
+    let result = ListPrefixDeleteJobsResult {
+        jobs: state
+            .prefix_delete_jobs
+            .list(bucket)
+            .iter()
+            .map(|j| j.to_status())
+            .collect(),
+    };
+    xml_ok(&to_xml(&result).unwrap_or_default(), StatusCode::OK)
+}
+
+/// `DELETE /{bucket}?prefix-delete&job-id=...`
+pub fn cancel_prefix_delete(state: &AppState, bucket: &str, job_id: Option<&str>) -> Response {
+    let Some(id) = job_id else {
+        return S3Error::xml_response(
+            "InvalidArgument",
+            "job-id is required to cancel a prefix-delete job",
+            StatusCode::BAD_REQUEST,
+        );
+    };
+    let Some(job) = state.prefix_delete_jobs.get(bucket, id) else {
+        return no_such_job(id);
+    };
+    job.cancel.store(true, Ordering::Relaxed);
+    info!("prefix-delete {}: cancel requested", id);
+    job_response(&job.to_status(), StatusCode::OK)
+}
+
+/// Walk the prefix page by page and delete every key.
+async fn run_job(
+    state: Arc<AppState>,
+    job: Arc<PrefixDeleteJob>,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+) {
+    // Keys we couldn't delete stay in the listing, so resume after the
+    // last key processed rather than from the top of the prefix.
+    let mut start_after = String::new();
+    let mut continuation_token: Option<String> = None;
+
+    loop {
+        if job.cancel.load(Ordering::Relaxed) {
+            info!("prefix-delete {}: cancelled", job.id);
+            job.finish(JobState::Cancelled, String::new());
+            return;
+        }
+
+        let (keys, next_token) = match list_page(
+            &state,
+            &job.bucket,
+            &job.prefix,
+            &start_after,
+            continuation_token.as_deref(),
+        )
+        .await
+        {
+            Ok(page) => page,
+            Err(e) => {
+                warn!("prefix-delete {}: listing failed: {}", job.id, e);
+                job.finish(JobState::Failed, e);
+                return;
+            }
+        };
+        if keys.is_empty() {
+            break;
+        }
+        job.listed.fetch_add(keys.len() as u64, Ordering::Relaxed);
+
+        for key in keys {
+            if job.cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let response = delete_object(
+                State(Arc::clone(&state)),
+                Path((job.bucket.clone(), key.clone())),
+                auth.clone(),
+                None,
+                headers.clone(),
+            )
+            .await;
+            if response.status().is_success() {
+                job.deleted.fetch_add(1, Ordering::Relaxed);
+            } else {
+                job.failed.fetch_add(1, Ordering::Relaxed);
+            }
+            start_after = key;
+        }
+
+        continuation_token = next_token;
+    }
+
+    info!(
+        "prefix-delete {}: completed, deleted={} failed={}",
+        job.id,
+        job.deleted.load(Ordering::Relaxed),
+        job.failed.load(Ordering::Relaxed)
+    );
+    job.finish(JobState::Completed, String::new());
+}
+
+/// One page of keys under `prefix`, plus the scatter-gather
+/// continuation token when Meta's listing index had nothing.
+pub(crate) async fn list_page(
+    state: &AppState,
+    bucket: &str,
+    prefix: &str,
+    start_after: &str,
+    continuation_token: Option<&str>,
+) -> Result<(Vec<String>, Option<String>), String> {
+    let mut meta_client = state.meta_client.clone();
+
+    if continuation_token.is_none() {
+        let resp = meta_client
+            .list_objects(ListObjectsRequest {
+                bucket: bucket.to_string(),
+                prefix: prefix.to_string(),
+                delimiter: String::new(),
+                start_after: start_after.to_string(),
+                continuation_token: String::new(),
+                max_keys: LIST_PAGE_SIZE,
+                include_versions: false,
+            })
+            .await
+            .map_err(|e| format!("list_objects failed: {e}"))?
+            .into_inner();
+        if !resp.entries.is_empty() {
+            return Ok((resp.entries.into_iter().map(|e| e.key).collect(), None));
+        }
+        if !start_after.is_empty() {
+            return Ok((Vec::new(), None));
+        }
+    }
+
+    // Meta's listing index may be empty for pre-migration objects; fall
+    // back to scatter-gather across the OSDs, same as ListObjects.
+    let result = state
+        .scatter_gather
+        .list_objects(
+            &mut meta_client,
+            bucket,
+            prefix,
+            LIST_PAGE_SIZE,
+            continuation_token,
+        )
+        .await
+        .map_err(|e| format!("scatter-gather listing failed: {e}"))?;
+    let next = if result.is_truncated {
+        result.next_continuation_token
+    } else {
+        None
+    };
+    Ok((result.objects.into_iter().map(|o| o.key).collect(), next))
+}
+
+fn job_response(status: &PrefixDeleteJobStatus, code: StatusCode) -> Response {
+    xml_ok(&to_xml(status).unwrap_or_default(), code)
+}
+
+fn xml_ok(xml: &str, code: StatusCode) -> Response {
+    Response::builder()
+        .status(code)
+        .header(header::CONTENT_TYPE, "application/xml")
+        .body(Body::from(format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{xml}"
+        )))
+        .unwrap()
+}
+
+fn no_such_job(id: &str) -> Response {
+    S3Error::xml_response(
+        "NoSuchJob",
+        &format!("prefix-delete job '{id}' not found on this gateway"),
+        StatusCode::NOT_FOUND,
+    )
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_prefix_rejected() {
+        let jobs = PrefixDeleteJobs::new();
+        let first = jobs.start("b", "logs/").unwrap();
+        assert_eq!(jobs.start("b", "logs/2024/").unwrap_err().id, first.id);
+        assert_eq!(jobs.start("b", "lo").unwrap_err().id, first.id);
+        assert!(jobs.start("b", "data/").is_ok());
+        assert!(jobs.start("other", "logs/").is_ok());
+
+        first.finish(JobState::Completed, String::new());
+        assert!(jobs.start("b", "logs/").is_ok());
+    }
+
+    #[test]
+    fn test_finished_jobs_pruned() {
+        let jobs = PrefixDeleteJobs::new();
+        for i in 0..MAX_FINISHED_JOBS + 5 {
+            let job = jobs.start("b", &format!("p{i}/")).unwrap();
+            job.finish(JobState::Completed, String::new());
+        }
+        assert!(jobs.list("b").len() <= MAX_FINISHED_JOBS);
+    }
+}
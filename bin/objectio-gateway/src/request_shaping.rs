@@ -0,0 +1,292 @@
+//! Request shaping by operation class.
+//!
+//! Splits a gateway-wide concurrency budget into weighted pools, one per
+//! operation class, so a storm in one class (a `ListObjects` crawler, a
+//! batch `DeleteObjects` job) queues behind its own pool instead of
+//! starving latency-sensitive GET/PUT traffic.
+//!
+//! Classes:
+//! - **read** — GET/HEAD of objects, bucket config sub-resources
+//!   (`?policy`, `?versioning`, ...), CORS preflights
+//! - **write** — PUT of anything, multipart initiate/complete, other POSTs
+//! - **list** — `ListBuckets`, `ListObjects{,V2}`, `ListObjectVersions`,
+//!   `ListMultipartUploads`, `ListParts`
+//! - **delete** — DELETE of anything, `POST /{bucket}?delete`,
+//!   `POST /{bucket}?prefix-delete`
+//!
+//! A request waits up to `--shaping-queue-timeout-ms` for a slot in its
+//! class's pool, then gets `503 SlowDown` so SDKs back off and retry.
+//! The slot is held until the handler returns. Pools are strict: an idle
+//! class does not lend its slots to a busy one.
+//!
+//! Disabled unless `--shaping-max-concurrency` is non-zero.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use objectio_s3::s3_metrics;
+use tokio::sync::Semaphore;
+use tracing::{debug, info};
+
+use crate::s3::S3Error;
+
+/// Bucket sub-resources whose GET returns configuration, not a listing.
+const BUCKET_CONFIG_SUBRESOURCES: &[&str] = &[
+    "accelerate",
+    "acl",
+    "analytics",
+    "cors",
+    "encryption",
+    "intelligent-tiering",
+    "inventory",
+    "lifecycle",
+    "location",
+    "logging",
+    "metrics",
+    "notification",
+    "object-lock",
+    "ownershipControls",
+    "policy",
+    "policyStatus",
+    "publicAccessBlock",
+    "replication",
+    "requestPayment",
+    "tagging",
+    "versioning",
+    "website",
+];
+
+/// Operation class a request is shaped under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpClass {
+    Read,
+    Write,
+    List,
+    Delete,
+}
+
+impl OpClass {
+    pub const ALL: [Self; 4] = [Self::Read, Self::Write, Self::List, Self::Delete];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::List => "list",
+            Self::Delete => "delete",
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Classify an S3 request from its method, path and raw query.
+    #[must_use]
+    pub fn classify(method: &Method, path: &str, query: Option<&str>) -> Self {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let has_param = |name: &str| {
+            query.is_some_and(|q| {
+                q.split('&')
+                    .any(|kv| kv.split('=').next().unwrap_or(kv) == name)
+            })
+        };
+
+        match *method {
+            Method::PUT => Self::Write,
+            Method::DELETE => Self::Delete,
+            Method::POST if segments.len() == 1 && has_param("delete") => Self::Delete,
+            Method::POST if segments.len() == 1 && has_param("prefix-delete") => Self::Delete,
+            // SelectObjectContent only reads the object.
+            Method::POST if has_param("select") => Self::Read,
+            Method::POST => Self::Write,
+            Method::GET => match segments.len() {
+                0 => Self::List,
+                1 if BUCKET_CONFIG_SUBRESOURCES.iter().any(|s| has_param(s)) => Self::Read,
+                1 => Self::List,
+                _ if has_param("uploadId") => Self::List,
+                _ => Self::Read,
+            },
+            _ => Self::Read,
+        }
+    }
+}
+
+/// Shaping configuration, built from the gateway's CLI flags.
+#[derive(Debug, Clone)]
+pub struct ShapingConfig {
+    /// Total concurrent S3 requests across all classes. 0 = disabled.
+    pub max_concurrency: usize,
+    /// Relative weight of each class, indexed by [`OpClass`].
+    pub shares: [u32; 4],
+    pub queue_timeout: Duration,
+}
+
+impl ShapingConfig {
+    /// Parse `read=40,write=40,list=10,delete=10`. Classes left out keep
+    /// a share of 1 so they're never locked out entirely.
+    pub fn parse_shares(spec: &str) -> Result<[u32; 4], String> {
+        let mut shares = [1; 4];
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (name, weight) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected class=weight, got '{part}'"))?;
+            let class = OpClass::ALL
+                .into_iter()
+                .find(|c| c.as_str() == name.trim())
+                .ok_or_else(|| {
+                    format!("unknown class '{name}' (expected read | write | list | delete)")
+                })?;
+            let weight: u32 = weight
+                .trim()
+                .parse()
+                .map_err(|e| format!("invalid weight for '{name}': {e}"))?;
+            if weight == 0 {
+                return Err(format!("share for '{name}' must be at least 1"));
+            }
+            shares[class.index()] = weight;
+        }
+        Ok(shares)
+    }
+
+    /// Pool size per class: the class's proportion of
+    /// `max_concurrency`, at least one slot.
+    #[must_use]
+    pub fn pool_sizes(&self) -> [usize; 4] {
+        let total: u64 = self.shares.iter().map(|&s| u64::from(s)).sum();
+        let mut sizes = [0; 4];
+        for (size, &share) in sizes.iter_mut().zip(&self.shares) {
+            let scaled = self.max_concurrency as u64 * u64::from(share) / total.max(1);
+            *size = usize::try_from(scaled).unwrap_or(usize::MAX).max(1);
+        }
+        sizes
+    }
+}
+
+/// Per-class concurrency pools shared by every S3 listener.
+pub struct RequestShaper {
+    pools: [Arc<Semaphore>; 4],
+    queue_timeout: Duration,
+}
+
+impl RequestShaper {
+    /// Build the shaper, or `None` when shaping is disabled.
+    #[must_use]
+    pub fn new(config: &ShapingConfig) -> Option<Arc<Self>> {
+        if config.max_concurrency == 0 {
+            return None;
+        }
+        let sizes = config.pool_sizes();
+        info!(
+            "Request shaping: {} slots — read={} write={} list={} delete={}, queue timeout {:?}",
+            config.max_concurrency, sizes[0], sizes[1], sizes[2], sizes[3], config.queue_timeout
+        );
+        Some(Arc::new(Self {
+            pools: sizes.map(|n| Arc::new(Semaphore::new(n))),
+            queue_timeout: config.queue_timeout,
+        }))
+    }
+}
+
+/// Axum middleware: hold a slot in the request's class pool for the
+/// duration of the handler, or answer `503 SlowDown` on queue timeout.
+pub async fn shaping_layer(
+    State(shaper): State<Arc<RequestShaper>>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    // Load balancer probes must never queue behind client traffic.
+    if request.uri().path() == "/health" {
+        return next.run(request).await;
+    }
+
+    let class = OpClass::classify(
+        request.method(),
+        request.uri().path(),
+        request.uri().query(),
+    );
+    let pool = Arc::clone(&shaper.pools[class.index()]);
+    let Ok(Ok(_permit)) = tokio::time::timeout(shaper.queue_timeout, pool.acquire_owned()).await
+    else {
+        s3_metrics().record_shaping_rejection(class.as_str());
+        debug!(
+            "request shaping: {} {} rejected, {} pool saturated",
+            request.method(),
+            request.uri().path(),
+            class.as_str()
+        );
+        let mut response = S3Error::xml_response(
+            "SlowDown",
+            "Please reduce your request rate.",
+            StatusCode::SERVICE_UNAVAILABLE,
+        );
+        response
+            .headers_mut()
+            .insert("Retry-After", axum::http::HeaderValue::from_static("1"));
+        return response.into_response();
+    };
+
+    s3_metrics().shaping_started(class.as_str());
+    let response = next.run(request).await;
+    s3_metrics().shaping_finished(class.as_str());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let c = |m: Method, path: &str, q: Option<&str>| OpClass::classify(&m, path, q);
+        assert_eq!(c(Method::GET, "/", None), OpClass::List);
+        assert_eq!(c(Method::GET, "/b", Some("list-type=2")), OpClass::List);
+        assert_eq!(c(Method::GET, "/b/", None), OpClass::List);
+        assert_eq!(c(Method::GET, "/b", Some("versioning")), OpClass::Read);
+        assert_eq!(c(Method::GET, "/b/k", None), OpClass::Read);
+        assert_eq!(c(Method::GET, "/b/k", Some("uploadId=x")), OpClass::List);
+        assert_eq!(c(Method::HEAD, "/b/k", None), OpClass::Read);
+        assert_eq!(c(Method::PUT, "/b/k", None), OpClass::Write);
+        assert_eq!(c(Method::POST, "/b/k", Some("uploads")), OpClass::Write);
+        assert_eq!(c(Method::POST, "/b", Some("delete")), OpClass::Delete);
+        assert_eq!(
+            c(Method::POST, "/b", Some("prefix-delete&prefix=x/")),
+            OpClass::Delete
+        );
+        assert_eq!(c(Method::DELETE, "/b/k", None), OpClass::Delete);
+    }
+
+    #[test]
+    fn test_parse_shares() {
+        assert_eq!(
+            ShapingConfig::parse_shares("read=4, list=2").unwrap(),
+            [4, 1, 2, 1]
+        );
+        assert!(ShapingConfig::parse_shares("scan=1").is_err());
+        assert!(ShapingConfig::parse_shares("read=0").is_err());
+    }
+
+    #[test]
+    fn test_pool_sizes() {
+        let config = ShapingConfig {
+            max_concurrency: 100,
+            shares: [40, 40, 10, 10],
+            queue_timeout: Duration::from_secs(1),
+        };
+        assert_eq!(config.pool_sizes(), [40, 40, 10, 10]);
+
+        let tiny = ShapingConfig {
+            max_concurrency: 2,
+            ..config
+        };
+        assert_eq!(tiny.pool_sizes(), [1, 1, 1, 1]);
+    }
+}
@@ -4,8 +4,10 @@
 /// Block size is 4MB with ~96 bytes overhead, so use 4MB - 4KB for safety margin
 const MAX_SHARD_SIZE: usize = 4 * 1024 * 1024 - 4096; // ~4MB per shard
 
+use crate::key_encoding::encode_key;
 use crate::osd_pool::{
-    OsdPool, delete_object_meta_from_all, get_object_meta_from_any, put_object_meta_to_all,
+    OsdPool, delete_object_meta_from_all, get_object_meta_from_any,
+    get_object_version_meta_from_any, meta_lookup_nodes, put_object_meta_to_all,
     read_shard_from_osd, write_shard_to_osd,
 };
 use crate::scatter_gather::ScatterGatherEngine;
@@ -29,6 +31,7 @@ use objectio_erasure::{
 };
 use objectio_proto::metadata::{
     AbortMultipartUploadRequest,
+    AccessControlPolicy,
     BucketSseConfiguration,
     CompleteMultipartUploadRequest as ProtoCompleteMultipartUploadRequest,
     CreateAccessKeyRequest,
@@ -66,6 +69,7 @@ use objectio_proto::metadata::{
     ObjectMeta,
     ObjectRetention,
     PartInfo,
+    PutBucketAclRequest,
     PutBucketEncryptionRequest,
     PutBucketLifecycleRequest,
     PutBucketVersioningRequest,
@@ -127,6 +131,15 @@ pub struct AppState {
     /// at startup. Behind an `Arc<dyn>` so handlers can clone into async
     /// tasks without bound-lifetime issues.
     pub host_provider: Arc<dyn crate::host_provider::HostProvider>,
+    /// Server-side `?prefix-delete` jobs started on this gateway.
+    pub prefix_delete_jobs: crate::prefix_delete::PrefixDeleteJobs,
+    /// Per-prefix read tracking feeding `/_admin/advisor`.
+    pub access_tracker: crate::advisor::AccessTracker,
+    /// Decoded stripes keyed by `(object_id, stripe_id)`; filled by GETs
+    /// and by `/_admin/prefetch`.
+    pub stripe_cache: objectio_common::ByteCache<(Vec<u8>, u64)>,
+    /// `/_admin/prefetch` jobs started on this gateway.
+    pub prefetch_jobs: crate::prefetch::PrefetchJobs,
 }
 
 impl AppState {
@@ -291,6 +304,11 @@ struct SseCKey {
     md5_b64: String,
 }
 
+/// Header prefix for the SSE-C key of the object being read or written.
+const SSE_C_HEADER_PREFIX: &str = "x-amz-server-side-encryption-customer";
+/// Header prefix for the SSE-C key of a CopyObject source.
+const SSE_C_COPY_SOURCE_HEADER_PREFIX: &str = "x-amz-copy-source-server-side-encryption-customer";
+
 /// Parse + validate SSE-C customer-key headers.
 ///
 /// Returns `Ok(None)` when the headers are absent (i.e. this request isn't
@@ -298,22 +316,31 @@ struct SseCKey {
 /// `Err(Response)` for partial/malformed header sets.
 #[allow(clippy::result_large_err)]
 fn parse_sse_c_headers(headers: &HeaderMap) -> Result<Option<SseCKey>, Response> {
-    let algo = headers
-        .get("x-amz-server-side-encryption-customer-algorithm")
-        .and_then(|v| v.to_str().ok());
-    let key = headers
-        .get("x-amz-server-side-encryption-customer-key")
-        .and_then(|v| v.to_str().ok());
-    let md5 = headers
-        .get("x-amz-server-side-encryption-customer-key-md5")
-        .and_then(|v| v.to_str().ok());
+    parse_sse_c_headers_with_prefix(headers, SSE_C_HEADER_PREFIX)
+}
+
+/// [`parse_sse_c_headers`] for an arbitrary header family — CopyObject
+/// carries the source key under `x-amz-copy-source-server-side-encryption-customer-*`.
+#[allow(clippy::result_large_err)]
+fn parse_sse_c_headers_with_prefix(
+    headers: &HeaderMap,
+    prefix: &str,
+) -> Result<Option<SseCKey>, Response> {
+    let get = |suffix: &str| {
+        headers
+            .get(format!("{prefix}-{suffix}"))
+            .and_then(|v| v.to_str().ok())
+    };
+    let algo = get("algorithm");
+    let key = get("key");
+    let md5 = get("key-md5");
     match (algo, key, md5) {
         (None, None, None) => Ok(None),
         (Some(a), Some(k), Some(m)) => {
             if a != "AES256" {
                 return Err(S3Error::xml_response(
                     "InvalidArgument",
-                    "x-amz-server-side-encryption-customer-algorithm must be AES256",
+                    &format!("{prefix}-algorithm must be AES256"),
                     StatusCode::BAD_REQUEST,
                 ));
             }
@@ -344,7 +371,7 @@ fn parse_sse_c_headers(headers: &HeaderMap) -> Result<Option<SseCKey>, Response>
             if computed_b64 != m {
                 return Err(S3Error::xml_response(
                     "InvalidArgument",
-                    "x-amz-server-side-encryption-customer-key-md5 does not match MD5 of the customer key",
+                    &format!("{prefix}-key-md5 does not match MD5 of the customer key"),
                     StatusCode::BAD_REQUEST,
                 ));
             }
@@ -357,12 +384,27 @@ fn parse_sse_c_headers(headers: &HeaderMap) -> Result<Option<SseCKey>, Response>
         }
         _ => Err(S3Error::xml_response(
             "InvalidRequest",
-            "SSE-C requires all three x-amz-server-side-encryption-customer-* headers",
+            &format!("SSE-C requires all three {prefix}-* headers"),
             StatusCode::BAD_REQUEST,
         )),
     }
 }
 
+/// Reject an SSE-C key that isn't the one `object` was written with.
+/// Objects written before the key MD5 was recorded can't be checked and
+/// are let through.
+#[allow(clippy::result_large_err)]
+fn check_sse_c_key(object: &ObjectMeta, cust: &SseCKey) -> Result<(), Response> {
+    if object.sse_customer_key_md5.is_empty() || object.sse_customer_key_md5 == cust.md5_b64 {
+        return Ok(());
+    }
+    Err(S3Error::xml_response(
+        "AccessDenied",
+        "The provided customer key does not match the key used to encrypt the object",
+        StatusCode::FORBIDDEN,
+    ))
+}
+
 /// Parse the optional `x-amz-server-side-encryption-context` header.
 ///
 /// AWS S3 delivers this as a base64-encoded JSON object of string→string.
@@ -600,6 +642,19 @@ fn add_metadata_headers(
     builder
 }
 
+/// Add `x-amz-object-lock-*` headers for a locked object
+fn add_object_lock_headers(
+    mut builder: http::response::Builder,
+    object: &ObjectMeta,
+) -> http::response::Builder {
+    for (name, value) in
+        crate::object_lock::response_headers(object.retention.as_ref(), object.legal_hold.as_ref())
+    {
+        builder = builder.header(name, value);
+    }
+    builder
+}
+
 /// Parsed Range header
 #[derive(Debug, Clone, Copy)]
 struct ByteRange {
@@ -727,13 +782,17 @@ fn sse_condition_vars(headers: Option<&HeaderMap>) -> HashMap<String, String> {
     vars
 }
 
-/// Check bucket policy and return error response if access is denied.
+/// Check bucket policy and ACLs and return error response if access is denied.
+///
+/// An explicit policy Deny or Allow decides; anything the policy leaves
+/// implicit (no policy, no matching statement) falls through to the
+/// bucket/object ACL (see [`check_acl`]).
 ///
 /// `headers` (when provided) is used to populate request-side condition
 /// keys like `s3:x-amz-server-side-encryption`. Most callers pass the
 /// incoming HTTP request headers; callers without a header-carrying
 /// context (internal RPCs) can pass `None`.
-async fn check_bucket_policy(
+pub(crate) async fn check_bucket_policy(
     state: &AppState,
     bucket: &str,
     user_arn: &str,
@@ -782,10 +841,8 @@ async fn check_bucket_policy(
                                 ))
                             }
                             PolicyDecision::ImplicitDeny => {
-                                // No explicit allow in policy - check if user is bucket owner
-                                // For now, we allow implicit deny (owner has full access)
-                                // In production, you'd check if user is the bucket owner
-                                None
+                                // No explicit allow in policy - the ACL decides
+                                check_acl(state, bucket, user_arn, action, resource).await
                             }
                             PolicyDecision::Allow => None,
                         }
@@ -793,12 +850,12 @@ async fn check_bucket_policy(
                     Err(e) => {
                         error!("Failed to parse bucket policy: {}", e);
                         // Invalid policy, don't block - log and continue
-                        None
+                        check_acl(state, bucket, user_arn, action, resource).await
                     }
                 }
             } else {
-                // No policy set - allow (owner-only access by default)
-                None
+                // No policy set - the ACL decides
+                check_acl(state, bucket, user_arn, action, resource).await
             }
         }
         Err(e) => {
@@ -806,13 +863,96 @@ async fn check_bucket_policy(
             if e.code() != tonic::Code::NotFound {
                 error!("Failed to fetch bucket policy: {}", e);
             }
-            None
+            check_acl(state, bucket, user_arn, action, resource).await
+        }
+    }
+}
+
+/// Evaluate bucket/object ACLs for an action the bucket policy left
+/// implicit.
+///
+/// Buckets without an ACL (created before ACL support) stay open to any
+/// authenticated caller. The system admin and the bucket owner always pass.
+/// Object-level actions use the object's ACL when it has one and fall back
+/// to the bucket's otherwise.
+async fn check_acl(
+    state: &AppState,
+    bucket: &str,
+    user_arn: &str,
+    action: &str,
+    resource: &str,
+) -> Option<Response> {
+    if user_arn == ADMIN_USER_ARN {
+        return None;
+    }
+    let mut client = state.meta_client.clone();
+    let bucket_acl = match client
+        .get_bucket(GetBucketRequest {
+            name: bucket.to_string(),
+        })
+        .await
+    {
+        Ok(resp) => resp.into_inner().bucket.and_then(|b| b.acl)?,
+        Err(e) => {
+            // Missing bucket is reported by the handler itself
+            if e.code() != tonic::Code::NotFound {
+                error!("Failed to fetch bucket ACL: {}", e);
+            }
+            return None;
+        }
+    };
+    if user_arn == bucket_acl.owner_id {
+        return None;
+    }
+
+    let allowed = match crate::acl::required_permission(action) {
+        None => false,
+        Some((crate::acl::AclTarget::Bucket, permission)) => {
+            crate::acl::allows(&bucket_acl, Some(user_arn), permission)
+        }
+        Some((crate::acl::AclTarget::Object, permission)) => {
+            let object_acl = match resource
+                .strip_prefix(&build_s3_arn(bucket, None))
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                Some(key) => match get_placement_nodes_for_object(state, bucket, key).await {
+                    Ok(nodes) => get_object_meta_from_any(&state.osd_pool, &nodes, bucket, key)
+                        .await
+                        .ok()
+                        .flatten()
+                        .and_then(|m| m.acl),
+                    Err(_) => None,
+                },
+                None => None,
+            };
+            crate::acl::allows(
+                object_acl.as_ref().unwrap_or(&bucket_acl),
+                Some(user_arn),
+                permission,
+            )
         }
+    };
+    if allowed {
+        return None;
     }
+    debug!("ACL denied access: {} {} {}", user_arn, action, resource);
+    Some(S3Error::xml_response(
+        "AccessDenied",
+        "Access Denied",
+        StatusCode::FORBIDDEN,
+    ))
+}
+
+/// Validate an `encoding-type` query parameter (see
+/// [`crate::key_encoding::parse_encoding_type`]).
+#[allow(clippy::result_large_err)]
+pub(crate) fn parse_encoding_type(encoding_type: Option<&str>) -> Result<bool, Response> {
+    crate::key_encoding::parse_encoding_type(encoding_type)
+        .map_err(|e| S3Error::xml_response("InvalidArgument", &e, StatusCode::BAD_REQUEST))
 }
 
 /// Build ARN for an S3 resource
-fn build_s3_arn(bucket: &str, key: Option<&str>) -> String {
+pub(crate) fn build_s3_arn(bucket: &str, key: Option<&str>) -> String {
     match key {
         Some(k) => format!("arn:obio:s3:::{}/{}", bucket, k),
         None => format!("arn:obio:s3:::{}", bucket),
@@ -828,6 +968,8 @@ pub struct ListObjectsParams {
     max_keys: Option<u32>,
     #[serde(rename = "continuation-token")]
     continuation_token: Option<String>,
+    #[serde(rename = "start-after")]
+    start_after: Option<String>,
     /// If present (even empty), this is a policy request
     policy: Option<String>,
     /// If present, this is a list object versions request
@@ -841,6 +983,24 @@ pub struct ListObjectsParams {
     lifecycle: Option<String>,
     /// If present, this is a get bucket encryption request
     encryption: Option<String>,
+    /// If present, report prefix-delete job progress
+    #[serde(rename = "prefix-delete")]
+    prefix_delete: Option<String>,
+    #[serde(rename = "job-id")]
+    job_id: Option<String>,
+    /// If present, this is a get bucket ACL request
+    acl: Option<String>,
+    /// If present, this is a list multipart uploads request
+    uploads: Option<String>,
+    #[serde(rename = "key-marker")]
+    key_marker: Option<String>,
+    #[serde(rename = "upload-id-marker")]
+    upload_id_marker: Option<String>,
+    #[serde(rename = "max-uploads")]
+    max_uploads: Option<u32>,
+    /// `url` asks for keys and prefixes to be URL-encoded in the response
+    #[serde(rename = "encoding-type")]
+    encoding_type: Option<String>,
 }
 
 impl ListObjectsParams {
@@ -861,6 +1021,10 @@ pub struct PostBucketParams {
     /// If present, this is a prefix-scoped grep across multiple keys.
     /// The request body carries a [`grep::PrefixGrepRequest`].
     grep: Option<String>,
+    /// If present, start a server-side delete of every key under `prefix`
+    #[serde(rename = "prefix-delete")]
+    prefix_delete: Option<String>,
+    prefix: Option<String>,
 }
 
 impl PostBucketParams {
@@ -884,6 +1048,8 @@ pub struct PutBucketParams {
     lifecycle: Option<String>,
     /// If present, this is a put bucket encryption request
     encryption: Option<String>,
+    /// If present, this is a put bucket ACL request
+    acl: Option<String>,
 }
 
 /// Query parameters for DELETE bucket operations
@@ -898,6 +1064,11 @@ pub struct DeleteBucketParams {
     lifecycle: Option<String>,
     /// If present, this is a bucket encryption delete request
     encryption: Option<String>,
+    /// If present, cancel the prefix-delete job named by `job-id`
+    #[serde(rename = "prefix-delete")]
+    prefix_delete: Option<String>,
+    #[serde(rename = "job-id")]
+    job_id: Option<String>,
 }
 
 /// Query parameters for PUT object operations (handles both simple PUT and multipart)
@@ -914,6 +1085,8 @@ pub struct PutObjectParams {
     /// If present, this is a put legal hold request
     #[serde(rename = "legal-hold")]
     legal_hold: Option<String>,
+    /// If present, this is a put object ACL request
+    acl: Option<String>,
 }
 
 /// Query parameters for GET object operations (handles both GET and list parts)
@@ -937,6 +1110,11 @@ pub struct GetObjectParams {
     /// If present, this is a get legal hold request
     #[serde(rename = "legal-hold")]
     legal_hold: Option<String>,
+    /// If present, this is a get object ACL request
+    acl: Option<String>,
+    /// `url` asks for the key to be URL-encoded in a list parts response
+    #[serde(rename = "encoding-type")]
+    encoding_type: Option<String>,
 }
 
 /// Query parameters for POST object operations (handles multipart initiate/complete)
@@ -952,6 +1130,11 @@ pub struct PostObjectParams {
     /// [`grep::GrepEvent`] per line. The query-string value is ignored
     /// — presence alone is the signal.
     grep: Option<String>,
+    /// If present, S3 Select (`SelectObjectContent`); see `select.rs`.
+    select: Option<String>,
+    /// Must be `2` alongside `?select`
+    #[serde(rename = "select-type")]
+    select_type: Option<String>,
 }
 
 /// Query parameters for DELETE object operations (handles both delete and abort)
@@ -1008,6 +1191,12 @@ pub struct ListBucketResult {
     #[serde(rename = "Delimiter")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delimiter: Option<String>,
+    #[serde(rename = "EncodingType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding_type: Option<String>,
+    #[serde(rename = "StartAfter")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_after: Option<String>,
     #[serde(rename = "MaxKeys")]
     pub max_keys: u32,
     #[serde(rename = "KeyCount")]
@@ -1026,6 +1215,34 @@ pub struct ListBucketResult {
     pub contents: Vec<ObjectContent>,
 }
 
+impl ListBucketResult {
+    /// Render as the XML response body, URL-encoding keys, prefixes and
+    /// the delimiter for `encoding-type=url`.
+    fn into_response(mut self, url_encode: bool) -> Response {
+        if url_encode {
+            self.encoding_type = Some("url".to_string());
+            self.prefix = encode_key(self.prefix, true);
+            self.delimiter = self.delimiter.map(|d| encode_key(d, true));
+            self.start_after = self.start_after.map(|k| encode_key(k, true));
+            for p in &mut self.common_prefixes {
+                p.prefix = encode_key(std::mem::take(&mut p.prefix), true);
+            }
+            for c in &mut self.contents {
+                c.key = encode_key(std::mem::take(&mut c.key), true);
+            }
+        }
+        let xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}",
+            to_xml(&self).unwrap_or_default()
+        );
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/xml")
+            .body(Body::from(xml))
+            .unwrap()
+    }
+}
+
 #[derive(Serialize)]
 pub struct CommonPrefix {
     #[serde(rename = "Prefix")]
@@ -1122,6 +1339,9 @@ pub struct ListPartsResult {
     pub key: String,
     #[serde(rename = "UploadId")]
     pub upload_id: String,
+    #[serde(rename = "EncodingType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding_type: Option<String>,
     #[serde(rename = "PartNumberMarker")]
     pub part_number_marker: u32,
     #[serde(rename = "NextPartNumberMarker")]
@@ -1152,10 +1372,17 @@ pub struct PartItem {
 /// Response for ListMultipartUploads
 #[derive(Serialize)]
 #[serde(rename = "ListMultipartUploadsResult")]
-#[allow(dead_code)]
 pub struct ListMultipartUploadsResult {
     #[serde(rename = "Bucket")]
     pub bucket: String,
+    #[serde(rename = "Prefix")]
+    pub prefix: String,
+    #[serde(rename = "Delimiter")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delimiter: Option<String>,
+    #[serde(rename = "EncodingType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding_type: Option<String>,
     #[serde(rename = "KeyMarker")]
     pub key_marker: String,
     #[serde(rename = "UploadIdMarker")]
@@ -1173,11 +1400,13 @@ pub struct ListMultipartUploadsResult {
     #[serde(rename = "Upload")]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub uploads: Vec<UploadItem>,
+    #[serde(rename = "CommonPrefixes")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub common_prefixes: Vec<CommonPrefix>,
 }
 
 /// Upload item in ListMultipartUploads response
 #[derive(Serialize)]
-#[allow(dead_code)]
 pub struct UploadItem {
     #[serde(rename = "Key")]
     pub key: String,
@@ -1291,6 +1520,99 @@ fn timestamp_to_http_date(ts: u64) -> String {
         .unwrap_or_else(|| "Thu, 01 Jan 1970 00:00:00 GMT".to_string())
 }
 
+/// The response for a failed GET/HEAD precondition, or `None` to serve
+/// the object. HEAD responses carry no body.
+fn precondition_response(
+    outcome: crate::conditional::Outcome,
+    object: &ObjectMeta,
+    head: bool,
+) -> Option<Response> {
+    use crate::conditional::Outcome;
+    match outcome {
+        Outcome::Proceed => None,
+        Outcome::NotModified => Some(
+            Response::builder()
+                .status(StatusCode::NOT_MODIFIED)
+                .header("ETag", &object.etag)
+                .header(
+                    header::LAST_MODIFIED,
+                    timestamp_to_http_date(object.modified_at),
+                )
+                .body(Body::empty())
+                .unwrap(),
+        ),
+        Outcome::PreconditionFailed if head => Some(
+            Response::builder()
+                .status(StatusCode::PRECONDITION_FAILED)
+                .body(Body::empty())
+                .unwrap(),
+        ),
+        Outcome::PreconditionFailed => Some(precondition_failed()),
+    }
+}
+
+fn precondition_failed() -> Response {
+    S3Error::xml_response(
+        "PreconditionFailed",
+        "At least one of the pre-conditions you specified did not hold",
+        StatusCode::PRECONDITION_FAILED,
+    )
+}
+
+/// Enforce `If-Match` / `If-None-Match` on a PUT or CopyObject destination
+/// by looking up the object currently stored under `key`.
+#[allow(clippy::result_large_err)]
+async fn check_write_preconditions(
+    state: &AppState,
+    bucket: &str,
+    key: &str,
+    headers: &HeaderMap,
+) -> Result<(), Response> {
+    use crate::conditional::WriteConflict;
+    if !crate::conditional::has_write_conditions(headers) {
+        return Ok(());
+    }
+    let mut meta_client = state.meta_client.clone();
+    let placement = meta_client
+        .get_placement(GetPlacementRequest {
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+            size: 0,
+            storage_class: "STANDARD".to_string(),
+            pool: String::new(),
+        })
+        .await
+        .map_err(|e| {
+            S3Error::xml_response(
+                "InternalError",
+                &format!("Failed to get placement: {e}"),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        })?
+        .into_inner();
+    let current =
+        get_object_meta_from_any(&state.osd_pool, &meta_lookup_nodes(&placement), bucket, key)
+            .await
+            .map_err(|e| {
+                S3Error::xml_response(
+                    "InternalError",
+                    &e.to_string(),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                )
+            })?
+            .filter(|m| !m.is_delete_marker);
+
+    match crate::conditional::evaluate_write(headers, current.as_ref().map(|m| m.etag.as_str())) {
+        Ok(()) => Ok(()),
+        Err(WriteConflict::NoSuchKey) => Err(S3Error::xml_response(
+            "NoSuchKey",
+            "The specified key does not exist",
+            StatusCode::NOT_FOUND,
+        )),
+        Err(WriteConflict::PreconditionFailed) => Err(precondition_failed()),
+    }
+}
+
 /// List all buckets (GET /)
 pub async fn list_buckets(
     State(state): State<Arc<AppState>>,
@@ -1376,6 +1698,10 @@ pub async fn create_bucket(
     if params.encryption.is_some() {
         return put_bucket_encryption_internal(state, bucket, body).await;
     }
+    if params.acl.is_some() {
+        return put_bucket_acl_internal(state, bucket, auth.map(|Extension(a)| a), headers, body)
+            .await;
+    }
 
     // Check for object lock at bucket creation
     let enable_lock = headers
@@ -1390,6 +1716,15 @@ pub async fn create_bucket(
         .map(|Extension(a)| a.tenant.clone())
         .unwrap_or_default();
 
+    // Authenticated callers own the buckets they create
+    let acl = match auth.as_ref() {
+        Some(Extension(a)) => match canned_acl_from_headers(&headers, &a.user_arn) {
+            Ok(acl) => Some(acl),
+            Err(resp) => return resp,
+        },
+        None => None,
+    };
+
     match client
         .create_bucket(CreateBucketRequest {
             name: bucket.clone(),
@@ -1397,6 +1732,7 @@ pub async fn create_bucket(
             storage_class: "STANDARD".to_string(),
             region: "us-east-1".to_string(),
             tenant,
+            acl,
         })
         .await
     {
@@ -1464,11 +1800,21 @@ pub async fn post_bucket(
 ) -> Response {
     // Check if this is a delete objects request
     if params.is_delete_request() {
-        return delete_objects(State(state), Path(bucket), auth, body).await;
+        return delete_objects(State(state), Path(bucket), auth, headers, body).await;
     }
     if params.grep.is_some() {
         return grep_prefix_internal(state, bucket, auth, headers, body).await;
     }
+    if params.prefix_delete.is_some() {
+        return crate::prefix_delete::start_prefix_delete(
+            state,
+            bucket,
+            params.prefix,
+            auth,
+            headers,
+        )
+        .await;
+    }
 
     // Unknown POST operation on bucket
     S3Error::xml_response(
@@ -1493,6 +1839,13 @@ pub async fn delete_bucket(
     if params.encryption.is_some() {
         return delete_bucket_encryption_internal(state, bucket).await;
     }
+    if params.prefix_delete.is_some() {
+        return crate::prefix_delete::cancel_prefix_delete(
+            &state,
+            &bucket,
+            params.job_id.as_deref(),
+        );
+    }
 
     let mut client = state.meta_client.clone();
 
@@ -1591,12 +1944,26 @@ pub async fn list_objects(
     if params.encryption.is_some() {
         return get_bucket_encryption_internal(state, bucket).await;
     }
+    if params.prefix_delete.is_some() {
+        return crate::prefix_delete::get_prefix_delete(&state, &bucket, params.job_id.as_deref());
+    }
+    if params.acl.is_some() {
+        return get_bucket_acl_internal(state, bucket, auth.map(|Extension(a)| a)).await;
+    }
+    if params.uploads.is_some() {
+        return list_multipart_uploads_internal(state, bucket, &params).await;
+    }
     if params.versions.is_some() {
+        let url_encode = match parse_encoding_type(params.encoding_type.as_deref()) {
+            Ok(e) => e,
+            Err(resp) => return resp,
+        };
         return list_object_versions_internal(
             state,
             bucket,
             params.prefix.clone().unwrap_or_default(),
             params.max_keys.unwrap_or(1000),
+            url_encode,
         )
         .await;
     }
@@ -1619,10 +1986,15 @@ pub async fn list_objects(
         }
     }
 
+    let url_encode = match parse_encoding_type(params.encoding_type.as_deref()) {
+        Ok(e) => e,
+        Err(resp) => return resp,
+    };
     let prefix = params.prefix.clone().unwrap_or_default();
-    let delimiter = params.delimiter.clone();
+    let delimiter = params.delimiter.clone().filter(|d| !d.is_empty());
     let max_keys = params.max_keys.unwrap_or(1000);
     let continuation_token = params.continuation_token.as_deref();
+    let start_after = params.start_after.clone().filter(|s| !s.is_empty());
 
     // First verify bucket exists
     let mut client = state.meta_client.clone();
@@ -1661,7 +2033,7 @@ pub async fn list_objects(
             bucket: bucket.clone(),
             prefix: prefix.clone(),
             delimiter: delimiter.clone().unwrap_or_default(),
-            start_after: String::new(),
+            start_after: start_after.clone().unwrap_or_default(),
             continuation_token: continuation_token
                 .map(ToString::to_string)
                 .unwrap_or_default(),
@@ -1696,6 +2068,8 @@ pub async fn list_objects(
                     name: bucket.clone(),
                     prefix: prefix.clone(),
                     delimiter: delimiter.clone(),
+                    encoding_type: None,
+                    start_after: start_after.clone(),
                     max_keys,
                     is_truncated: r.is_truncated,
                     next_continuation_token: if r.next_continuation_token.is_empty() {
@@ -1707,15 +2081,7 @@ pub async fn list_objects(
                     common_prefixes,
                     contents,
                 };
-                let xml = format!(
-                    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}",
-                    to_xml(&result).unwrap_or_default()
-                );
-                return Response::builder()
-                    .status(StatusCode::OK)
-                    .header(header::CONTENT_TYPE, "application/xml")
-                    .body(Body::from(xml))
-                    .unwrap();
+                return result.into_response(url_encode);
             }
         }
     }
@@ -1733,7 +2099,11 @@ pub async fn list_objects(
         )
         .await
     {
-        Ok(list_result) => {
+        Ok(mut list_result) => {
+            if let Some(after) = &start_after {
+                list_result.objects.retain(|o| o.key > *after);
+            }
+
             // Process delimiter to extract common prefixes
             let (contents, common_prefixes) = if let Some(ref delim) = delimiter {
                 let mut prefixes_set = std::collections::BTreeSet::new();
@@ -1792,6 +2162,8 @@ pub async fn list_objects(
                 name: bucket,
                 prefix,
                 delimiter,
+                encoding_type: None,
+                start_after,
                 max_keys,
                 is_truncated: list_result.is_truncated,
                 next_continuation_token: list_result.next_continuation_token,
@@ -1799,17 +2171,7 @@ pub async fn list_objects(
                 common_prefixes,
                 contents,
             };
-
-            let xml = format!(
-                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}",
-                to_xml(&result).unwrap_or_default()
-            );
-
-            Response::builder()
-                .status(StatusCode::OK)
-                .header(header::CONTENT_TYPE, "application/xml")
-                .body(Body::from(xml))
-                .unwrap()
+            result.into_response(url_encode)
         }
         Err(e) => {
             use crate::scatter_gather::ScatterGatherError;
@@ -1821,6 +2183,8 @@ pub async fn list_objects(
                         name: bucket,
                         prefix,
                         delimiter,
+                        encoding_type: None,
+                        start_after,
                         max_keys,
                         is_truncated: false,
                         next_continuation_token: None,
@@ -1828,15 +2192,7 @@ pub async fn list_objects(
                         common_prefixes: vec![],
                         contents: vec![],
                     };
-                    let xml = format!(
-                        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}",
-                        to_xml(&result).unwrap_or_default()
-                    );
-                    Response::builder()
-                        .status(StatusCode::OK)
-                        .header(header::CONTENT_TYPE, "application/xml")
-                        .body(Body::from(xml))
-                        .unwrap()
+                    result.into_response(url_encode)
                 }
                 ScatterGatherError::InvalidToken
                 | ScatterGatherError::TokenSignatureMismatch
@@ -1886,6 +2242,7 @@ async fn copy_sse_decision(
             key: source_key.to_string(),
             size: 0,
             storage_class: "STANDARD".to_string(),
+            pool: String::new(),
         })
         .await
         .map_err(|e| {
@@ -1905,7 +2262,7 @@ async fn copy_sse_decision(
     }
     let source_meta = match get_object_meta_from_any(
         &state.osd_pool,
-        &src_placement.nodes,
+        &meta_lookup_nodes(&src_placement),
         source_bucket,
         source_key,
     )
@@ -1930,27 +2287,39 @@ async fn copy_sse_decision(
 
     let src_algo =
         SseAlgorithm::try_from(source_meta.encryption_algorithm).unwrap_or(SseAlgorithm::SseNone);
-    if src_algo == SseAlgorithm::SseC {
-        return Err(S3Error::xml_response(
-            "NotImplemented",
-            "CopyObject for SSE-C source objects is not yet supported",
-            StatusCode::NOT_IMPLEMENTED,
-        ));
+    let src_customer_key =
+        parse_sse_c_headers_with_prefix(copy_headers, SSE_C_COPY_SOURCE_HEADER_PREFIX)?;
+    match (src_algo, &src_customer_key) {
+        (SseAlgorithm::SseC, Some(cust)) => check_sse_c_key(&source_meta, cust)?,
+        (SseAlgorithm::SseC, None) => {
+            return Err(S3Error::xml_response(
+                "InvalidRequest",
+                "The source object was stored using a form of SSE-C; the customer key must be provided in the x-amz-copy-source-server-side-encryption-customer-* headers",
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+        (_, Some(_)) => {
+            return Err(S3Error::xml_response(
+                "InvalidRequest",
+                "The source object is not encrypted with a customer-provided key",
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+        (_, None) => {}
     }
 
-    let dst_decision = resolve_sse_decision(meta_client, dest_bucket, Some(copy_headers)).await?;
-    if let Some(ref d) = dst_decision
-        && d.algorithm == SseAlgorithm::SseC
-    {
-        return Err(S3Error::xml_response(
-            "NotImplemented",
-            "CopyObject with an SSE-C destination is not yet supported",
-            StatusCode::NOT_IMPLEMENTED,
-        ));
+    // SSE-C destinations always go through the re-encrypting slow path:
+    // the PUT handler encrypts under the destination customer key.
+    if parse_sse_c_headers(copy_headers)?.is_some() {
+        return Ok(CopyDecision {
+            needs_reencrypt: true,
+        });
     }
+    let dst_decision = resolve_sse_decision(meta_client, dest_bucket, Some(copy_headers)).await?;
 
     // Fast-path eligibility: source and destination share exactly the same
-    // SSE parameters. Otherwise the bytes need to be re-encrypted.
+    // SSE parameters. Otherwise the bytes need to be re-encrypted. An SSE-C
+    // source never qualifies — its bytes are unreadable without the key.
     let needs_reencrypt = match (src_algo, dst_decision.as_ref()) {
         (SseAlgorithm::SseNone, None) => false,
         (SseAlgorithm::SseS3, Some(d)) if d.algorithm == SseAlgorithm::SseS3 => false,
@@ -1987,14 +2356,49 @@ async fn copy_object_reencrypt(
     );
 
     // 1. Read the source object as plaintext. The existing GET handler takes
-    //    care of reconstruction + decryption.
+    //    care of reconstruction + decryption; an SSE-C source key arrives
+    //    under the copy-source header family and is handed to GET under
+    //    the regular one.
+    let mut get_headers = HeaderMap::new();
+    for suffix in ["algorithm", "key", "key-md5"] {
+        if let Some(v) = copy_headers.get(format!("{SSE_C_COPY_SOURCE_HEADER_PREFIX}-{suffix}"))
+            && let Ok(name) =
+                http::HeaderName::from_bytes(format!("{SSE_C_HEADER_PREFIX}-{suffix}").as_bytes())
+        {
+            get_headers.insert(name, v.clone());
+        }
+    }
+    // Source preconditions ride along as the GET's own; a 304 from GET
+    // is a failed copy precondition.
+    for (copy_name, name) in [
+        (crate::conditional::COPY_SOURCE_IF_MATCH, header::IF_MATCH),
+        (
+            crate::conditional::COPY_SOURCE_IF_NONE_MATCH,
+            header::IF_NONE_MATCH,
+        ),
+        (
+            crate::conditional::COPY_SOURCE_IF_MODIFIED_SINCE,
+            header::IF_MODIFIED_SINCE,
+        ),
+        (
+            crate::conditional::COPY_SOURCE_IF_UNMODIFIED_SINCE,
+            header::IF_UNMODIFIED_SINCE,
+        ),
+    ] {
+        if let Some(v) = copy_headers.get(copy_name) {
+            get_headers.insert(name, v.clone());
+        }
+    }
     let get_resp = get_object(
         State(Arc::clone(&state)),
         Path((source_bucket.clone(), source_key.clone())),
         auth.clone(),
-        HeaderMap::new(),
+        get_headers,
     )
     .await;
+    if get_resp.status() == StatusCode::NOT_MODIFIED {
+        return precondition_failed();
+    }
     if !get_resp.status().is_success() {
         return get_resp;
     }
@@ -2102,32 +2506,52 @@ pub async fn put_object(
     headers: HeaderMap,
     body: Bytes,
 ) -> Response {
+    // Objects only carry their own ACL when x-amz-acl asks for one;
+    // otherwise the bucket's ACL applies
+    let object_acl = match auth.as_ref() {
+        Some(Extension(a)) if headers.contains_key("x-amz-acl") => {
+            match canned_acl_from_headers(&headers, &a.user_arn) {
+                Ok(acl) => Some(acl),
+                Err(resp) => return resp,
+            }
+        }
+        _ => None,
+    };
+
+    if let Err((code, msg)) = crate::key_encoding::validate_key(&key) {
+        return S3Error::xml_response(code, msg, StatusCode::BAD_REQUEST);
+    }
+
     // Check for copy source header (CopyObject operation)
-    let copy_source = headers
+    let copy_source = match headers
         .get("x-amz-copy-source")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| {
-            // URL decode and strip leading slash if present
-            let decoded = urlencoding::decode(s).unwrap_or_else(|_| s.into());
-            decoded.trim_start_matches('/').to_string()
-        });
+        .map(|v| {
+            v.to_str()
+                .map_err(|_| "x-amz-copy-source must be ASCII".to_string())
+        })
+        .transpose()
+        .and_then(|raw| raw.map(crate::key_encoding::parse_copy_source).transpose())
+    {
+        Ok(source) => source,
+        Err(e) => {
+            return S3Error::xml_response("InvalidArgument", &e, StatusCode::BAD_REQUEST);
+        }
+    };
 
     // CopyObject: metadata-only fast path when source and destination SSE
-    // match; decrypt→re-encrypt slow path when they differ. SSE-C on either
-    // side is deliberately unsupported (requires a separate set of
-    // copy-source-* customer-key headers that we don't wire through yet).
+    // match; decrypt→re-encrypt slow path when they differ or either side
+    // is SSE-C (source key in x-amz-copy-source-server-side-encryption-
+    // customer-*, destination key in the regular customer-* headers).
     if let Some(ref source) = copy_source {
-        // Parse source bucket/key (format: "bucket/key" or "/bucket/key")
-        let parts: Vec<&str> = source.splitn(2, '/').collect();
-        if parts.len() != 2 {
+        if source.version_id.is_some() {
             return S3Error::xml_response(
-                "InvalidArgument",
-                "Invalid x-amz-copy-source format",
-                StatusCode::BAD_REQUEST,
+                "NotImplemented",
+                "Copying a specific source version is not supported",
+                StatusCode::NOT_IMPLEMENTED,
             );
         }
-        let source_bucket = parts[0];
-        let source_key = parts[1];
+        let source_bucket = source.bucket.as_str();
+        let source_key = source.key.as_str();
 
         // Auth check (PutObject on destination)
         if let Some(Extension(auth_result)) = &auth {
@@ -2147,12 +2571,22 @@ pub async fn put_object(
             }
         }
 
+        if let Err(resp) = check_write_preconditions(&state, &bucket, &key, &headers).await {
+            return resp;
+        }
+
+        // Lock settings are not copied from the source
+        let (retention, legal_hold) = match object_lock_for_put(&state, &bucket, &headers).await {
+            Ok(lock) => lock,
+            Err(resp) => return resp,
+        };
+
         let mut meta_client = state.meta_client.clone();
 
         // Pre-flight: peek at the source object's SSE state + resolve the
         // destination SSE decision from headers/bucket-default. If they
-        // match, stay on the metadata-only fast path below. If they differ
-        // (and neither side is SSE-C), take the re-encrypting slow path.
+        // match, stay on the metadata-only fast path below. If they differ,
+        // take the re-encrypting slow path.
         let copy_decision = match copy_sse_decision(
             &state,
             &mut meta_client,
@@ -2194,6 +2628,7 @@ pub async fn put_object(
                 key: source_key.to_string(),
                 size: 0,
                 storage_class: "STANDARD".to_string(),
+                pool: String::new(),
             })
             .await
         {
@@ -2223,6 +2658,7 @@ pub async fn put_object(
                 key: key.clone(),
                 size: 0,
                 storage_class: "STANDARD".to_string(),
+                pool: String::new(),
             })
             .await
         {
@@ -2252,7 +2688,7 @@ pub async fn put_object(
         let dest_meta = {
             let source_meta = match get_object_meta_from_any(
                 &state.osd_pool,
-                &src_placement.nodes,
+                &meta_lookup_nodes(&src_placement),
                 source_bucket,
                 source_key,
             )
@@ -2275,6 +2711,14 @@ pub async fn put_object(
                     );
                 }
             };
+            if crate::conditional::evaluate_copy_source(
+                &headers,
+                &source_meta.etag,
+                source_meta.modified_at,
+            ) != crate::conditional::Outcome::Proceed
+            {
+                return precondition_failed();
+            }
 
             let now = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -2287,6 +2731,9 @@ pub async fn put_object(
                 etag: new_etag,
                 created_at: now,
                 modified_at: now,
+                acl: object_acl,
+                retention,
+                legal_hold,
                 ..source_meta
             };
 
@@ -2358,6 +2805,15 @@ pub async fn put_object(
         }
     }
 
+    if let Err(resp) = check_write_preconditions(&state, &bucket, &key, &headers).await {
+        return resp;
+    }
+
+    let (retention, legal_hold) = match object_lock_for_put(&state, &bucket, &headers).await {
+        Ok(lock) => lock,
+        Err(resp) => return resp,
+    };
+
     let mut meta_client = state.meta_client.clone();
 
     // Generate object ID and ETag (MD5 of the *plaintext* body — matches AWS
@@ -2406,6 +2862,7 @@ pub async fn put_object(
             key: key.clone(),
             size: original_size,
             storage_class: "STANDARD".to_string(),
+            pool: String::new(),
         })
         .await
     {
@@ -2583,13 +3040,15 @@ pub async fn put_object(
             version_id: version_id.clone(),
             storage_class: "STANDARD".to_string(),
             is_delete_marker: false,
-            retention: None,
-            legal_hold: None,
+            retention,
+            legal_hold,
             encryption_algorithm: sse_algorithm as i32,
             kms_key_id: sse_kms_key_id.clone(),
             encrypted_dek: sse_encrypted_dek.clone(),
             encryption_iv: sse_iv.clone(),
             encryption_context: sse_encryption_context.clone(),
+            sse_customer_key_md5: sse_c_key_md5.clone(),
+            acl: object_acl,
         };
 
         if let Err(e) = put_object_meta_to_all(
@@ -2892,13 +3351,15 @@ pub async fn put_object(
         version_id: version_id.clone(),
         storage_class: "STANDARD".to_string(),
         is_delete_marker: false,
-        retention: None,
-        legal_hold: None,
+        retention,
+        legal_hold,
         encryption_algorithm: sse_algorithm as i32,
         kms_key_id: sse_kms_key_id.clone(),
         encrypted_dek: sse_encrypted_dek,
         encryption_iv: sse_iv,
         encryption_context: sse_encryption_context,
+        sse_customer_key_md5: sse_c_key_md5.clone(),
+        acl: object_acl,
     };
 
     if let Err(e) = put_object_meta_to_all(
@@ -3013,6 +3474,8 @@ pub async fn get_object(
         }
     }
 
+    state.access_tracker.record(&bucket, &key);
+
     let mut meta_client = state.meta_client.clone();
 
     // Get placement to find primary OSD (CRUSH is deterministic)
@@ -3022,6 +3485,7 @@ pub async fn get_object(
             key: key.clone(),
             size: 0, // Size not needed for lookup
             storage_class: "STANDARD".to_string(),
+            pool: String::new(),
         })
         .await
     {
@@ -3086,8 +3550,13 @@ pub async fn get_object(
     // (e.g. topology changed), fetch all active nodes as fallback
     // This is done lazily below only if a node_id is missing from the map.
 
-    let object = match get_object_meta_from_any(&state.osd_pool, &placement.nodes, &bucket, &key)
-        .await
+    let object = match get_object_meta_from_any(
+        &state.osd_pool,
+        &meta_lookup_nodes(&placement),
+        &bucket,
+        &key,
+    )
+    .await
     {
         Ok(Some(obj)) => obj,
         Ok(None) => {
@@ -3103,6 +3572,11 @@ pub async fn get_object(
         }
     };
 
+    let outcome = crate::conditional::evaluate_read(&headers, &object.etag, object.modified_at);
+    if let Some(resp) = precondition_response(outcome, &object, false) {
+        return resp;
+    }
+
     // Check for stripes
     if object.stripes.is_empty() {
         error!("Object has no stripe metadata: {}/{}", bucket, key);
@@ -3197,6 +3671,9 @@ pub async fn get_object(
                 }
                 Err(resp) => return resp,
             };
+            if let Err(resp) = check_sse_c_key(&object, &cust) {
+                return resp;
+            }
             (Some(cust.key), None, cust.md5_b64)
         }
     };
@@ -3295,15 +3772,33 @@ pub async fn get_object(
             );
         };
 
-        // Replication mode: just read raw data from any replica
-        if stripe_ec_type == ErasureType::ErasureReplication {
+        // Use stripe's object_id if available (for multipart uploads)
+        // Fall back to object.object_id for backwards compat
+        let shard_object_id = if !stripe.object_id.is_empty() {
+            &stripe.object_id
+        } else {
+            &object.object_id
+        };
+
+        // Object IDs are never reused across writes, so a cached stripe
+        // can't go stale. Cached bytes are the decoded stripe as stored —
+        // still encrypted for SSE objects.
+        let cache_key = (shard_object_id.clone(), stripe.stripe_id);
+        let stripe_data = if let Some(cached) = state.stripe_cache.get(&cache_key) {
+            debug!(
+                "Stripe {} of {}/{} served from cache",
+                stripe_idx, bucket, key
+            );
+            cached.to_vec()
+        } else if stripe_ec_type == ErasureType::ErasureReplication {
+            // Replication mode: just read raw data from any replica
             debug!(
                 "Reading replicated stripe {} of {}/{}: size={}",
                 stripe_idx, bucket, key, stripe_data_size
             );
 
             // Try each replica until we get the data
-            let mut data_read = false;
+            let mut replica_data = None;
             for shard_loc in &stripe.shards {
                 let node_addr = resolve_node_address(
                     &mut node_address_map,
@@ -3320,14 +3815,6 @@ pub async fn get_object(
                     local_group: shard_loc.local_group,
                 };
 
-                // Use stripe's object_id if available (for multipart uploads)
-                // Fall back to object.object_id for backwards compat
-                let shard_object_id = if !stripe.object_id.is_empty() {
-                    &stripe.object_id
-                } else {
-                    &object.object_id
-                };
-
                 match read_shard_from_osd(
                     &state.osd_pool,
                     &node_placement,
@@ -3337,47 +3824,15 @@ pub async fn get_object(
                 )
                 .await
                 {
-                    Ok(data) => {
+                    Ok(mut data) => {
                         debug!(
                             "Read replicated data from replica {} ({} bytes)",
                             shard_loc.position,
                             data.len()
                         );
                         // Truncate to actual data size (in case of padding)
-                        let actual_data = if data.len() > stripe_data_size {
-                            data[..stripe_data_size].to_vec()
-                        } else {
-                            data
-                        };
-                        let (mut slice, slice_start_in_stripe): (Vec<u8>, u64) =
-                            if let Some(ref range) = resolved_range {
-                                let stripe_end = stripe_byte_offset + stripe_data_size as u64;
-                                let slice_start =
-                                    range.start.saturating_sub(stripe_byte_offset) as usize;
-                                let slice_end = std::cmp::min(range.end + 1, stripe_end)
-                                    .saturating_sub(stripe_byte_offset)
-                                    as usize;
-                                (
-                                    actual_data[slice_start..slice_end].to_vec(),
-                                    slice_start as u64,
-                                )
-                            } else {
-                                (actual_data, 0)
-                            };
-                        if let Some(dek) = get_sse_dek.as_ref()
-                            && let Err(resp) = decrypt_stripe_slice(
-                                dek,
-                                stripe,
-                                &object,
-                                stripe_byte_offset,
-                                slice_start_in_stripe,
-                                &mut slice,
-                            )
-                        {
-                            return resp;
-                        }
-                        all_data.extend(slice);
-                        data_read = true;
+                        data.truncate(stripe_data_size);
+                        replica_data = Some(data);
                         break;
                     }
                     Err(e) => {
@@ -3389,7 +3844,7 @@ pub async fn get_object(
                 }
             }
 
-            if !data_read {
+            let Some(data) = replica_data else {
                 error!(
                     "Failed to read any replica for stripe {} of {}/{}",
                     stripe_idx, bucket, key
@@ -3399,152 +3854,150 @@ pub async fn get_object(
                     "Failed to read object: no replicas available",
                     StatusCode::INTERNAL_SERVER_ERROR,
                 );
-            }
-
-            continue; // Move to next stripe
-        }
-
-        // EC mode: need to read k shards and decode
-        let total_shards = ec_k + ec_m;
-
-        debug!(
-            "Reading EC stripe {} of {}/{}: size={}, ec={}+{}",
-            stripe_idx, bucket, key, stripe_data_size, ec_k, ec_m
-        );
-
-        // Read shards from OSDs - we need at least k shards
-        let mut shards: Vec<Option<Vec<u8>>> = vec![None; total_shards];
-        let mut read_count = 0;
-
-        // Create a map of position -> shard location for quick lookup
-        let shard_map: HashMap<u32, &ShardLocation> =
-            stripe.shards.iter().map(|s| (s.position, s)).collect();
-
-        // Use stripe's object_id if available (for multipart uploads)
-        // Fall back to object.object_id for backwards compat
-        let ec_shard_object_id = if !stripe.object_id.is_empty() {
-            &stripe.object_id
+            };
+            state.stripe_cache.insert(cache_key, &data);
+            data
         } else {
-            &object.object_id
-        };
+            // EC mode: need to read k shards and decode
+            let total_shards = ec_k + ec_m;
 
-        // Rank all shard positions by topological distance to this
-        // gateway so reads pull from the nearest OSDs first. Any k of the
-        // total_shards positions decode correctly, so we no longer need a
-        // separate data-first / parity-fallback split — a single ranked
-        // pass handles both. Secondary sort key is position, which
-        // preserves the legacy "data shards before parity" preference
-        // when topology info is absent or ties.
-        let me = &state.self_topology;
-        let mut ranked_positions: Vec<(u32, objectio_placement::TopologyDistance)> = (0
-            ..total_shards as u32)
-            .filter_map(|pos| {
-                let shard_loc = shard_map.get(&pos)?;
-                let dist = node_topo_map
-                    .get(&shard_loc.node_id)
-                    .map_or(objectio_placement::TopologyDistance::Unknown, |fd| {
-                        objectio_placement::distance(me, fd)
-                    });
-                Some((pos, dist))
-            })
-            .collect();
-        ranked_positions.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+            debug!(
+                "Reading EC stripe {} of {}/{}: size={}, ec={}+{}",
+                stripe_idx, bucket, key, stripe_data_size, ec_k, ec_m
+            );
 
-        for (pos, dist) in ranked_positions {
-            if read_count >= ec_k {
-                break;
-            }
-            let Some(shard_loc) = shard_map.get(&pos) else {
-                continue;
-            };
-            let node_addr =
-                resolve_node_address(&mut node_address_map, &mut meta_client, &shard_loc.node_id)
-                    .await;
-            let node_placement = objectio_proto::metadata::NodePlacement {
-                position: shard_loc.position,
-                node_id: shard_loc.node_id.clone(),
-                node_address: node_addr,
-                disk_id: shard_loc.disk_id.clone(),
-                shard_type: shard_loc.shard_type,
-                local_group: shard_loc.local_group,
-            };
+            // Read shards from OSDs - we need at least k shards
+            let mut shards: Vec<Option<Vec<u8>>> = vec![None; total_shards];
+            let mut read_count = 0;
+
+            // Create a map of position -> shard location for quick lookup
+            let shard_map: HashMap<u32, &ShardLocation> =
+                stripe.shards.iter().map(|s| (s.position, s)).collect();
+
+            // Rank all shard positions by topological distance to this
+            // gateway so reads pull from the nearest OSDs first. Any k of the
+            // total_shards positions decode correctly, so we no longer need a
+            // separate data-first / parity-fallback split — a single ranked
+            // pass handles both. Secondary sort key is position, which
+            // preserves the legacy "data shards before parity" preference
+            // when topology info is absent or ties.
+            let me = &state.self_topology;
+            let mut ranked_positions: Vec<(u32, objectio_placement::TopologyDistance)> = (0
+                ..total_shards as u32)
+                .filter_map(|pos| {
+                    let shard_loc = shard_map.get(&pos)?;
+                    let dist = node_topo_map
+                        .get(&shard_loc.node_id)
+                        .map_or(objectio_placement::TopologyDistance::Unknown, |fd| {
+                            objectio_placement::distance(me, fd)
+                        });
+                    Some((pos, dist))
+                })
+                .collect();
+            ranked_positions.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
 
-            match read_shard_from_osd(
-                &state.osd_pool,
-                &node_placement,
-                ec_shard_object_id,
-                stripe.stripe_id,
-                pos,
-            )
-            .await
-            {
-                Ok(data) => {
-                    let bytes = data.len();
-                    debug!("Read shard {} ({} bytes, {})", pos, bytes, dist.as_str());
-                    // Record per-locality read traffic so operators can see
-                    // how much cross-zone/cross-dc bandwidth a typical
-                    // object read consumes (Phase 2.4).
-                    objectio_s3::observe_locality_read_bytes(dist.as_str(), bytes as u64);
-                    shards[pos as usize] = Some(data);
-                    read_count += 1;
+            for (pos, dist) in ranked_positions {
+                if read_count >= ec_k {
+                    break;
                 }
-                Err(e) => {
-                    warn!("Failed to read shard {}: {}", pos, e);
+                let Some(shard_loc) = shard_map.get(&pos) else {
+                    continue;
+                };
+                let node_addr = resolve_node_address(
+                    &mut node_address_map,
+                    &mut meta_client,
+                    &shard_loc.node_id,
+                )
+                .await;
+                let node_placement = objectio_proto::metadata::NodePlacement {
+                    position: shard_loc.position,
+                    node_id: shard_loc.node_id.clone(),
+                    node_address: node_addr,
+                    disk_id: shard_loc.disk_id.clone(),
+                    shard_type: shard_loc.shard_type,
+                    local_group: shard_loc.local_group,
+                };
+
+                match read_shard_from_osd(
+                    &state.osd_pool,
+                    &node_placement,
+                    shard_object_id,
+                    stripe.stripe_id,
+                    pos,
+                )
+                .await
+                {
+                    Ok(data) => {
+                        let bytes = data.len();
+                        debug!("Read shard {} ({} bytes, {})", pos, bytes, dist.as_str());
+                        // Record per-locality read traffic so operators can see
+                        // how much cross-zone/cross-dc bandwidth a typical
+                        // object read consumes (Phase 2.4).
+                        objectio_s3::observe_locality_read_bytes(dist.as_str(), bytes as u64);
+                        shards[pos as usize] = Some(data);
+                        read_count += 1;
+                    }
+                    Err(e) => {
+                        warn!("Failed to read shard {}: {}", pos, e);
+                    }
                 }
             }
-        }
-
-        // Check if we have enough shards
-        if read_count < ec_k {
-            error!(
-                "Insufficient shards to reconstruct stripe {}: have {}, need {}",
-                stripe_idx, read_count, ec_k
-            );
-            return S3Error::xml_response(
-                "InternalError",
-                &format!(
-                    "Cannot read object: only {} shards available for stripe {}, need {}",
-                    read_count, stripe_idx, ec_k
-                ),
-                StatusCode::INTERNAL_SERVER_ERROR,
-            );
-        }
 
-        // Decode using erasure coding. Match the codec to how this stripe
-        // was encoded — reading an LRC-written stripe with a plain MDS codec
-        // produces wrong bytes (the decoder treats local parity shards as
-        // global parity and reconstruction diverges). LRC config pulls the
-        // (k, l, g) triple straight off the StripeMeta.
-        let codec_config = if stripe_ec_type == ErasureType::ErasureLrc {
-            ErasureConfig::lrc(
-                ec_k as u8,
-                stripe.ec_local_parity as u8,
-                stripe.ec_global_parity as u8,
-            )
-        } else {
-            ErasureConfig::new(ec_k as u8, ec_m as u8)
-        };
-        let codec = match ErasureCodec::new(codec_config) {
-            Ok(c) => c,
-            Err(e) => {
-                error!("Failed to create erasure codec: {}", e);
+            // Check if we have enough shards
+            if read_count < ec_k {
+                error!(
+                    "Insufficient shards to reconstruct stripe {}: have {}, need {}",
+                    stripe_idx, read_count, ec_k
+                );
                 return S3Error::xml_response(
                     "InternalError",
-                    &format!("Erasure coding error: {}", e),
+                    &format!(
+                        "Cannot read object: only {} shards available for stripe {}, need {}",
+                        read_count, stripe_idx, ec_k
+                    ),
                     StatusCode::INTERNAL_SERVER_ERROR,
                 );
             }
-        };
 
-        let stripe_data = match codec.decode(&mut shards, stripe_data_size) {
-            Ok(d) => d,
-            Err(e) => {
-                error!("Failed to decode stripe {}: {}", stripe_idx, e);
-                return S3Error::xml_response(
-                    "InternalError",
-                    &format!("Erasure decoding failed for stripe {}: {}", stripe_idx, e),
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                );
+            // Decode using erasure coding. Match the codec to how this stripe
+            // was encoded — reading an LRC-written stripe with a plain MDS codec
+            // produces wrong bytes (the decoder treats local parity shards as
+            // global parity and reconstruction diverges). LRC config pulls the
+            // (k, l, g) triple straight off the StripeMeta.
+            let codec_config = if stripe_ec_type == ErasureType::ErasureLrc {
+                ErasureConfig::lrc(
+                    ec_k as u8,
+                    stripe.ec_local_parity as u8,
+                    stripe.ec_global_parity as u8,
+                )
+            } else {
+                ErasureConfig::new(ec_k as u8, ec_m as u8)
+            };
+            let codec = match ErasureCodec::new(codec_config) {
+                Ok(c) => c,
+                Err(e) => {
+                    error!("Failed to create erasure codec: {}", e);
+                    return S3Error::xml_response(
+                        "InternalError",
+                        &format!("Erasure coding error: {}", e),
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    );
+                }
+            };
+
+            match codec.decode(&mut shards, stripe_data_size) {
+                Ok(d) => {
+                    state.stripe_cache.insert(cache_key, &d);
+                    d
+                }
+                Err(e) => {
+                    error!("Failed to decode stripe {}: {}", stripe_idx, e);
+                    return S3Error::xml_response(
+                        "InternalError",
+                        &format!("Erasure decoding failed for stripe {}: {}", stripe_idx, e),
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    );
+                }
             }
         };
 
@@ -3637,6 +4090,7 @@ pub async fn get_object(
         }
 
         let builder = add_metadata_headers(builder, &object.user_metadata);
+        let builder = add_object_lock_headers(builder, &object);
 
         builder.body(Body::from(all_data)).unwrap()
     } else {
@@ -3669,6 +4123,7 @@ pub async fn get_object(
         }
 
         let builder = add_metadata_headers(builder, &object.user_metadata);
+        let builder = add_object_lock_headers(builder, &object);
 
         builder.body(Body::from(all_data)).unwrap()
     }
@@ -3764,6 +4219,7 @@ pub async fn head_object(
     State(state): State<Arc<AppState>>,
     Path((bucket, key)): Path<(String, String)>,
     auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
 ) -> Response {
     // If key is empty (trailing slash on bucket), treat as head_bucket
     if key.is_empty() {
@@ -3788,6 +4244,8 @@ pub async fn head_object(
         }
     }
 
+    state.access_tracker.record(&bucket, &key);
+
     let mut meta_client = state.meta_client.clone();
 
     // Get placement to find primary OSD
@@ -3797,6 +4255,7 @@ pub async fn head_object(
             key: key.clone(),
             size: 0,
             storage_class: "STANDARD".to_string(),
+            pool: String::new(),
         })
         .await
     {
@@ -3816,8 +4275,20 @@ pub async fn head_object(
             .unwrap();
     }
 
-    match get_object_meta_from_any(&state.osd_pool, &placement.nodes, &bucket, &key).await {
+    match get_object_meta_from_any(
+        &state.osd_pool,
+        &meta_lookup_nodes(&placement),
+        &bucket,
+        &key,
+    )
+    .await
+    {
         Ok(Some(obj)) => {
+            let outcome = crate::conditional::evaluate_read(&headers, &obj.etag, obj.modified_at);
+            if let Some(resp) = precondition_response(outcome, &obj, true) {
+                return resp;
+            }
+
             let mut builder = Response::builder()
                 .status(StatusCode::OK)
                 .header(header::CONTENT_TYPE, &obj.content_type)
@@ -3846,17 +4317,37 @@ pub async fn head_object(
                     }
                 }
                 SseAlgorithm::SseC => {
-                    // Advertise the customer-algorithm marker so clients know
-                    // this object needs a customer-key on GET. We don't know
-                    // the key's md5 server-side; clients already do.
-                    builder =
-                        builder.header("x-amz-server-side-encryption-customer-algorithm", "AES256");
+                    // HEAD needs the customer key just like GET — AWS won't
+                    // reveal SSE-C object metadata to a caller without it.
+                    let cust = match parse_sse_c_headers(&headers) {
+                        Ok(Some(c)) => c,
+                        Ok(None) => {
+                            return Response::builder()
+                                .status(StatusCode::BAD_REQUEST)
+                                .body(Body::empty())
+                                .unwrap();
+                        }
+                        Err(resp) => return resp,
+                    };
+                    if check_sse_c_key(&obj, &cust).is_err() {
+                        return Response::builder()
+                            .status(StatusCode::FORBIDDEN)
+                            .body(Body::empty())
+                            .unwrap();
+                    }
+                    builder = builder
+                        .header("x-amz-server-side-encryption-customer-algorithm", "AES256")
+                        .header(
+                            "x-amz-server-side-encryption-customer-key-md5",
+                            &cust.md5_b64,
+                        );
                 }
                 SseAlgorithm::SseNone => {}
             }
 
             // Add user metadata headers
             let builder = add_metadata_headers(builder, &obj.user_metadata);
+            let builder = add_object_lock_headers(builder, &obj);
 
             builder.body(Body::empty()).unwrap()
         }
@@ -3906,6 +4397,7 @@ pub async fn delete_object(
             key: key.clone(),
             size: 0,
             storage_class: "STANDARD".to_string(),
+            pool: String::new(),
         })
         .await
     {
@@ -3925,49 +4417,6 @@ pub async fn delete_object(
             .unwrap();
     }
 
-    // Lock enforcement: check retention and legal hold before deleting
-    if let Ok(Some(meta)) =
-        get_object_meta_from_any(&state.osd_pool, &placement.nodes, &bucket, &key).await
-    {
-        // Check legal hold
-        if meta.legal_hold.as_ref().is_some_and(|lh| lh.status) {
-            return S3Error::xml_response(
-                "AccessDenied",
-                "Object is under legal hold and cannot be deleted",
-                StatusCode::FORBIDDEN,
-            );
-        }
-
-        // Check retention
-        if let Some(retention) = &meta.retention {
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
-            if retention.retain_until_date > now {
-                let bypass = headers
-                    .get("x-amz-bypass-governance-retention")
-                    .and_then(|v| v.to_str().ok())
-                    .is_some_and(|v| v.eq_ignore_ascii_case("true"));
-
-                if retention.mode() == RetentionMode::RetentionCompliance {
-                    return S3Error::xml_response(
-                        "AccessDenied",
-                        "Object is under compliance retention and cannot be deleted",
-                        StatusCode::FORBIDDEN,
-                    );
-                }
-                if retention.mode() == RetentionMode::RetentionGovernance && !bypass {
-                    return S3Error::xml_response(
-                        "AccessDenied",
-                        "Object is under governance retention. Use x-amz-bypass-governance-retention header to override",
-                        StatusCode::FORBIDDEN,
-                    );
-                }
-            }
-        }
-    }
-
     // Check versioning state
     let versioning_enabled = match meta_client
         .get_bucket_versioning(GetBucketVersioningRequest {
@@ -3979,6 +4428,27 @@ pub async fn delete_object(
         Err(_) => false,
     };
 
+    // Lock enforcement: a delete marker leaves every version in place, so
+    // only deletes that remove data are checked against retention/legal hold
+    if !(versioning_enabled && version_id.is_none())
+        && let Ok(Some(meta)) = get_object_version_meta_from_any(
+            &state.osd_pool,
+            &meta_lookup_nodes(&placement),
+            &bucket,
+            &key,
+            version_id.as_deref().unwrap_or(""),
+        )
+        .await
+        && let Err(e) = crate::object_lock::check_removal(
+            meta.retention.as_ref(),
+            meta.legal_hold.as_ref(),
+            crate::object_lock::bypass_governance(&headers),
+            now_secs(),
+        )
+    {
+        return lock_error_response(&e);
+    }
+
     if versioning_enabled && version_id.is_none() {
         // Versioned delete without version_id: create a delete marker
         let marker_version_id = Uuid::new_v4().to_string();
@@ -4039,8 +4509,14 @@ pub async fn delete_object(
 
     // Non-versioned delete, or versioned delete with specific version_id
     let vid = version_id.as_deref().unwrap_or("");
-    if let Err(e) =
-        delete_object_meta_from_all(&state.osd_pool, &placement.nodes, &bucket, &key, vid).await
+    if let Err(e) = delete_object_meta_from_all(
+        &state.osd_pool,
+        &meta_lookup_nodes(&placement),
+        &bucket,
+        &key,
+        vid,
+    )
+    .await
     {
         warn!("Failed to delete object metadata from OSD: {}", e);
     }
@@ -4082,6 +4558,7 @@ pub async fn delete_objects(
     State(state): State<Arc<AppState>>,
     Path(bucket): Path<String>,
     auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
     body: Bytes,
 ) -> Response {
     debug!("DELETE objects: {} (batch)", bucket);
@@ -4152,6 +4629,7 @@ pub async fn delete_objects(
                 key: obj.key.clone(),
                 size: 0,
                 storage_class: "STANDARD".to_string(),
+                pool: String::new(),
             })
             .await
         {
@@ -4179,9 +4657,37 @@ pub async fn delete_objects(
             continue;
         }
 
-        if let Err(e) =
-            delete_object_meta_from_all(&state.osd_pool, &placement.nodes, &bucket, &obj.key, "")
-                .await
+        if let Ok(Some(meta)) = get_object_version_meta_from_any(
+            &state.osd_pool,
+            &meta_lookup_nodes(&placement),
+            &bucket,
+            &obj.key,
+            obj.version_id.as_deref().unwrap_or(""),
+        )
+        .await
+            && let Err(e) = crate::object_lock::check_removal(
+                meta.retention.as_ref(),
+                meta.legal_hold.as_ref(),
+                crate::object_lock::bypass_governance(&headers),
+                now_secs(),
+            )
+        {
+            errors.push(DeleteError {
+                key: obj.key,
+                code: e.code.to_string(),
+                message: e.message,
+            });
+            continue;
+        }
+
+        if let Err(e) = delete_object_meta_from_all(
+            &state.osd_pool,
+            &meta_lookup_nodes(&placement),
+            &bucket,
+            &obj.key,
+            "",
+        )
+        .await
         {
             warn!(
                 "Failed to delete object {}/{} from OSDs: {}",
@@ -4379,6 +4885,7 @@ async fn delete_bucket_policy_internal(state: Arc<AppState>, bucket: String) ->
 /// POST /{bucket}/{key}?uploads - Initiate multipart upload
 /// POST /{bucket}/{key}?uploadId=X - Complete multipart upload
 /// POST /{bucket}/{key}?grep - Gateway-side regex grep; streams NDJSON
+/// POST /{bucket}/{key}?select&select-type=2 - S3 Select; streams events
 pub async fn post_object(
     State(state): State<Arc<AppState>>,
     Path((bucket, key)): Path<(String, String)>,
@@ -4395,10 +4902,12 @@ pub async fn post_object(
         complete_multipart_upload_internal(state, bucket, key, upload_id, body).await
     } else if params.grep.is_some() {
         grep_object_internal(state, bucket, key, auth, headers, body).await
+    } else if params.select.is_some() {
+        select_object_internal(state, bucket, key, auth, headers, params.select_type, body).await
     } else {
         S3Error::xml_response(
             "InvalidRequest",
-            "POST request must include ?uploads, ?uploadId, or ?grep parameter",
+            "POST request must include ?uploads, ?uploadId, ?grep, or ?select parameter",
             StatusCode::BAD_REQUEST,
         )
     }
@@ -4471,6 +4980,81 @@ async fn grep_object_internal(
     grep::respond(req, reader, size, etag)
 }
 
+/// `POST /{bucket}/{key}?select&select-type=2` — S3 Select. HEADs the
+/// object through the normal pipeline (policy, SSE-C, NotFound), then
+/// hands `select.rs` a fetcher that issues ranged GetObject calls, so
+/// the scan only reads the stripes it needs. See `select.rs` for the
+/// supported formats and SQL.
+async fn select_object_internal(
+    state: Arc<AppState>,
+    bucket: String,
+    key: String,
+    auth: Option<Extension<AuthResult>>,
+    headers: HeaderMap,
+    select_type: Option<String>,
+    body: Bytes,
+) -> Response {
+    use crate::select;
+    if select_type.as_deref() != Some("2") {
+        return S3Error::xml_response(
+            "InvalidArgument",
+            "select-type must be 2",
+            StatusCode::BAD_REQUEST,
+        );
+    }
+    let req = match select::parse_request(&body) {
+        Ok(r) => r,
+        Err(e) => return S3Error::xml_response(e.code, &e.message, StatusCode::BAD_REQUEST),
+    };
+
+    // Carry auth + SSE-C headers to the reads; drop the POST body's.
+    let mut get_headers = headers;
+    for h in [header::CONTENT_TYPE, header::CONTENT_LENGTH, header::RANGE] {
+        get_headers.remove(h);
+    }
+    let head = head_object(
+        State(state.clone()),
+        Path((bucket.clone(), key.clone())),
+        auth.clone(),
+        get_headers.clone(),
+    )
+    .await;
+    if !head.status().is_success() {
+        return head;
+    }
+    // Pin every ranged read to the version HEAD saw, so an overwrite
+    // mid-scan fails the query instead of mixing two objects
+    if let Some(etag) = head.headers().get("ETag") {
+        get_headers.insert(header::IF_MATCH, etag.clone());
+    }
+    let size: u64 = head
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+
+    let fetch: select::RangeFetch = Arc::new(move |start, end| {
+        let state = state.clone();
+        let path = Path((bucket.clone(), key.clone()));
+        let auth = auth.clone();
+        let mut headers = get_headers.clone();
+        Box::pin(async move {
+            if let Ok(v) = format!("bytes={start}-{end}").parse() {
+                headers.insert(header::RANGE, v);
+            }
+            let resp = get_object(State(state), path, auth, headers).await;
+            if !resp.status().is_success() {
+                return Err(format!("GetObject returned {}", resp.status()));
+            }
+            axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    });
+    select::respond(req, size, fetch)
+}
+
 /// `POST /{bucket}?grep` — prefix-scoped regex grep across many keys.
 /// Lists keys under the requested prefix, runs the same scan as the
 /// single-object path on each, and streams match events tagged by
@@ -4704,7 +5288,7 @@ async fn grep_prefix_internal(
 }
 
 /// Initiate multipart upload - internal implementation
-async fn initiate_multipart_upload_internal(
+pub(crate) async fn initiate_multipart_upload_internal(
     state: Arc<AppState>,
     bucket: String,
     key: String,
@@ -4961,18 +5545,29 @@ pub async fn put_object_with_params(
             .await;
     }
     if params.retention.is_some() {
-        return put_object_retention_internal(state, bucket, key, body).await;
+        return put_object_retention_internal(state, bucket, key, headers, body).await;
     }
     if params.legal_hold.is_some() {
         return put_object_legal_hold_internal(state, bucket, key, body).await;
     }
+    if params.acl.is_some() {
+        return put_object_acl_internal(
+            state,
+            bucket,
+            key,
+            auth.map(|Extension(a)| a),
+            headers,
+            body,
+        )
+        .await;
+    }
 
     // Otherwise, it's a regular PUT object
     put_object(State(state), Path((bucket, key)), auth, headers, body).await
 }
 
 /// Upload part - internal implementation
-async fn upload_part_internal(
+pub(crate) async fn upload_part_internal(
     state: Arc<AppState>,
     bucket: String,
     key: String,
@@ -5123,6 +5718,7 @@ async fn upload_part_internal(
             key: part_key.clone(),
             size: part_size,
             storage_class: "STANDARD".to_string(),
+            pool: String::new(),
         })
         .await
     {
@@ -5518,7 +6114,7 @@ async fn upload_part_internal(
 }
 
 /// Complete multipart upload - internal implementation
-async fn complete_multipart_upload_internal(
+pub(crate) async fn complete_multipart_upload_internal(
     state: Arc<AppState>,
     bucket: String,
     key: String,
@@ -5601,6 +6197,7 @@ async fn complete_multipart_upload_internal(
                         key: key.clone(),
                         size: object.size,
                         storage_class: "STANDARD".to_string(),
+                        pool: String::new(),
                     })
                     .await
                 {
@@ -5723,6 +6320,10 @@ pub async fn get_object_with_params(
             object_lock: None,
             lifecycle: None,
             encryption: None,
+            prefix_delete: None,
+            job_id: None,
+            acl: None,
+            ..Default::default()
         };
         return list_objects(State(state), Path(bucket), Query(list_params), auth).await;
     }
@@ -5736,6 +6337,7 @@ pub async fn get_object_with_params(
             upload_id,
             params.max_parts.unwrap_or(1000),
             params.part_number_marker.unwrap_or(0),
+            params.encoding_type.as_deref(),
         )
         .await;
     }
@@ -5745,6 +6347,10 @@ pub async fn get_object_with_params(
     if params.legal_hold.is_some() {
         return get_object_legal_hold_internal(state, bucket, key).await;
     }
+    if params.acl.is_some() {
+        return get_object_acl_internal(state, bucket, key, auth.map(|Extension(a)| a), headers)
+            .await;
+    }
 
     // Otherwise, it's a regular GET object
     get_object(State(state), Path((bucket, key)), auth, headers).await
@@ -5758,7 +6364,13 @@ async fn list_parts_internal(
     upload_id: String,
     max_parts: u32,
     part_number_marker: u32,
+    encoding_type: Option<&str>,
 ) -> Response {
+    let url_encode = match parse_encoding_type(encoding_type) {
+        Ok(e) => e,
+        Err(resp) => return resp,
+    };
+    let max_parts = max_parts.min(1000);
     let mut client = state.meta_client.clone();
 
     match client
@@ -5776,7 +6388,8 @@ async fn list_parts_internal(
 
             let result = ListPartsResult {
                 bucket: resp.bucket,
-                key: resp.key,
+                key: encode_key(resp.key, url_encode),
+                encoding_type: url_encode.then(|| "url".to_string()),
                 upload_id: resp.upload_id,
                 part_number_marker,
                 next_part_number_marker: if resp.is_truncated {
@@ -5853,7 +6466,7 @@ pub async fn delete_object_with_params(
 }
 
 /// Abort multipart upload - internal implementation
-async fn abort_multipart_upload_internal(
+pub(crate) async fn abort_multipart_upload_internal(
     state: Arc<AppState>,
     bucket: String,
     key: String,
@@ -5891,21 +6504,36 @@ async fn abort_multipart_upload_internal(
 }
 
 /// GET /{bucket}?uploads - List multipart uploads
-#[allow(dead_code)]
-pub async fn list_multipart_uploads(
-    State(state): State<Arc<AppState>>,
-    Path(bucket): Path<String>,
+async fn list_multipart_uploads_internal(
+    state: Arc<AppState>,
+    bucket: String,
+    params: &ListObjectsParams,
 ) -> Response {
+    let url_encode = match parse_encoding_type(params.encoding_type.as_deref()) {
+        Ok(e) => e,
+        Err(resp) => return resp,
+    };
+    let prefix = params.prefix.clone().unwrap_or_default();
+    let delimiter = params.delimiter.clone().filter(|d| !d.is_empty());
+    let key_marker = params.key_marker.clone().unwrap_or_default();
+    let upload_id_marker = params.upload_id_marker.clone().unwrap_or_default();
+    let max_uploads = params.max_uploads.unwrap_or(1000).min(1000);
     let mut client = state.meta_client.clone();
 
     match client
         .list_multipart_uploads(ListMultipartUploadsRequest {
             bucket: bucket.clone(),
-            prefix: String::new(),
-            key_marker: String::new(),
-            upload_id_marker: String::new(),
-            max_uploads: 1000,
-        })
+            prefix: prefix.clone(),
+            key_marker: key_marker.clone(),
+            // The upload-id marker only applies alongside a key marker
+            upload_id_marker: if key_marker.is_empty() {
+                String::new()
+            } else {
+                upload_id_marker.clone()
+            },
+            max_uploads,
+            delimiter: delimiter.clone().unwrap_or_default(),
+        })
         .await
     {
         Ok(response) => {
@@ -5913,30 +6541,34 @@ pub async fn list_multipart_uploads(
 
             let result = ListMultipartUploadsResult {
                 bucket: bucket.clone(),
-                key_marker: String::new(),
-                upload_id_marker: String::new(),
-                next_key_marker: if resp.is_truncated {
-                    Some(resp.next_key_marker)
-                } else {
-                    None
-                },
-                next_upload_id_marker: if resp.is_truncated {
-                    Some(resp.next_upload_id_marker)
-                } else {
-                    None
-                },
-                max_uploads: 1000,
+                prefix: encode_key(prefix, url_encode),
+                delimiter: delimiter.map(|d| encode_key(d, url_encode)),
+                encoding_type: url_encode.then(|| "url".to_string()),
+                key_marker: encode_key(key_marker, url_encode),
+                upload_id_marker,
+                next_key_marker: resp
+                    .is_truncated
+                    .then(|| encode_key(resp.next_key_marker, url_encode)),
+                next_upload_id_marker: resp.is_truncated.then_some(resp.next_upload_id_marker),
+                max_uploads,
                 is_truncated: resp.is_truncated,
                 uploads: resp
                     .uploads
                     .into_iter()
                     .map(|u| UploadItem {
-                        key: u.key,
+                        key: encode_key(u.key, url_encode),
                         upload_id: u.upload_id,
                         initiated: timestamp_to_iso(u.initiated),
                         storage_class: u.storage_class,
                     })
                     .collect(),
+                common_prefixes: resp
+                    .common_prefixes
+                    .into_iter()
+                    .map(|p| CommonPrefix {
+                        prefix: encode_key(p, url_encode),
+                    })
+                    .collect(),
             };
 
             let xml = format!(
@@ -6130,6 +6762,82 @@ struct ObjectLockRuleResponseXml {
     default_retention: DefaultRetentionXml,
 }
 
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn lock_error_response(e: &crate::object_lock::LockError) -> Response {
+    let status = if e.code == "AccessDenied" {
+        StatusCode::FORBIDDEN
+    } else {
+        StatusCode::BAD_REQUEST
+    };
+    S3Error::xml_response(e.code, &e.message, status)
+}
+
+/// Bucket's Object Lock configuration, if one was ever set.
+async fn fetch_object_lock_config(
+    state: &AppState,
+    bucket: &str,
+) -> Result<Option<ProtoObjectLockConfig>, Response> {
+    let mut client = state.meta_client.clone();
+    match client
+        .get_object_lock_configuration(GetObjectLockConfigRequest {
+            bucket: bucket.to_string(),
+        })
+        .await
+    {
+        Ok(resp) => {
+            let inner = resp.into_inner();
+            Ok(if inner.found { inner.config } else { None })
+        }
+        Err(e) if e.code() == tonic::Code::NotFound => Ok(None),
+        Err(e) => {
+            error!("Failed to get object lock config for {}: {}", bucket, e);
+            Err(S3Error::xml_response(
+                "InternalError",
+                &e.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))
+        }
+    }
+}
+
+/// Reject per-object retention/legal hold on buckets without Object Lock.
+async fn require_object_lock_enabled(state: &AppState, bucket: &str) -> Result<(), Response> {
+    if fetch_object_lock_config(state, bucket)
+        .await?
+        .is_some_and(|c| c.enabled)
+    {
+        Ok(())
+    } else {
+        Err(S3Error::xml_response(
+            "InvalidRequest",
+            "Bucket is missing Object Lock Configuration",
+            StatusCode::BAD_REQUEST,
+        ))
+    }
+}
+
+/// Retention and legal hold for an object being written, from its lock
+/// headers or the bucket's default retention.
+///
+/// Object Lock buckets are always versioned (enabling lock requires it and
+/// versioning can't then be suspended), so a write never replaces a
+/// locked version in place.
+async fn object_lock_for_put(
+    state: &AppState,
+    bucket: &str,
+    headers: &HeaderMap,
+) -> Result<(Option<ObjectRetention>, Option<LegalHold>), Response> {
+    let config = fetch_object_lock_config(state, bucket).await?;
+    crate::object_lock::for_put(headers, config.as_ref(), now_secs())
+        .map_err(|e| lock_error_response(&e))
+}
+
 async fn put_object_lock_config_internal(
     state: Arc<AppState>,
     bucket: String,
@@ -6146,6 +6854,47 @@ async fn put_object_lock_config_internal(
         }
     };
 
+    // Object Lock can't be turned off once on, and needs versioning so
+    // overwrites keep the locked versions
+    if config.object_lock_enabled.as_deref() != Some("Enabled") {
+        return S3Error::xml_response(
+            "MalformedXML",
+            "ObjectLockEnabled must be Enabled",
+            StatusCode::BAD_REQUEST,
+        );
+    }
+    let mut client = state.meta_client.clone();
+    match client
+        .get_bucket_versioning(GetBucketVersioningRequest {
+            bucket: bucket.clone(),
+        })
+        .await
+    {
+        Ok(resp) if resp.get_ref().state() == VersioningState::VersioningEnabled => {}
+        Ok(_) => {
+            return S3Error::xml_response(
+                "InvalidBucketState",
+                "Versioning must be enabled on the bucket to enable Object Lock",
+                StatusCode::CONFLICT,
+            );
+        }
+        Err(e) if e.code() == tonic::Code::NotFound => {
+            return S3Error::xml_response(
+                "NoSuchBucket",
+                "The specified bucket does not exist",
+                StatusCode::NOT_FOUND,
+            );
+        }
+        Err(e) => {
+            error!("Failed to get versioning for {}: {}", bucket, e);
+            return S3Error::xml_response(
+                "InternalError",
+                &e.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            );
+        }
+    }
+
     let default_retention = config.rule.and_then(|r| r.default_retention).map(|dr| {
         let mode = match dr.mode.as_str() {
             "GOVERNANCE" => RetentionMode::RetentionGovernance,
@@ -6158,15 +6907,11 @@ async fn put_object_lock_config_internal(
         }
     });
 
-    let mut client = state.meta_client.clone();
     match client
         .put_object_lock_configuration(PutObjectLockConfigRequest {
             bucket: bucket.clone(),
             config: Some(ProtoObjectLockConfig {
-                enabled: config
-                    .object_lock_enabled
-                    .as_deref()
-                    .is_some_and(|v| v == "Enabled"),
+                enabled: true,
                 default_retention,
             }),
         })
@@ -6760,8 +7505,13 @@ async fn put_object_retention_internal(
     state: Arc<AppState>,
     bucket: String,
     key: String,
+    headers: HeaderMap,
     body: Bytes,
 ) -> Response {
+    if let Err(resp) = require_object_lock_enabled(&state, &bucket).await {
+        return resp;
+    }
+
     let req: RetentionRequest = match quick_xml::de::from_reader(body.as_ref()) {
         Ok(r) => r,
         Err(e) => {
@@ -6786,9 +7536,10 @@ async fn put_object_retention_internal(
     };
 
     // Parse ISO 8601 date to unix timestamp
-    let retain_until = chrono::DateTime::parse_from_rfc3339(&req.retain_until_date)
-        .map(|dt| dt.timestamp() as u64)
-        .unwrap_or(0);
+    let retain_until = match crate::object_lock::parse_retain_until(&req.retain_until_date) {
+        Ok(t) => t,
+        Err(e) => return lock_error_response(&e),
+    };
 
     let nodes = match get_placement_nodes_for_object(&state, &bucket, &key).await {
         Ok(n) => n,
@@ -6812,10 +7563,19 @@ async fn put_object_retention_internal(
         }
     };
 
-    object_meta.retention = Some(ObjectRetention {
+    let retention = ObjectRetention {
         mode: mode.into(),
         retain_until_date: retain_until,
-    });
+    };
+    if let Err(e) = crate::object_lock::check_retention_update(
+        object_meta.retention.as_ref(),
+        &retention,
+        crate::object_lock::bypass_governance(&headers),
+        now_secs(),
+    ) {
+        return lock_error_response(&e);
+    }
+    object_meta.retention = Some(retention);
 
     if let Err(e) =
         put_object_meta_to_all(&state.osd_pool, &nodes, &bucket, &key, object_meta, false).await
@@ -6893,6 +7653,10 @@ async fn put_object_legal_hold_internal(
     key: String,
     body: Bytes,
 ) -> Response {
+    if let Err(resp) = require_object_lock_enabled(&state, &bucket).await {
+        return resp;
+    }
+
     let req: LegalHoldRequest = match quick_xml::de::from_reader(body.as_ref()) {
         Ok(r) => r,
         Err(e) => {
@@ -6989,6 +7753,320 @@ async fn get_object_legal_hold_internal(
     }
 }
 
+// ============================================================================
+// ACLs
+// ============================================================================
+
+/// ACL carried by a PUT ?acl request: the `x-amz-acl` canned ACL when
+/// present, otherwise the `AccessControlPolicy` body.
+#[allow(clippy::result_large_err)]
+fn acl_from_request(
+    headers: &HeaderMap,
+    body: &[u8],
+    owner_id: &str,
+) -> Result<AccessControlPolicy, Response> {
+    let result = match headers.get("x-amz-acl").and_then(|v| v.to_str().ok()) {
+        Some(name) => crate::acl::canned(name, owner_id),
+        None if body.is_empty() => Err("missing ACL body or x-amz-acl header".to_string()),
+        None => crate::acl::from_xml(body, owner_id),
+    };
+    result.map_err(|e| S3Error::xml_response("InvalidArgument", &e, StatusCode::BAD_REQUEST))
+}
+
+/// Canned ACL for a new bucket or object from its `x-amz-acl` header
+/// (`private` when absent).
+#[allow(clippy::result_large_err)]
+fn canned_acl_from_headers(
+    headers: &HeaderMap,
+    owner_id: &str,
+) -> Result<AccessControlPolicy, Response> {
+    let name = headers
+        .get("x-amz-acl")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("private");
+    crate::acl::canned(name, owner_id)
+        .map_err(|e| S3Error::xml_response("InvalidArgument", &e, StatusCode::BAD_REQUEST))
+}
+
+fn acl_xml_response(acl: &AccessControlPolicy) -> Response {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/xml")
+        .body(Body::from(crate::acl::to_xml(acl)))
+        .unwrap()
+}
+
+/// Fetch the bucket's stored ACL (`None` for buckets created before ACL
+/// support).
+async fn fetch_bucket_acl(
+    state: &AppState,
+    bucket: &str,
+) -> Result<Option<AccessControlPolicy>, Response> {
+    let mut client = state.meta_client.clone();
+    match client
+        .get_bucket(GetBucketRequest {
+            name: bucket.to_string(),
+        })
+        .await
+    {
+        Ok(resp) => Ok(resp.into_inner().bucket.and_then(|b| b.acl)),
+        Err(e) if e.code() == tonic::Code::NotFound => Err(S3Error::xml_response(
+            "NoSuchBucket",
+            "The specified bucket does not exist",
+            StatusCode::NOT_FOUND,
+        )),
+        Err(e) => {
+            error!("Failed to get bucket: {}", e);
+            Err(S3Error::xml_response(
+                "InternalError",
+                &e.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ))
+        }
+    }
+}
+
+/// Authorize an ACL read/write against policy and the current ACL.
+async fn check_acl_access(
+    state: &AppState,
+    bucket: &str,
+    key: Option<&str>,
+    auth: Option<&AuthResult>,
+    action: &str,
+    headers: Option<&HeaderMap>,
+) -> Option<Response> {
+    let auth_result = auth?;
+    check_bucket_policy(
+        state,
+        bucket,
+        &auth_result.user_arn,
+        action,
+        &build_s3_arn(bucket, key),
+        headers,
+        auth_result.auth_mode,
+    )
+    .await
+}
+
+/// PUT /{bucket}?acl
+///
+/// The owner is kept from the existing ACL. A bucket created before ACL
+/// support has none, so the first caller to set one becomes its owner.
+async fn put_bucket_acl_internal(
+    state: Arc<AppState>,
+    bucket: String,
+    auth: Option<AuthResult>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if let Some(deny) = check_acl_access(
+        &state,
+        &bucket,
+        None,
+        auth.as_ref(),
+        "s3:PutBucketAcl",
+        Some(&headers),
+    )
+    .await
+    {
+        return deny;
+    }
+    let current = match fetch_bucket_acl(&state, &bucket).await {
+        Ok(acl) => acl,
+        Err(resp) => return resp,
+    };
+    let owner_id = current.map_or_else(
+        || auth.map(|a| a.user_arn).unwrap_or_default(),
+        |acl| acl.owner_id,
+    );
+    let acl = match acl_from_request(&headers, &body, &owner_id) {
+        Ok(acl) => acl,
+        Err(resp) => return resp,
+    };
+
+    let mut client = state.meta_client.clone();
+    match client
+        .put_bucket_acl(PutBucketAclRequest {
+            bucket: bucket.clone(),
+            acl: Some(acl),
+        })
+        .await
+    {
+        Ok(_) => {
+            info!("Updated ACL for bucket {}", bucket);
+            Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::empty())
+                .unwrap()
+        }
+        Err(e) if e.code() == tonic::Code::NotFound => S3Error::xml_response(
+            "NoSuchBucket",
+            "The specified bucket does not exist",
+            StatusCode::NOT_FOUND,
+        ),
+        Err(e) => {
+            error!("Failed to put bucket ACL: {}", e);
+            S3Error::xml_response(
+                "InternalError",
+                &e.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        }
+    }
+}
+
+/// GET /{bucket}?acl
+async fn get_bucket_acl_internal(
+    state: Arc<AppState>,
+    bucket: String,
+    auth: Option<AuthResult>,
+) -> Response {
+    if let Some(deny) = check_acl_access(
+        &state,
+        &bucket,
+        None,
+        auth.as_ref(),
+        "s3:GetBucketAcl",
+        None,
+    )
+    .await
+    {
+        return deny;
+    }
+    match fetch_bucket_acl(&state, &bucket).await {
+        Ok(Some(acl)) => acl_xml_response(&acl),
+        Ok(None) => acl_xml_response(&crate::acl::implicit(
+            &auth.map(|a| a.user_arn).unwrap_or_default(),
+        )),
+        Err(resp) => resp,
+    }
+}
+
+/// PUT /{bucket}/{key}?acl
+async fn put_object_acl_internal(
+    state: Arc<AppState>,
+    bucket: String,
+    key: String,
+    auth: Option<AuthResult>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if let Some(deny) = check_acl_access(
+        &state,
+        &bucket,
+        Some(&key),
+        auth.as_ref(),
+        "s3:PutObjectAcl",
+        Some(&headers),
+    )
+    .await
+    {
+        return deny;
+    }
+
+    let nodes = match get_placement_nodes_for_object(&state, &bucket, &key).await {
+        Ok(n) => n,
+        Err(resp) => return resp,
+    };
+
+    let mut object_meta = match get_object_meta_from_any(&state.osd_pool, &nodes, &bucket, &key)
+        .await
+    {
+        Ok(Some(meta)) => meta,
+        Ok(None) => {
+            return S3Error::xml_response("NoSuchKey", "Object not found", StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            error!("Failed to get object metadata: {}", e);
+            return S3Error::xml_response(
+                "InternalError",
+                &e.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            );
+        }
+    };
+
+    // Objects without an ACL are owned by the bucket owner
+    let owner_id = match object_meta.acl.as_ref() {
+        Some(acl) => acl.owner_id.clone(),
+        None => match fetch_bucket_acl(&state, &bucket).await {
+            Ok(Some(acl)) => acl.owner_id,
+            Ok(None) => auth.map(|a| a.user_arn).unwrap_or_default(),
+            Err(resp) => return resp,
+        },
+    };
+    object_meta.acl = match acl_from_request(&headers, &body, &owner_id) {
+        Ok(acl) => Some(acl),
+        Err(resp) => return resp,
+    };
+
+    if let Err(e) =
+        put_object_meta_to_all(&state.osd_pool, &nodes, &bucket, &key, object_meta, false).await
+    {
+        error!("Failed to update object ACL: {}", e);
+        return S3Error::xml_response(
+            "InternalError",
+            &e.to_string(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+        );
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// GET /{bucket}/{key}?acl
+async fn get_object_acl_internal(
+    state: Arc<AppState>,
+    bucket: String,
+    key: String,
+    auth: Option<AuthResult>,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(deny) = check_acl_access(
+        &state,
+        &bucket,
+        Some(&key),
+        auth.as_ref(),
+        "s3:GetObjectAcl",
+        Some(&headers),
+    )
+    .await
+    {
+        return deny;
+    }
+
+    let nodes = match get_placement_nodes_for_object(&state, &bucket, &key).await {
+        Ok(n) => n,
+        Err(resp) => return resp,
+    };
+
+    match get_object_meta_from_any(&state.osd_pool, &nodes, &bucket, &key).await {
+        Ok(Some(ObjectMeta { acl: Some(acl), .. })) => acl_xml_response(&acl),
+        Ok(Some(_)) => match fetch_bucket_acl(&state, &bucket).await {
+            Ok(bucket_acl) => {
+                let owner_id = bucket_acl.map_or_else(
+                    || auth.map(|a| a.user_arn).unwrap_or_default(),
+                    |acl| acl.owner_id,
+                );
+                acl_xml_response(&crate::acl::implicit(&owner_id))
+            }
+            Err(resp) => resp,
+        },
+        Ok(None) => S3Error::xml_response("NoSuchKey", "Object not found", StatusCode::NOT_FOUND),
+        Err(e) => {
+            error!("Failed to get object metadata: {}", e);
+            S3Error::xml_response(
+                "InternalError",
+                &e.to_string(),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+        }
+    }
+}
+
 // ============================================================================
 // List Object Versions
 // ============================================================================
@@ -7000,6 +8078,9 @@ struct ListVersionsResult {
     name: String,
     #[serde(rename = "Prefix")]
     prefix: String,
+    #[serde(rename = "EncodingType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding_type: Option<String>,
     #[serde(rename = "MaxKeys")]
     max_keys: u32,
     #[serde(rename = "IsTruncated")]
@@ -7047,6 +8128,7 @@ async fn list_object_versions_internal(
     bucket: String,
     prefix: String,
     max_keys: u32,
+    url_encode: bool,
 ) -> Response {
     // Use scatter-gather to list versions from all OSDs
     use objectio_proto::storage::ListObjectVersionsMetaRequest;
@@ -7156,9 +8238,19 @@ async fn list_object_versions_internal(
         all_versions.truncate(max_keys as usize);
     }
 
+    if url_encode {
+        for v in &mut all_versions {
+            v.key = encode_key(std::mem::take(&mut v.key), true);
+        }
+        for m in &mut all_delete_markers {
+            m.key = encode_key(std::mem::take(&mut m.key), true);
+        }
+    }
+
     let result = ListVersionsResult {
         name: bucket,
-        prefix,
+        prefix: encode_key(prefix, url_encode),
+        encoding_type: url_encode.then(|| "url".to_string()),
         max_keys,
         is_truncated,
         versions: all_versions,
@@ -7190,6 +8282,7 @@ async fn get_placement_nodes_for_object(
             bucket: bucket.to_string(),
             size: 0,
             storage_class: String::new(),
+            pool: String::new(),
         })
         .await
     {
@@ -7202,7 +8295,7 @@ async fn get_placement_nodes_for_object(
                     StatusCode::INTERNAL_SERVER_ERROR,
                 ))
             } else {
-                Ok(placement.nodes)
+                Ok(meta_lookup_nodes(&placement))
             }
         }
         Err(e) => {
@@ -0,0 +1,1352 @@
+//! S3 Select (`SelectObjectContent`) for CSV, JSON and Parquet objects.
+//!
+//! Analytics engines push a filter down to the storage layer and get
+//! back only the matching records, instead of downloading the object.
+//!
+//! ## Wire
+//!
+//! ```text
+//! POST /{bucket}/{key}?select&select-type=2
+//!
+//! <SelectObjectContentRequest>
+//!   <Expression>SELECT s.name FROM S3Object s WHERE s.age > 30</Expression>
+//!   <ExpressionType>SQL</ExpressionType>
+//!   <InputSerialization>
+//!     <CompressionType>NONE|GZIP</CompressionType>
+//!     <CSV><FileHeaderInfo>USE</FileHeaderInfo></CSV>  | <JSON><Type>LINES</Type></JSON> | <Parquet/>
+//!   </InputSerialization>
+//!   <OutputSerialization><CSV/> | <JSON/></OutputSerialization>
+//!   <RequestProgress><Enabled>true</Enabled></RequestProgress>
+//!   <ScanRange><Start>0</Start><End>1048576</End></ScanRange>
+//! </SelectObjectContentRequest>
+//! ```
+//!
+//! The response is an AWS event stream: binary frames carrying
+//! `Records` (output rows), optional `Progress`, then `Stats` and
+//! `End`. Errors hit after the 200 has gone out arrive as an error
+//! frame, which is how the SDKs expect them.
+//!
+//! ## Implementation
+//!
+//! * The object is read through the regular GetObject path in
+//!   `CHUNK_SIZE` ranged reads, so only the stripes overlapping each
+//!   range are fetched and decoded, and policy / SSE-C apply as usual.
+//! * CSV and JSON are parsed incrementally; a record may span chunk
+//!   boundaries but may not exceed `MAX_RECORD_SIZE`. GZIP input is
+//!   inflated in small slices so a compression bomb can't balloon
+//!   memory.
+//! * `ScanRange` (uncompressed CSV / JSON Lines) processes records that
+//!   *start* inside the range, reading past the end only to finish the
+//!   last one.
+//! * Parquet reads the footer, then fetches only the column chunks the
+//!   query references, one row group at a time.
+//! * `LIMIT` stops reading as soon as enough rows have been produced.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::http::{StatusCode, header};
+use axum::response::Response;
+use bytes::{Buf, Bytes};
+use futures::future::BoxFuture;
+use parquet::file::metadata::ParquetMetaDataReader;
+use parquet::file::reader::{ChunkReader, FileReader, Length};
+use parquet::file::serialized_reader::{ReadOptionsBuilder, SerializedFileReader};
+use parquet::schema::types::Type as ParquetType;
+use serde::Deserialize;
+use serde_json::Value as Json;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::select_engine::{Query, Record, Row, SelectError, expand_from};
+
+/// Bytes fetched per ranged read of the source object.
+pub const CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Largest single CSV / JSON record, as in S3.
+pub const MAX_RECORD_SIZE: usize = 1024 * 1024;
+
+/// Output is batched into `Records` frames of roughly this size.
+const RECORDS_FRAME_SIZE: usize = 256 * 1024;
+
+/// Compressed bytes inflated at a time for GZIP input.
+const GZIP_SLICE: usize = 16 * 1024;
+
+/// Fetches an inclusive byte range of the source object.
+pub type RangeFetch =
+    Arc<dyn Fn(u64, u64) -> BoxFuture<'static, Result<Bytes, String>> + Send + Sync>;
+
+// ---------------------------------------------------------------------------
+// Request
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SelectRequestXml {
+    expression: String,
+    expression_type: String,
+    request_progress: Option<RequestProgressXml>,
+    input_serialization: InputXml,
+    output_serialization: OutputXml,
+    scan_range: Option<ScanRangeXml>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RequestProgressXml {
+    enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InputXml {
+    compression_type: Option<String>,
+    #[serde(rename = "CSV")]
+    csv: Option<CsvXml>,
+    #[serde(rename = "JSON")]
+    json: Option<JsonXml>,
+    parquet: Option<ParquetXml>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct OutputXml {
+    #[serde(rename = "CSV")]
+    csv: Option<CsvXml>,
+    #[serde(rename = "JSON")]
+    json: Option<JsonXml>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CsvXml {
+    file_header_info: Option<String>,
+    comments: Option<String>,
+    quote_escape_character: Option<String>,
+    record_delimiter: Option<String>,
+    field_delimiter: Option<String>,
+    quote_character: Option<String>,
+    quote_fields: Option<String>,
+    allow_quoted_record_delimiter: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct JsonXml {
+    #[serde(rename = "Type")]
+    kind: Option<String>,
+    record_delimiter: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParquetXml {}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ScanRangeXml {
+    start: Option<u64>,
+    end: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeaderInfo {
+    None,
+    Ignore,
+    Use,
+}
+
+#[derive(Debug, Clone)]
+struct CsvInput {
+    header: HeaderInfo,
+    comment: Option<u8>,
+    quote: u8,
+    escape: u8,
+    record_delimiter: Vec<u8>,
+    field_delimiter: u8,
+    allow_quoted_record_delimiter: bool,
+}
+
+#[derive(Debug, Clone)]
+enum InputFormat {
+    Csv(CsvInput),
+    JsonDocument,
+    JsonLines,
+    Parquet,
+}
+
+#[derive(Debug, Clone)]
+struct CsvOutput {
+    always_quote: bool,
+    quote: String,
+    escape: String,
+    record_delimiter: String,
+    field_delimiter: String,
+}
+
+#[derive(Debug, Clone)]
+enum OutputFormat {
+    Csv(CsvOutput),
+    Json { record_delimiter: String },
+}
+
+/// A validated SelectObjectContent request.
+#[derive(Debug)]
+pub struct SelectRequest {
+    query: Query,
+    input: InputFormat,
+    gzip: bool,
+    output: OutputFormat,
+    progress: bool,
+    scan_range: Option<(Option<u64>, Option<u64>)>,
+}
+
+/// Elements whose value may legitimately be whitespace.
+const DELIMITER_TAGS: [&str; 5] = [
+    "RecordDelimiter",
+    "FieldDelimiter",
+    "QuoteCharacter",
+    "QuoteEscapeCharacter",
+    "Comments",
+];
+
+/// The XML deserializer trims whitespace-only text, which would turn a
+/// `\n` or `\t` delimiter into an empty string. Character references
+/// survive trimming, so rewrite literal whitespace in the delimiter
+/// elements as references before parsing.
+fn protect_whitespace(xml: &str) -> String {
+    let mut out = String::with_capacity(xml.len());
+    let mut rest = xml;
+    'scan: while let Some(lt) = rest.find('<') {
+        out.push_str(&rest[..lt]);
+        rest = &rest[lt..];
+        for tag in DELIMITER_TAGS {
+            let open = format!("<{tag}>");
+            let close = format!("</{tag}>");
+            if let Some(body) = rest.strip_prefix(open.as_str())
+                && let Some(end) = body.find(close.as_str())
+            {
+                out.push_str(&open);
+                for c in body[..end].chars() {
+                    match c {
+                        ' ' | '\t' | '\r' | '\n' => out.push_str(&format!("&#{};", c as u32)),
+                        c => out.push(c),
+                    }
+                }
+                out.push_str(&close);
+                rest = &body[end + close.len()..];
+                continue 'scan;
+            }
+        }
+        out.push('<');
+        rest = &rest[1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn invalid(message: impl Into<String>) -> SelectError {
+    SelectError::new("InvalidRequest", message)
+}
+
+fn single_byte(value: Option<String>, default: u8, what: &str) -> Result<u8, SelectError> {
+    match value.as_deref() {
+        None | Some("") => Ok(default),
+        Some(s) if s.len() == 1 => Ok(s.as_bytes()[0]),
+        Some(_) => Err(invalid(format!("{what} must be a single ASCII character"))),
+    }
+}
+
+fn non_empty(value: Option<String>, default: &str) -> String {
+    value
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Parse and validate a `SelectObjectContentRequest` body.
+pub fn parse_request(body: &[u8]) -> Result<SelectRequest, SelectError> {
+    let text = std::str::from_utf8(body)
+        .map_err(|_| SelectError::new("MalformedXML", "request body is not UTF-8"))?;
+    let doc: SelectRequestXml =
+        quick_xml::de::from_str(&protect_whitespace(text)).map_err(|e| {
+            SelectError::new(
+                "MalformedXML",
+                format!("invalid SelectObjectContentRequest: {e}"),
+            )
+        })?;
+
+    if !doc.expression_type.eq_ignore_ascii_case("SQL") {
+        return Err(SelectError::new(
+            "InvalidExpressionType",
+            "ExpressionType must be SQL",
+        ));
+    }
+    let query = Query::parse(&doc.expression)?;
+
+    let gzip = match doc
+        .input_serialization
+        .compression_type
+        .as_deref()
+        .unwrap_or("NONE")
+        .to_ascii_uppercase()
+        .as_str()
+    {
+        "NONE" => false,
+        "GZIP" => true,
+        "BZIP2" => {
+            return Err(SelectError::new(
+                "NotImplemented",
+                "BZIP2 input is not supported",
+            ));
+        }
+        other => {
+            return Err(SelectError::new(
+                "InvalidCompressionFormat",
+                format!("unknown CompressionType {other}"),
+            ));
+        }
+    };
+
+    let input = match (
+        doc.input_serialization.csv,
+        doc.input_serialization.json,
+        doc.input_serialization.parquet,
+    ) {
+        (Some(csv), None, None) => {
+            let quote = single_byte(csv.quote_character, b'"', "QuoteCharacter")?;
+            InputFormat::Csv(CsvInput {
+                header: match csv
+                    .file_header_info
+                    .as_deref()
+                    .unwrap_or("NONE")
+                    .to_ascii_uppercase()
+                    .as_str()
+                {
+                    "NONE" => HeaderInfo::None,
+                    "IGNORE" => HeaderInfo::Ignore,
+                    "USE" => HeaderInfo::Use,
+                    other => return Err(invalid(format!("invalid FileHeaderInfo {other}"))),
+                },
+                comment: csv
+                    .comments
+                    .filter(|c| !c.is_empty())
+                    .map(|c| single_byte(Some(c), b'#', "Comments"))
+                    .transpose()?,
+                quote,
+                escape: single_byte(csv.quote_escape_character, quote, "QuoteEscapeCharacter")?,
+                record_delimiter: non_empty(csv.record_delimiter, "\n").into_bytes(),
+                field_delimiter: single_byte(csv.field_delimiter, b',', "FieldDelimiter")?,
+                allow_quoted_record_delimiter: csv.allow_quoted_record_delimiter.unwrap_or(false),
+            })
+        }
+        (None, Some(json), None) => match json
+            .kind
+            .as_deref()
+            .unwrap_or("DOCUMENT")
+            .to_ascii_uppercase()
+            .as_str()
+        {
+            "DOCUMENT" => InputFormat::JsonDocument,
+            "LINES" => InputFormat::JsonLines,
+            other => return Err(invalid(format!("invalid JSON Type {other}"))),
+        },
+        (None, None, Some(_)) if gzip => {
+            return Err(invalid("Parquet input does not take a CompressionType"));
+        }
+        (None, None, Some(_)) => InputFormat::Parquet,
+        _ => {
+            return Err(invalid(
+                "InputSerialization must specify exactly one of CSV, JSON or Parquet",
+            ));
+        }
+    };
+
+    let output = match (doc.output_serialization.csv, doc.output_serialization.json) {
+        (Some(csv), None) => {
+            let quote = non_empty(csv.quote_character, "\"");
+            OutputFormat::Csv(CsvOutput {
+                always_quote: csv
+                    .quote_fields
+                    .is_some_and(|q| q.eq_ignore_ascii_case("ALWAYS")),
+                escape: non_empty(csv.quote_escape_character, &quote),
+                quote,
+                record_delimiter: non_empty(csv.record_delimiter, "\n"),
+                field_delimiter: non_empty(csv.field_delimiter, ","),
+            })
+        }
+        (None, Some(json)) => OutputFormat::Json {
+            record_delimiter: non_empty(json.record_delimiter, "\n"),
+        },
+        _ => {
+            return Err(invalid(
+                "OutputSerialization must specify exactly one of CSV or JSON",
+            ));
+        }
+    };
+
+    let scan_range = doc.scan_range.map(|r| (r.start, r.end));
+    if let Some((start, end)) = scan_range {
+        let scannable = !gzip
+            && match &input {
+                InputFormat::Csv(c) => !c.allow_quoted_record_delimiter,
+                InputFormat::JsonLines => true,
+                _ => false,
+            };
+        if !scannable {
+            return Err(invalid(
+                "ScanRange is only supported for uncompressed CSV and JSON Lines input",
+            ));
+        }
+        if let (Some(s), Some(e)) = (start, end)
+            && s > e
+        {
+            return Err(SelectError::new(
+                "InvalidRange",
+                "ScanRange Start must not be after End",
+            ));
+        }
+    }
+
+    Ok(SelectRequest {
+        query,
+        input,
+        gzip,
+        output,
+        progress: doc
+            .request_progress
+            .and_then(|p| p.enabled)
+            .unwrap_or(false),
+        scan_range,
+    })
+}
+
+/// Inclusive byte range of records to process for an object of `size`
+/// bytes. An `End` alone means "the last `End` bytes", as in S3.
+fn resolve_scan_range(range: Option<(Option<u64>, Option<u64>)>, size: u64) -> (u64, u64) {
+    let last = size.saturating_sub(1);
+    match range {
+        None | Some((None, None)) => (0, last),
+        Some((Some(start), None)) => (start, last),
+        Some((None, Some(tail))) => (size.saturating_sub(tail), last),
+        Some((Some(start), Some(end))) => (start, end.min(last)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Event stream framing
+// ---------------------------------------------------------------------------
+
+/// Encode one event-stream message: prelude (total length, headers
+/// length, prelude CRC32), string headers, payload, message CRC32.
+pub fn encode_message(headers: &[(&str, &str)], payload: &[u8]) -> Bytes {
+    let mut hdr = Vec::new();
+    for (name, value) in headers {
+        hdr.push(name.len() as u8);
+        hdr.extend_from_slice(name.as_bytes());
+        hdr.push(7); // string
+        hdr.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        hdr.extend_from_slice(value.as_bytes());
+    }
+    let total = 12 + hdr.len() + payload.len() + 4;
+    let mut msg = Vec::with_capacity(total);
+    msg.extend_from_slice(&(total as u32).to_be_bytes());
+    msg.extend_from_slice(&(hdr.len() as u32).to_be_bytes());
+    msg.extend_from_slice(&crc32fast::hash(&msg).to_be_bytes());
+    msg.extend_from_slice(&hdr);
+    msg.extend_from_slice(payload);
+    msg.extend_from_slice(&crc32fast::hash(&msg).to_be_bytes());
+    Bytes::from(msg)
+}
+
+fn event(event_type: &str, content_type: Option<&str>, payload: &[u8]) -> Bytes {
+    let mut headers = vec![(":message-type", "event"), (":event-type", event_type)];
+    if let Some(ct) = content_type {
+        headers.push((":content-type", ct));
+    }
+    encode_message(&headers, payload)
+}
+
+fn error_message(err: &SelectError) -> Bytes {
+    encode_message(
+        &[
+            (":message-type", "error"),
+            (":error-code", err.code),
+            (":error-message", &err.message),
+        ],
+        &[],
+    )
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Stats {
+    scanned: u64,
+    processed: u64,
+    returned: u64,
+}
+
+impl Stats {
+    fn xml(self, root: &str) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><{root}><BytesScanned>{}</BytesScanned>\
+             <BytesProcessed>{}</BytesProcessed><BytesReturned>{}</BytesReturned></{root}>",
+            self.scanned, self.processed, self.returned
+        )
+    }
+}
+
+/// Output side of a running select: serializes rows, batches them into
+/// `Records` frames and tracks stats. `closed` flips when the client
+/// hangs up so the scan can stop early.
+struct Sink {
+    tx: tokio::sync::mpsc::Sender<Result<Bytes, std::io::Error>>,
+    output: OutputFormat,
+    progress: bool,
+    buf: Vec<u8>,
+    stats: Stats,
+    closed: bool,
+}
+
+impl Sink {
+    async fn send(&mut self, frame: Bytes) {
+        if !self.closed && self.tx.send(Ok(frame)).await.is_err() {
+            self.closed = true;
+        }
+    }
+
+    async fn row(&mut self, row: &Row) {
+        write_row(&self.output, row, &mut self.buf);
+        if self.buf.len() >= RECORDS_FRAME_SIZE {
+            self.flush().await;
+        }
+    }
+
+    async fn flush(&mut self) {
+        if self.buf.is_empty() {
+            return;
+        }
+        let payload = std::mem::take(&mut self.buf);
+        self.stats.returned += payload.len() as u64;
+        self.send(event("Records", Some("application/octet-stream"), &payload))
+            .await;
+    }
+
+    async fn report_progress(&mut self) {
+        if self.progress {
+            let xml = self.stats.xml("Progress");
+            self.send(event("Progress", Some("text/xml"), xml.as_bytes()))
+                .await;
+        }
+    }
+}
+
+fn write_row(output: &OutputFormat, row: &Row, out: &mut Vec<u8>) {
+    match output {
+        OutputFormat::Json { record_delimiter } => {
+            let obj: serde_json::Map<String, Json> = row
+                .iter()
+                .map(|(name, v)| (name.clone(), v.to_json()))
+                .collect();
+            let _ = serde_json::to_writer(&mut *out, &Json::Object(obj));
+            out.extend_from_slice(record_delimiter.as_bytes());
+        }
+        OutputFormat::Csv(csv) => {
+            for (i, (_, v)) in row.iter().enumerate() {
+                if i > 0 {
+                    out.extend_from_slice(csv.field_delimiter.as_bytes());
+                }
+                let text = v.to_text();
+                let needs_quotes = csv.always_quote
+                    || text.contains(csv.field_delimiter.as_str())
+                    || text.contains(csv.quote.as_str())
+                    || text.contains(csv.record_delimiter.as_str())
+                    || text.contains(['\r', '\n']);
+                if needs_quotes {
+                    out.extend_from_slice(csv.quote.as_bytes());
+                    let escaped =
+                        text.replace(csv.quote.as_str(), &format!("{}{}", csv.escape, csv.quote));
+                    out.extend_from_slice(escaped.as_bytes());
+                    out.extend_from_slice(csv.quote.as_bytes());
+                } else {
+                    out.extend_from_slice(text.as_bytes());
+                }
+            }
+            out.extend_from_slice(csv.record_delimiter.as_bytes());
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CSV / JSON record reader
+// ---------------------------------------------------------------------------
+
+/// Incremental splitter for CSV and JSON input. Bytes go in with
+/// [`RecordReader::feed`]; complete records come out of
+/// [`RecordReader::drain`], tagged with their absolute start offset.
+struct RecordReader {
+    input: InputFormat,
+    buf: Vec<u8>,
+    /// Absolute object offset of `buf[0]`
+    offset: u64,
+    header: Option<Arc<Vec<String>>>,
+    /// First record is still to be consumed as a header (USE / IGNORE)
+    header_pending: bool,
+    /// Discard up to the first record boundary (ScanRange not at 0)
+    skip_partial: bool,
+}
+
+impl RecordReader {
+    fn new(input: InputFormat, offset: u64) -> Self {
+        let header_pending =
+            matches!(&input, InputFormat::Csv(c) if c.header != HeaderInfo::None) && offset == 0;
+        Self {
+            input,
+            buf: Vec::new(),
+            offset,
+            header: None,
+            header_pending,
+            skip_partial: false,
+        }
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Locate the next record in `buf[from..]`: `(content_end,
+    /// next_start)`. `None` means more input is needed.
+    fn split(&self, from: usize, eof: bool) -> Option<(usize, usize)> {
+        let data = &self.buf[from..];
+        let found = match &self.input {
+            InputFormat::Csv(csv) => {
+                let delim = csv.record_delimiter.as_slice();
+                let mut in_quotes = false;
+                let mut i = 0;
+                let mut hit = None;
+                while i < data.len() {
+                    let b = data[i];
+                    if csv.allow_quoted_record_delimiter {
+                        if in_quotes && b == csv.escape && csv.escape != csv.quote {
+                            i += 2;
+                            continue;
+                        }
+                        if b == csv.quote {
+                            in_quotes = !in_quotes;
+                        }
+                    }
+                    if !in_quotes && data[i..].starts_with(delim) {
+                        hit = Some((i, i + delim.len()));
+                        break;
+                    }
+                    i += 1;
+                }
+                hit
+            }
+            _ => data.iter().position(|&b| b == b'\n').map(|i| (i, i + 1)),
+        };
+        match found {
+            Some((end, next)) => Some((from + end, from + next)),
+            None if eof && from < self.buf.len() => Some((self.buf.len(), self.buf.len())),
+            None => None,
+        }
+    }
+
+    /// Pull every complete record out of the buffer. Records starting
+    /// after `last` (the ScanRange end) are not returned; the bool
+    /// result says the range is exhausted.
+    fn drain(
+        &mut self,
+        eof: bool,
+        last: u64,
+        query: &Query,
+        out: &mut Vec<Record>,
+    ) -> Result<bool, SelectError> {
+        let mut pos = 0;
+        if self.skip_partial {
+            let end_byte = match &self.input {
+                InputFormat::Csv(csv) => csv.record_delimiter.last().copied().unwrap_or(b'\n'),
+                _ => b'\n',
+            };
+            match self.buf.iter().position(|&b| b == end_byte) {
+                Some(i) => {
+                    pos = i + 1;
+                    self.skip_partial = false;
+                }
+                None if eof => pos = self.buf.len(),
+                None => return self.compact(0, false),
+            }
+        }
+
+        if matches!(self.input, InputFormat::JsonDocument) {
+            return self.drain_documents(pos, eof, query, out);
+        }
+
+        let mut exhausted = false;
+        while pos < self.buf.len() {
+            if self.offset + pos as u64 > last {
+                exhausted = true;
+                break;
+            }
+            let Some((end, next)) = self.split(pos, eof) else {
+                break;
+            };
+            let line = &self.buf[pos..end];
+            let line = match &self.input {
+                InputFormat::Csv(c) if c.record_delimiter != b"\n" => line,
+                _ => line.strip_suffix(b"\r").unwrap_or(line),
+            };
+            if end - pos > MAX_RECORD_SIZE {
+                return Err(record_too_large());
+            }
+            match &self.input {
+                InputFormat::Csv(csv) => {
+                    if csv.comment.is_some_and(|c| line.first() == Some(&c)) {
+                        pos = next;
+                        continue;
+                    }
+                    let fields = parse_csv_fields(line, csv);
+                    if self.header_pending {
+                        self.header_pending = false;
+                        if csv.header == HeaderInfo::Use {
+                            self.header = Some(Arc::new(fields));
+                        }
+                    } else {
+                        out.push(Record::Csv {
+                            fields,
+                            header: self.header.clone(),
+                        });
+                    }
+                }
+                _ => {
+                    if !line.iter().all(u8::is_ascii_whitespace) {
+                        let v: Json = serde_json::from_slice(line).map_err(|e| {
+                            SelectError::new("InvalidJsonType", format!("invalid JSON record: {e}"))
+                        })?;
+                        out.extend(
+                            expand_from(v, query.from_path())
+                                .into_iter()
+                                .map(Record::Json),
+                        );
+                    }
+                }
+            }
+            pos = next;
+        }
+        self.compact(pos, exhausted)
+    }
+
+    fn drain_documents(
+        &mut self,
+        mut pos: usize,
+        eof: bool,
+        query: &Query,
+        out: &mut Vec<Record>,
+    ) -> Result<bool, SelectError> {
+        let mut stream = serde_json::Deserializer::from_slice(&self.buf[pos..]).into_iter::<Json>();
+        loop {
+            match stream.next() {
+                Some(Ok(v)) => out.extend(
+                    expand_from(v, query.from_path())
+                        .into_iter()
+                        .map(Record::Json),
+                ),
+                Some(Err(e)) if e.is_eof() && !eof => break,
+                Some(Err(e)) => {
+                    return Err(SelectError::new(
+                        "InvalidJsonType",
+                        format!("invalid JSON document: {e}"),
+                    ));
+                }
+                None => break,
+            }
+        }
+        pos += stream.byte_offset();
+        if self.buf.len() - pos > MAX_RECORD_SIZE {
+            return Err(record_too_large());
+        }
+        self.compact(pos, false)
+    }
+
+    fn compact(&mut self, consumed: usize, exhausted: bool) -> Result<bool, SelectError> {
+        self.buf.drain(..consumed);
+        self.offset += consumed as u64;
+        if self.buf.len() > MAX_RECORD_SIZE && !exhausted {
+            return Err(record_too_large());
+        }
+        Ok(exhausted)
+    }
+}
+
+fn record_too_large() -> SelectError {
+    SelectError::new(
+        "OverMaxRecordSize",
+        format!("a record exceeds the maximum size of {MAX_RECORD_SIZE} bytes"),
+    )
+}
+
+fn parse_csv_fields(line: &[u8], csv: &CsvInput) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = Vec::new();
+    let mut in_quotes = false;
+    let mut i = 0;
+    while i < line.len() {
+        let b = line[i];
+        if in_quotes {
+            if b == csv.escape && csv.escape != csv.quote && i + 1 < line.len() {
+                field.push(line[i + 1]);
+                i += 1;
+            } else if b == csv.quote {
+                if line.get(i + 1) == Some(&csv.quote) {
+                    field.push(b);
+                    i += 1;
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(b);
+            }
+        } else if b == csv.quote && field.is_empty() {
+            in_quotes = true;
+        } else if b == csv.field_delimiter {
+            fields.push(String::from_utf8_lossy(&field).into_owned());
+            field.clear();
+        } else {
+            field.push(b);
+        }
+        i += 1;
+    }
+    fields.push(String::from_utf8_lossy(&field).into_owned());
+    fields
+}
+
+// ---------------------------------------------------------------------------
+// Scan drivers
+// ---------------------------------------------------------------------------
+
+async fn fetch_range(fetch: &RangeFetch, start: u64, end: u64) -> Result<Bytes, SelectError> {
+    fetch(start, end)
+        .await
+        .map_err(|e| SelectError::new("InternalError", format!("reading object: {e}")))
+}
+
+/// Read the CSV header row from the start of the object, for a
+/// ScanRange that begins past it.
+async fn read_csv_header(
+    input: &InputFormat,
+    size: u64,
+    fetch: &RangeFetch,
+    query: &Query,
+) -> Result<Option<Arc<Vec<String>>>, SelectError> {
+    let mut reader = RecordReader::new(input.clone(), 0);
+    let mut pos = 0;
+    let mut scratch = Vec::new();
+    while reader.header_pending && pos < size {
+        let end = (pos + 64 * 1024).min(size) - 1;
+        reader.feed(&fetch_range(fetch, pos, end).await?);
+        pos = end + 1;
+        // Stop right after the header: `last = 0` ends the drain at
+        // the first data record.
+        reader.drain(pos >= size, 0, query, &mut scratch)?;
+    }
+    Ok(reader.header)
+}
+
+async fn run_text(
+    req: &SelectRequest,
+    size: u64,
+    fetch: &RangeFetch,
+    sink: &mut Sink,
+) -> Result<(), SelectError> {
+    let (first, last) = resolve_scan_range(req.scan_range, size);
+    if size == 0 || first > last {
+        return finish(req.query.executor(), sink).await;
+    }
+    let mut pos = first.saturating_sub(1);
+    let mut reader = RecordReader::new(req.input.clone(), pos);
+    if first > 0 {
+        reader.skip_partial = true;
+        if matches!(&req.input, InputFormat::Csv(c) if c.header == HeaderInfo::Use) {
+            reader.header = read_csv_header(&req.input, size, fetch, &req.query).await?;
+        }
+    }
+    let mut gz = req
+        .gzip
+        .then(|| flate2::write::MultiGzDecoder::new(Vec::new()));
+    let mut exec = req.query.executor();
+    let mut records = Vec::new();
+
+    'scan: while pos < size {
+        let end = (pos + CHUNK_SIZE).min(size) - 1;
+        let data = fetch_range(fetch, pos, end).await?;
+        sink.stats.scanned += data.len() as u64;
+        pos = end + 1;
+        let eof = pos >= size;
+
+        let slices: Vec<&[u8]> = if gz.is_some() {
+            data.chunks(GZIP_SLICE).collect()
+        } else {
+            vec![&data[..]]
+        };
+        let count = slices.len();
+        for (i, slice) in slices.into_iter().enumerate() {
+            let last_slice = eof && i + 1 == count;
+            match gz.as_mut() {
+                Some(dec) => {
+                    let inflate = |e: std::io::Error| {
+                        SelectError::new("InvalidCompressionFormat", format!("GZIP: {e}"))
+                    };
+                    dec.write_all(slice).map_err(inflate)?;
+                    if last_slice {
+                        dec.try_finish().map_err(inflate)?;
+                    }
+                    let plain = std::mem::take(dec.get_mut());
+                    sink.stats.processed += plain.len() as u64;
+                    reader.feed(&plain);
+                }
+                None => {
+                    sink.stats.processed += slice.len() as u64;
+                    reader.feed(slice);
+                }
+            }
+            let exhausted = reader.drain(last_slice, last, &req.query, &mut records)?;
+            for record in records.drain(..) {
+                if let Some(row) = exec.push(&record)? {
+                    sink.row(&row).await;
+                }
+                if exec.done() || sink.closed {
+                    break 'scan;
+                }
+            }
+            if exhausted {
+                break 'scan;
+            }
+        }
+        sink.report_progress().await;
+    }
+    finish(exec, sink).await
+}
+
+async fn finish(
+    exec: crate::select_engine::Executor<'_>,
+    sink: &mut Sink,
+) -> Result<(), SelectError> {
+    if let Some(row) = exec.finish()? {
+        sink.row(&row).await;
+    }
+    Ok(())
+}
+
+/// [`ChunkReader`] over the handful of byte ranges fetched for one row
+/// group (its column chunks plus the footer).
+struct SparseReader {
+    len: u64,
+    ranges: Vec<(u64, Bytes)>,
+}
+
+impl SparseReader {
+    fn find(&self, start: u64, length: u64) -> parquet::errors::Result<(u64, &Bytes)> {
+        self.ranges
+            .iter()
+            .find(|(off, b)| *off <= start && start + length <= off + b.len() as u64)
+            .map(|(off, b)| (*off, b))
+            .ok_or_else(|| {
+                parquet::errors::ParquetError::General(format!(
+                    "byte range {start}+{length} was not fetched"
+                ))
+            })
+    }
+}
+
+impl Length for SparseReader {
+    fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+impl ChunkReader for SparseReader {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn get_read(&self, start: u64) -> parquet::errors::Result<Self::T> {
+        // At least one byte, so a chunk ending exactly at `start`
+        // doesn't match.
+        let (off, b) = self.find(start, 1)?;
+        Ok(b.slice((start - off) as usize..).reader())
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+        let (off, b) = self.find(start, length as u64)?;
+        let from = (start - off) as usize;
+        Ok(b.slice(from..from + length))
+    }
+}
+
+fn parquet_error(e: impl std::fmt::Display) -> SelectError {
+    SelectError::new(
+        "ParquetParsingError",
+        format!("invalid Parquet object: {e}"),
+    )
+}
+
+async fn run_parquet(
+    req: &SelectRequest,
+    size: u64,
+    fetch: &RangeFetch,
+    sink: &mut Sink,
+) -> Result<(), SelectError> {
+    if size < 12 {
+        return Err(parquet_error("object is too small"));
+    }
+    let tail = fetch_range(fetch, size - 8, size - 1).await?;
+    if tail.len() != 8 || &tail[4..] != b"PAR1" {
+        return Err(parquet_error("missing PAR1 footer"));
+    }
+    let meta_len = u64::from(u32::from_le_bytes([tail[0], tail[1], tail[2], tail[3]]));
+    if meta_len + 12 > size {
+        return Err(parquet_error("footer length exceeds object size"));
+    }
+    let meta_start = size - 8 - meta_len;
+    let meta_bytes = fetch_range(fetch, meta_start, size - 9).await?;
+    sink.stats.scanned += 8 + meta_bytes.len() as u64;
+    let metadata = ParquetMetaDataReader::decode_metadata(&meta_bytes).map_err(parquet_error)?;
+    let mut footer = meta_bytes.to_vec();
+    footer.extend_from_slice(&tail);
+    let footer = Bytes::from(footer);
+
+    // Project to the top-level columns the query touches.
+    let schema = metadata.file_metadata().schema();
+    let wanted: Option<Vec<_>> = req.query.referenced_columns().map(|cols| {
+        schema
+            .get_fields()
+            .iter()
+            .filter(|f| cols.iter().any(|c| c.matches(f.name())))
+            .cloned()
+            .collect()
+    });
+    let projection = match &wanted {
+        Some(fields) if !fields.is_empty() => Some(
+            ParquetType::group_type_builder(schema.name())
+                .with_fields(fields.clone())
+                .build()
+                .map_err(parquet_error)?,
+        ),
+        _ => None,
+    };
+
+    let mut exec = req.query.executor();
+    for (idx, rg) in metadata.row_groups().iter().enumerate() {
+        if exec.done() || sink.closed {
+            break;
+        }
+        // Nothing referenced (e.g. COUNT(*)): the row count is enough.
+        if wanted.as_ref().is_some_and(Vec::is_empty) {
+            let empty = Record::Json(Json::Object(serde_json::Map::new()));
+            for _ in 0..rg.num_rows() {
+                if let Some(row) = exec.push(&empty)? {
+                    sink.row(&row).await;
+                }
+                if exec.done() || sink.closed {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        let mut ranges = vec![(meta_start, footer.clone())];
+        for col in rg.columns() {
+            let root = col.column_path().parts().first().map(String::as_str);
+            let needed = wanted
+                .as_ref()
+                .is_none_or(|w| w.iter().any(|f| Some(f.name()) == root));
+            if !needed {
+                continue;
+            }
+            let (start, len) = col.byte_range();
+            if len == 0 {
+                continue;
+            }
+            let data = fetch_range(fetch, start, start + len - 1).await?;
+            sink.stats.scanned += len;
+            sink.stats.processed += len;
+            ranges.push((start, data));
+        }
+
+        let options = ReadOptionsBuilder::new()
+            .with_predicate(Box::new(move |_, i| i == idx))
+            .build();
+        let reader =
+            SerializedFileReader::new_with_options(SparseReader { len: size, ranges }, options)
+                .map_err(parquet_error)?;
+        let rows = reader
+            .get_row_iter(projection.clone())
+            .map_err(parquet_error)?;
+        // Decode synchronously in batches; the row iterator doesn't
+        // live across an await.
+        let mut batch: Vec<Row> = Vec::new();
+        for row in rows {
+            let record = Record::Json(row.map_err(parquet_error)?.to_json_value());
+            if let Some(out) = exec.push(&record)? {
+                batch.push(out);
+            }
+            if exec.done() {
+                break;
+            }
+        }
+        for row in &batch {
+            sink.row(row).await;
+        }
+        sink.report_progress().await;
+    }
+    finish(exec, sink).await
+}
+
+/// Run the select on a background task and stream the event-stream
+/// response. `fetch` reads byte ranges of the (already authorized)
+/// source object.
+pub fn respond(req: SelectRequest, object_size: u64, fetch: RangeFetch) -> Response {
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<Bytes, std::io::Error>>(16);
+    tokio::spawn(async move {
+        let mut sink = Sink {
+            tx,
+            output: req.output.clone(),
+            progress: req.progress,
+            buf: Vec::new(),
+            stats: Stats::default(),
+            closed: false,
+        };
+        let result = match req.input {
+            InputFormat::Parquet => run_parquet(&req, object_size, &fetch, &mut sink).await,
+            _ => run_text(&req, object_size, &fetch, &mut sink).await,
+        };
+        sink.flush().await;
+        match result {
+            Ok(()) => {
+                let xml = sink.stats.xml("Stats");
+                sink.send(event("Stats", Some("text/xml"), xml.as_bytes()))
+                    .await;
+                sink.send(event("End", None, &[])).await;
+            }
+            Err(e) => sink.send(error_message(&e)).await,
+        }
+    });
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/vnd.amazon.eventstream")
+        .header(header::TRANSFER_ENCODING, "chunked")
+        .body(Body::from_stream(ReceiverStream::new(rx)))
+        .unwrap_or_else(|_| Response::new(Body::from("internal error building response")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(input: &str, output: &str, sql: &str) -> SelectRequest {
+        let xml = format!(
+            "<SelectObjectContentRequest><Expression>{sql}</Expression>\
+             <ExpressionType>SQL</ExpressionType>\
+             <InputSerialization>{input}</InputSerialization>\
+             <OutputSerialization>{output}</OutputSerialization></SelectObjectContentRequest>"
+        );
+        parse_request(xml.as_bytes()).unwrap()
+    }
+
+    fn fetcher(data: Vec<u8>) -> RangeFetch {
+        let data = Bytes::from(data);
+        Arc::new(move |start, end| {
+            let slice = data.slice(start as usize..=end as usize);
+            Box::pin(async move { Ok(slice) })
+        })
+    }
+
+    /// Decode the event stream, checking both CRCs, into
+    /// `(event-type, payload)` pairs.
+    fn decode(mut stream: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let mut out = Vec::new();
+        while !stream.is_empty() {
+            let total = u32::from_be_bytes(stream[0..4].try_into().unwrap()) as usize;
+            let hlen = u32::from_be_bytes(stream[4..8].try_into().unwrap()) as usize;
+            let msg = &stream[..total];
+            assert_eq!(
+                u32::from_be_bytes(msg[8..12].try_into().unwrap()),
+                crc32fast::hash(&msg[..8])
+            );
+            assert_eq!(
+                u32::from_be_bytes(msg[total - 4..].try_into().unwrap()),
+                crc32fast::hash(&msg[..total - 4])
+            );
+            let mut h = &msg[12..12 + hlen];
+            let mut kind = String::new();
+            while !h.is_empty() {
+                let n = h[0] as usize;
+                let name = std::str::from_utf8(&h[1..=n]).unwrap().to_string();
+                let vlen = u16::from_be_bytes([h[n + 2], h[n + 3]]) as usize;
+                let value = std::str::from_utf8(&h[n + 4..n + 4 + vlen]).unwrap();
+                if name == ":event-type" || name == ":error-code" {
+                    kind = value.to_string();
+                }
+                h = &h[n + 4 + vlen..];
+            }
+            out.push((kind, msg[12 + hlen..total - 4].to_vec()));
+            stream = &stream[total..];
+        }
+        out
+    }
+
+    async fn run(req: SelectRequest, data: Vec<u8>) -> (String, Vec<String>) {
+        let size = data.len() as u64;
+        let resp = respond(req, size, fetcher(data));
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let events = decode(&body);
+        let records: Vec<u8> = events
+            .iter()
+            .filter(|(k, _)| k == "Records")
+            .flat_map(|(_, p)| p.clone())
+            .collect();
+        let kinds = events.into_iter().map(|(k, _)| k).collect();
+        (String::from_utf8(records).unwrap(), kinds)
+    }
+
+    #[test]
+    fn test_parse_request_delimiters() {
+        let req = request(
+            "<CSV><FieldDelimiter>\t</FieldDelimiter><RecordDelimiter>\r\n</RecordDelimiter></CSV>",
+            "<JSON/>",
+            "SELECT * FROM S3Object",
+        );
+        let InputFormat::Csv(csv) = req.input else {
+            panic!("expected CSV input");
+        };
+        assert_eq!(csv.field_delimiter, b'\t');
+        assert_eq!(csv.record_delimiter, b"\r\n");
+        assert!(matches!(req.output, OutputFormat::Json { .. }));
+
+        let bad = "<SelectObjectContentRequest><Expression>SELECT * FROM S3Object</Expression>\
+                   <ExpressionType>SQL</ExpressionType><InputSerialization><CompressionType>BZIP2</CompressionType>\
+                   <CSV/></InputSerialization><OutputSerialization><CSV/></OutputSerialization>\
+                   </SelectObjectContentRequest>";
+        assert_eq!(
+            parse_request(bad.as_bytes()).unwrap_err().code,
+            "NotImplemented"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_csv_select_across_chunks() {
+        let mut data = b"id,name,score\n".to_vec();
+        for i in 0..50_000 {
+            data.extend_from_slice(format!("{i},\"user, {i}\",{}\n", i % 100).as_bytes());
+        }
+        let req = request(
+            "<CSV><FileHeaderInfo>USE</FileHeaderInfo></CSV>",
+            "<CSV/>",
+            "SELECT id, name FROM S3Object WHERE score = 99 LIMIT 3",
+        );
+        let (out, kinds) = run(req, data).await;
+        assert_eq!(
+            out,
+            "99,\"user, 99\"\n199,\"user, 199\"\n299,\"user, 299\"\n"
+        );
+        assert_eq!(kinds, vec!["Records", "Stats", "End"]);
+    }
+
+    #[tokio::test]
+    async fn test_json_lines_scan_range_and_gzip() {
+        let lines = "{\"a\":1}\n{\"a\":2}\n{\"a\":3}\n";
+        // Bytes 8..=9 fall inside the second record only.
+        let mut req = request(
+            "<JSON><Type>LINES</Type></JSON>",
+            "<JSON/>",
+            "SELECT s.a FROM S3Object s",
+        );
+        req.scan_range = Some((Some(8), Some(9)));
+        let (out, _) = run(req, lines.as_bytes().to_vec()).await;
+        assert_eq!(out, "{\"a\":2}\n");
+
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz.write_all(lines.as_bytes()).unwrap();
+        let req = request(
+            "<CompressionType>GZIP</CompressionType><JSON><Type>LINES</Type></JSON>",
+            "<CSV/>",
+            "SELECT SUM(a) FROM S3Object",
+        );
+        let (out, _) = run(req, gz.finish().unwrap()).await;
+        assert_eq!(out, "6\n");
+    }
+
+    #[tokio::test]
+    async fn test_json_document_and_errors() {
+        let doc = br#"{"rows": [{"k": "x", "v": 1}, {"k": "y", "v": 2}]}"#.to_vec();
+        let req = request(
+            "<JSON><Type>DOCUMENT</Type></JSON>",
+            "<JSON/>",
+            "SELECT r.k FROM S3Object[*].rows[*] r WHERE r.v > 1",
+        );
+        let (out, _) = run(req, doc).await;
+        assert_eq!(out, "{\"k\":\"y\"}\n");
+
+        let req = request(
+            "<JSON><Type>LINES</Type></JSON>",
+            "<JSON/>",
+            "SELECT * FROM S3Object",
+        );
+        let (_, kinds) = run(req, b"{\"a\":1}\nnot json\n".to_vec()).await;
+        assert_eq!(kinds.last().map(String::as_str), Some("InvalidJsonType"));
+    }
+
+    #[tokio::test]
+    async fn test_parquet_projection() {
+        use parquet::data_type::{ByteArray, ByteArrayType, Int64Type};
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+
+        let schema = Arc::new(
+            parse_message_type(
+                "message t { required int64 id; required binary name (UTF8); required int64 big; }",
+            )
+            .unwrap(),
+        );
+        let mut file = Vec::new();
+        let mut writer = SerializedFileWriter::new(&mut file, schema, Arc::default()).unwrap();
+        for group in 0..2i64 {
+            let mut rg = writer.next_row_group().unwrap();
+            let ids: Vec<i64> = (0..10).map(|i| group * 10 + i).collect();
+            let mut col = rg.next_column().unwrap().unwrap();
+            col.typed::<Int64Type>()
+                .write_batch(&ids, None, None)
+                .unwrap();
+            col.close().unwrap();
+            let names: Vec<ByteArray> = ids
+                .iter()
+                .map(|i| format!("n{i}").as_str().into())
+                .collect();
+            let mut col = rg.next_column().unwrap().unwrap();
+            col.typed::<ByteArrayType>()
+                .write_batch(&names, None, None)
+                .unwrap();
+            col.close().unwrap();
+            let mut col = rg.next_column().unwrap().unwrap();
+            col.typed::<Int64Type>()
+                .write_batch(&ids, None, None)
+                .unwrap();
+            col.close().unwrap();
+            rg.close().unwrap();
+        }
+        writer.close().unwrap();
+
+        let req = request(
+            "<Parquet/>",
+            "<CSV/>",
+            "SELECT name FROM S3Object WHERE id IN (3, 15)",
+        );
+        let (out, _) = run(req, file.clone()).await;
+        assert_eq!(out, "n3\nn15\n");
+
+        let req = request(
+            "<Parquet/>",
+            "<JSON/>",
+            "SELECT COUNT(*) AS n FROM S3Object",
+        );
+        let (out, _) = run(req, file).await;
+        assert_eq!(out, "{\"n\":20}\n");
+    }
+}
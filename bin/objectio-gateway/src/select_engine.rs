@@ -0,0 +1,1953 @@
+//! SQL evaluator for S3 Select.
+//!
+//! Implements the subset of the S3 Select SQL dialect that analytics
+//! clients (Spark / Presto S3 Select pushdown, `aws s3api
+//! select-object-content`, boto3) actually send:
+//!
+//! ```text
+//! SELECT <* | expr [AS alias], ...>
+//! FROM S3Object[[*].path] [[AS] alias]
+//! [WHERE <expr>]
+//! [LIMIT <n>]
+//! ```
+//!
+//! * Column references: positional `_1`, `_2`, … (CSV), header / JSON
+//!   field names (case-insensitive unless double-quoted), `alias.name`,
+//!   nested `a.b[0].c` for JSON.
+//! * Operators: `= != <> < <= > >=`, `AND OR NOT`, `+ - * / %`, `||`,
+//!   `LIKE … [ESCAPE …]`, `IN (…)`, `BETWEEN … AND …`, `IS [NOT] NULL`.
+//! * Functions: `CAST`, `LOWER`, `UPPER`, `CHAR_LENGTH`, `TRIM`,
+//!   `SUBSTRING`, `COALESCE`, `NULLIF`, `CASE WHEN … END`.
+//! * Aggregates: `COUNT`, `SUM`, `AVG`, `MIN`, `MAX` (no `GROUP BY`,
+//!   matching S3).
+//!
+//! CSV fields are strings; comparing one with a number coerces it the
+//! way S3 does for `WHERE _3 > 100`-style filters, so most queries work
+//! without an explicit `CAST`.
+
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use serde_json::Value as Json;
+
+/// Query failure: S3 Select error code and message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectError {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl SelectError {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn parse(message: impl Into<String>) -> Self {
+        Self::new("ParseUnexpectedToken", message)
+    }
+
+    fn unsupported(message: impl Into<String>) -> Self {
+        Self::new("ParseUnsupportedSyntax", message)
+    }
+}
+
+/// A SQL value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    /// JSON array or object (nested JSON / Parquet data)
+    Json(Json),
+}
+
+impl Value {
+    fn from_json(v: &Json) -> Self {
+        match v {
+            Json::Null => Self::Null,
+            Json::Bool(b) => Self::Bool(*b),
+            Json::Number(n) => n
+                .as_i64()
+                .map_or_else(|| Self::Float(n.as_f64().unwrap_or(f64::NAN)), Self::Int),
+            Json::String(s) => Self::Str(s.clone()),
+            other => Self::Json(other.clone()),
+        }
+    }
+
+    /// JSON form used by the JSON output serializer.
+    pub fn to_json(&self) -> Json {
+        match self {
+            Self::Null => Json::Null,
+            Self::Bool(b) => Json::Bool(*b),
+            Self::Int(i) => Json::from(*i),
+            Self::Float(f) => serde_json::Number::from_f64(*f).map_or(Json::Null, Json::Number),
+            Self::Str(s) => Json::String(s.clone()),
+            Self::Json(j) => j.clone(),
+        }
+    }
+
+    /// Text form used by the CSV output serializer (NULL is empty).
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Null => String::new(),
+            Self::Bool(b) => b.to_string(),
+            Self::Int(i) => i.to_string(),
+            Self::Float(f) => f.to_string(),
+            Self::Str(s) => s.clone(),
+            Self::Json(j) => j.to_string(),
+        }
+    }
+
+    const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    fn as_number(&self) -> Option<Self> {
+        match self {
+            Self::Int(_) | Self::Float(_) => Some(self.clone()),
+            Self::Str(s) => parse_number(s.trim()),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self.as_number()? {
+            Self::Int(i) => Some(i as f64),
+            Self::Float(f) => Some(f),
+            _ => None,
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "NULL",
+            Self::Bool(_) => "BOOL",
+            Self::Int(_) => "INT",
+            Self::Float(_) => "FLOAT",
+            Self::Str(_) => "STRING",
+            Self::Json(_) => "STRUCT",
+        }
+    }
+}
+
+fn parse_number(s: &str) -> Option<Value> {
+    if let Ok(i) = s.parse::<i64>() {
+        return Some(Value::Int(i));
+    }
+    s.parse::<f64>()
+        .ok()
+        .filter(|f| f.is_finite())
+        .map(Value::Float)
+}
+
+/// One input record handed to the evaluator.
+#[derive(Debug, Clone)]
+pub enum Record {
+    /// CSV row, with the header names when `FileHeaderInfo` is `USE`
+    Csv {
+        fields: Vec<String>,
+        header: Option<Arc<Vec<String>>>,
+    },
+    /// JSON document / line, or a Parquet row
+    Json(Json),
+}
+
+/// One step of a column path.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PathSeg {
+    /// `name` (case-insensitive) or `"name"` (exact)
+    Field { name: String, quoted: bool },
+    /// `[n]`
+    Index(usize),
+    /// `[*]`, only valid in the FROM clause
+    Wildcard,
+}
+
+impl PathSeg {
+    /// Whether this field step names `key`.
+    pub fn matches(&self, key: &str) -> bool {
+        match self {
+            Self::Field { name, quoted: true } => name == key,
+            Self::Field {
+                name,
+                quoted: false,
+            } => name.eq_ignore_ascii_case(key),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinOp {
+    And,
+    Or,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Concat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CastType {
+    Int,
+    Float,
+    String,
+    Bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AggFunc {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TrimSide {
+    Both,
+    Leading,
+    Trailing,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    /// Column path, relative to the record (alias already stripped).
+    /// An empty path is the whole record.
+    Column(Vec<PathSeg>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Like {
+        expr: Box<Expr>,
+        pattern: Box<Expr>,
+        escape: Option<Box<Expr>>,
+        negated: bool,
+    },
+    In {
+        expr: Box<Expr>,
+        list: Vec<Expr>,
+        negated: bool,
+    },
+    Between {
+        expr: Box<Expr>,
+        low: Box<Expr>,
+        high: Box<Expr>,
+        negated: bool,
+    },
+    IsNull(Box<Expr>, bool),
+    Cast(Box<Expr>, CastType),
+    Trim {
+        expr: Box<Expr>,
+        side: TrimSide,
+        chars: Option<Box<Expr>>,
+    },
+    Case {
+        operand: Option<Box<Expr>>,
+        branches: Vec<(Expr, Expr)>,
+        default: Option<Box<Expr>>,
+    },
+    Func(String, Vec<Expr>),
+    /// Aggregate; `None` argument is `COUNT(*)`. The index is the
+    /// accumulator slot assigned at parse time.
+    Agg(AggFunc, Option<Box<Expr>>, usize),
+}
+
+// ---------------------------------------------------------------------------
+// Tokenizer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    QuotedIdent(String),
+    Str(String),
+    Number(Value),
+    Sym(&'static str),
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>, SelectError> {
+    const SYMS: [&str; 19] = [
+        "<>", "!=", "<=", ">=", "||", "=", "<", ">", "+", "-", "*", "/", "%", "(", ")", ",", ".",
+        "[", "]",
+    ];
+    let chars: Vec<char> = sql.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '\'' || c == '"' {
+            let mut s = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(SelectError::new(
+                            "LexerInvalidLiteral",
+                            "unterminated literal",
+                        ));
+                    }
+                    Some(&q) if q == c && chars.get(i + 1) == Some(&c) => {
+                        s.push(c);
+                        i += 2;
+                    }
+                    Some(&q) if q == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&ch) => {
+                        s.push(ch);
+                        i += 1;
+                    }
+                }
+            }
+            out.push(if c == '\'' {
+                Token::Str(s)
+            } else {
+                Token::QuotedIdent(s)
+            });
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric()
+                    || chars[i] == '.'
+                    || ((chars[i] == '+' || chars[i] == '-') && matches!(chars[i - 1], 'e' | 'E')))
+            {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = parse_number(&text).ok_or_else(|| {
+                SelectError::new("LexerInvalidLiteral", format!("invalid number '{text}'"))
+            })?;
+            out.push(Token::Number(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            out.push(Token::Ident(chars[start..i].iter().collect()));
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let sym = SYMS.iter().find(|s| rest.starts_with(**s)).ok_or_else(|| {
+                SelectError::new("LexerInvalidChar", format!("unexpected character '{c}'"))
+            })?;
+            out.push(Token::Sym(sym));
+            i += sym.len();
+        }
+    }
+    Ok(out)
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+/// Reserved words that end an expression / can't be an implicit alias.
+const RESERVED: [&str; 24] = [
+    "SELECT", "FROM", "WHERE", "LIMIT", "AS", "AND", "OR", "NOT", "LIKE", "ESCAPE", "IN",
+    "BETWEEN", "IS", "NULL", "TRUE", "FALSE", "CASE", "WHEN", "THEN", "ELSE", "END", "CAST", "FOR",
+    "MISSING",
+];
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Table alias from the FROM clause (plus the implicit `S3Object`)
+    alias: Option<String>,
+    /// Aggregates seen so far; an `Expr::Agg` slot indexes this
+    aggs: Vec<AggFunc>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn peek_keyword(&self, kw: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(s)) if s.eq_ignore_ascii_case(kw))
+    }
+
+    fn eat_keyword(&mut self, kw: &str) -> bool {
+        let hit = self.peek_keyword(kw);
+        if hit {
+            self.pos += 1;
+        }
+        hit
+    }
+
+    fn expect_keyword(&mut self, kw: &str) -> Result<(), SelectError> {
+        if self.eat_keyword(kw) {
+            Ok(())
+        } else {
+            Err(self.unexpected(kw))
+        }
+    }
+
+    fn peek_sym(&self, sym: &str) -> bool {
+        matches!(self.peek(), Some(Token::Sym(s)) if *s == sym)
+    }
+
+    fn eat_sym(&mut self, sym: &str) -> bool {
+        let hit = self.peek_sym(sym);
+        if hit {
+            self.pos += 1;
+        }
+        hit
+    }
+
+    fn expect_sym(&mut self, sym: &str) -> Result<(), SelectError> {
+        if self.eat_sym(sym) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{sym}'")))
+        }
+    }
+
+    fn unexpected(&self, wanted: &str) -> SelectError {
+        match self.peek() {
+            Some(t) => SelectError::parse(format!("expected {wanted}, found {t:?}")),
+            None => SelectError::parse(format!("expected {wanted}, found end of query")),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, SelectError> {
+        self.or_expr()
+    }
+
+    fn or_expr(&mut self) -> Result<Expr, SelectError> {
+        let mut left = self.and_expr()?;
+        while self.eat_keyword("OR") {
+            let right = self.and_expr()?;
+            left = Expr::Binary(BinOp::Or, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, SelectError> {
+        let mut left = self.not_expr()?;
+        while self.eat_keyword("AND") {
+            let right = self.not_expr()?;
+            left = Expr::Binary(BinOp::And, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn not_expr(&mut self) -> Result<Expr, SelectError> {
+        if self.eat_keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.not_expr()?)));
+        }
+        self.predicate()
+    }
+
+    fn predicate(&mut self) -> Result<Expr, SelectError> {
+        let left = self.concat_expr()?;
+        if self.eat_keyword("IS") {
+            let negated = self.eat_keyword("NOT");
+            if !self.eat_keyword("NULL") && !self.eat_keyword("MISSING") {
+                return Err(self.unexpected("NULL"));
+            }
+            return Ok(Expr::IsNull(Box::new(left), negated));
+        }
+        let negated = self.eat_keyword("NOT");
+        if self.eat_keyword("LIKE") {
+            let pattern = self.concat_expr()?;
+            let escape = if self.eat_keyword("ESCAPE") {
+                Some(Box::new(self.concat_expr()?))
+            } else {
+                None
+            };
+            return Ok(Expr::Like {
+                expr: Box::new(left),
+                pattern: Box::new(pattern),
+                escape,
+                negated,
+            });
+        }
+        if self.eat_keyword("IN") {
+            self.expect_sym("(")?;
+            let list = self.expr_list(")")?;
+            return Ok(Expr::In {
+                expr: Box::new(left),
+                list,
+                negated,
+            });
+        }
+        if self.eat_keyword("BETWEEN") {
+            let low = self.concat_expr()?;
+            self.expect_keyword("AND")?;
+            let high = self.concat_expr()?;
+            return Ok(Expr::Between {
+                expr: Box::new(left),
+                low: Box::new(low),
+                high: Box::new(high),
+                negated,
+            });
+        }
+        if negated {
+            return Err(self.unexpected("LIKE, IN or BETWEEN"));
+        }
+        let op = match self.peek() {
+            Some(Token::Sym("=")) => BinOp::Eq,
+            Some(Token::Sym("!=" | "<>")) => BinOp::Ne,
+            Some(Token::Sym("<")) => BinOp::Lt,
+            Some(Token::Sym("<=")) => BinOp::Le,
+            Some(Token::Sym(">")) => BinOp::Gt,
+            Some(Token::Sym(">=")) => BinOp::Ge,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.concat_expr()?;
+        Ok(Expr::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    fn concat_expr(&mut self) -> Result<Expr, SelectError> {
+        let mut left = self.additive()?;
+        while self.eat_sym("||") {
+            let right = self.additive()?;
+            left = Expr::Binary(BinOp::Concat, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn additive(&mut self) -> Result<Expr, SelectError> {
+        let mut left = self.multiplicative()?;
+        loop {
+            let op = if self.eat_sym("+") {
+                BinOp::Add
+            } else if self.eat_sym("-") {
+                BinOp::Sub
+            } else {
+                return Ok(left);
+            };
+            let right = self.multiplicative()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, SelectError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat_sym("*") {
+                BinOp::Mul
+            } else if self.eat_sym("/") {
+                BinOp::Div
+            } else if self.eat_sym("%") {
+                BinOp::Mod
+            } else {
+                return Ok(left);
+            };
+            let right = self.unary()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, SelectError> {
+        if self.eat_sym("-") {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        if self.eat_sym("+") {
+            return self.unary();
+        }
+        self.primary()
+    }
+
+    fn expr_list(&mut self, close: &str) -> Result<Vec<Expr>, SelectError> {
+        let mut list = Vec::new();
+        if self.eat_sym(close) {
+            return Ok(list);
+        }
+        loop {
+            list.push(self.expr()?);
+            if self.eat_sym(close) {
+                return Ok(list);
+            }
+            self.expect_sym(",")?;
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, SelectError> {
+        match self.next() {
+            Some(Token::Number(v)) => Ok(Expr::Literal(v)),
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::Str(s))),
+            Some(Token::Sym("(")) => {
+                let e = self.expr()?;
+                self.expect_sym(")")?;
+                Ok(e)
+            }
+            Some(Token::QuotedIdent(name)) => self.column(name, true),
+            Some(Token::Ident(word)) => {
+                let upper = word.to_ascii_uppercase();
+                match upper.as_str() {
+                    "NULL" | "MISSING" => Ok(Expr::Literal(Value::Null)),
+                    "TRUE" => Ok(Expr::Literal(Value::Bool(true))),
+                    "FALSE" => Ok(Expr::Literal(Value::Bool(false))),
+                    "CASE" => self.case_expr(),
+                    _ if self.peek_sym("(") => {
+                        self.pos += 1;
+                        self.call(&upper)
+                    }
+                    _ if RESERVED.contains(&upper.as_str()) => {
+                        Err(SelectError::parse(format!("unexpected keyword {upper}")))
+                    }
+                    _ => self.column(word, false),
+                }
+            }
+            Some(t) => Err(SelectError::parse(format!("unexpected token {t:?}"))),
+            None => Err(SelectError::parse("unexpected end of query")),
+        }
+    }
+
+    /// Column path starting with `first`; strips the table alias.
+    fn column(&mut self, first: String, quoted: bool) -> Result<Expr, SelectError> {
+        let mut path = vec![PathSeg::Field {
+            name: first,
+            quoted,
+        }];
+        loop {
+            if self.eat_sym(".") {
+                match self.next() {
+                    Some(Token::Ident(name)) => path.push(PathSeg::Field {
+                        name,
+                        quoted: false,
+                    }),
+                    Some(Token::QuotedIdent(name)) => {
+                        path.push(PathSeg::Field { name, quoted: true })
+                    }
+                    _ => return Err(SelectError::parse("expected field name after '.'")),
+                }
+            } else if self.eat_sym("[") {
+                match self.next() {
+                    Some(Token::Number(Value::Int(n))) if n >= 0 => {
+                        path.push(PathSeg::Index(n as usize));
+                    }
+                    Some(Token::Str(name)) => path.push(PathSeg::Field { name, quoted: true }),
+                    _ => {
+                        return Err(SelectError::unsupported(
+                            "only [n] and ['name'] are supported in column paths",
+                        ));
+                    }
+                }
+                self.expect_sym("]")?;
+            } else {
+                break;
+            }
+        }
+        if self.is_table_ref(&path[0]) {
+            path.remove(0);
+        }
+        Ok(Expr::Column(path))
+    }
+
+    fn is_table_ref(&self, seg: &PathSeg) -> bool {
+        self.alias.as_deref().is_some_and(|a| seg.matches(a)) || seg.matches("S3Object")
+    }
+
+    fn case_expr(&mut self) -> Result<Expr, SelectError> {
+        let operand = if self.peek_keyword("WHEN") {
+            None
+        } else {
+            Some(Box::new(self.expr()?))
+        };
+        let mut branches = Vec::new();
+        while self.eat_keyword("WHEN") {
+            let cond = self.expr()?;
+            self.expect_keyword("THEN")?;
+            branches.push((cond, self.expr()?));
+        }
+        if branches.is_empty() {
+            return Err(self.unexpected("WHEN"));
+        }
+        let default = if self.eat_keyword("ELSE") {
+            Some(Box::new(self.expr()?))
+        } else {
+            None
+        };
+        self.expect_keyword("END")?;
+        Ok(Expr::Case {
+            operand,
+            branches,
+            default,
+        })
+    }
+
+    /// Function call; the opening parenthesis is already consumed.
+    fn call(&mut self, name: &str) -> Result<Expr, SelectError> {
+        let agg = match name {
+            "COUNT" => Some(AggFunc::Count),
+            "SUM" => Some(AggFunc::Sum),
+            "AVG" => Some(AggFunc::Avg),
+            "MIN" => Some(AggFunc::Min),
+            "MAX" => Some(AggFunc::Max),
+            _ => None,
+        };
+        if let Some(func) = agg {
+            let arg = if func == AggFunc::Count && self.eat_sym("*") {
+                None
+            } else {
+                let before = self.aggs.len();
+                let e = self.expr()?;
+                if self.aggs.len() != before {
+                    return Err(SelectError::unsupported("nested aggregate functions"));
+                }
+                Some(Box::new(e))
+            };
+            self.expect_sym(")")?;
+            let slot = self.aggs.len();
+            self.aggs.push(func);
+            return Ok(Expr::Agg(func, arg, slot));
+        }
+        match name {
+            "CAST" => {
+                let e = self.expr()?;
+                self.expect_keyword("AS")?;
+                let ty = match self.next() {
+                    Some(Token::Ident(t)) => match t.to_ascii_uppercase().as_str() {
+                        "INT" | "INTEGER" | "BIGINT" | "SMALLINT" => CastType::Int,
+                        "FLOAT" | "DOUBLE" | "REAL" | "DECIMAL" | "NUMERIC" => CastType::Float,
+                        "STRING" | "VARCHAR" | "CHAR" | "TEXT" => CastType::String,
+                        "BOOL" | "BOOLEAN" => CastType::Bool,
+                        other => {
+                            return Err(SelectError::new(
+                                "UnsupportedSqlOperation",
+                                format!("CAST to {other} is not supported"),
+                            ));
+                        }
+                    },
+                    _ => return Err(self.unexpected("type name")),
+                };
+                // DECIMAL(10, 2) and friends: precision is ignored.
+                if self.eat_sym("(") {
+                    self.expr_list(")")?;
+                }
+                self.expect_sym(")")?;
+                Ok(Expr::Cast(Box::new(e), ty))
+            }
+            "TRIM" => {
+                let mut side = TrimSide::Both;
+                if self.eat_keyword("LEADING") {
+                    side = TrimSide::Leading;
+                } else if self.eat_keyword("TRAILING") {
+                    side = TrimSide::Trailing;
+                } else {
+                    self.eat_keyword("BOTH");
+                }
+                let (expr, chars) = if self.eat_keyword("FROM") {
+                    (self.expr()?, None)
+                } else {
+                    let first = self.expr()?;
+                    if self.eat_keyword("FROM") {
+                        (self.expr()?, Some(Box::new(first)))
+                    } else {
+                        (first, None)
+                    }
+                };
+                self.expect_sym(")")?;
+                Ok(Expr::Trim {
+                    expr: Box::new(expr),
+                    side,
+                    chars,
+                })
+            }
+            "SUBSTRING" => {
+                let s = self.expr()?;
+                let mut args = vec![s];
+                if self.eat_keyword("FROM") {
+                    args.push(self.expr()?);
+                    if self.eat_keyword("FOR") {
+                        args.push(self.expr()?);
+                    }
+                    self.expect_sym(")")?;
+                } else {
+                    self.expect_sym(",")?;
+                    args.extend(self.expr_list(")")?);
+                }
+                check_arity(name, &args, 2, 3)?;
+                Ok(Expr::Func(name.to_string(), args))
+            }
+            "LOWER" | "UPPER" | "CHAR_LENGTH" | "CHARACTER_LENGTH" => {
+                let args = self.expr_list(")")?;
+                check_arity(name, &args, 1, 1)?;
+                Ok(Expr::Func(name.to_string(), args))
+            }
+            "NULLIF" => {
+                let args = self.expr_list(")")?;
+                check_arity(name, &args, 2, 2)?;
+                Ok(Expr::Func(name.to_string(), args))
+            }
+            "COALESCE" => {
+                let args = self.expr_list(")")?;
+                check_arity(name, &args, 1, usize::MAX)?;
+                Ok(Expr::Func(name.to_string(), args))
+            }
+            other => Err(SelectError::new(
+                "UnsupportedFunction",
+                format!("function {other} is not supported"),
+            )),
+        }
+    }
+}
+
+fn check_arity(name: &str, args: &[Expr], min: usize, max: usize) -> Result<(), SelectError> {
+    if args.len() < min || args.len() > max {
+        return Err(SelectError::new(
+            "IncorrectSqlFunctionArgumentType",
+            format!("wrong number of arguments to {name}"),
+        ));
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Query
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Projection {
+    Star,
+    Items(Vec<(Expr, String)>),
+}
+
+/// A parsed S3 Select query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    projection: Projection,
+    from_path: Vec<PathSeg>,
+    filter: Option<Expr>,
+    limit: Option<u64>,
+    aggregates: Vec<AggFunc>,
+}
+
+impl Query {
+    /// Parse an S3 Select SQL expression.
+    pub fn parse(sql: &str) -> Result<Self, SelectError> {
+        let tokens = tokenize(sql)?;
+        // The FROM clause defines the alias the SELECT list uses, so
+        // find it before parsing the projection.
+        // Skip the FROM inside `SUBSTRING(x FROM n)` / `TRIM(… FROM x)`.
+        let mut depth = 0usize;
+        let from_at = tokens
+            .iter()
+            .position(|t| match t {
+                Token::Sym("(") => {
+                    depth += 1;
+                    false
+                }
+                Token::Sym(")") => {
+                    depth = depth.saturating_sub(1);
+                    false
+                }
+                Token::Ident(s) => depth == 0 && s.eq_ignore_ascii_case("FROM"),
+                _ => false,
+            })
+            .ok_or_else(|| SelectError::parse("query has no FROM clause"))?;
+        let mut p = Parser {
+            tokens,
+            pos: from_at + 1,
+            alias: None,
+            aggs: Vec::new(),
+        };
+        let from_path = p.parse_from()?;
+        let tail = p.pos;
+
+        p.pos = 0;
+        p.expect_keyword("SELECT")?;
+        let projection = if p.eat_sym("*") {
+            Projection::Star
+        } else {
+            let mut items = Vec::new();
+            loop {
+                let expr = p.expr()?;
+                let alias = if p.eat_keyword("AS") {
+                    match p.next() {
+                        Some(Token::Ident(a) | Token::QuotedIdent(a)) => Some(a),
+                        _ => return Err(p.unexpected("alias")),
+                    }
+                } else {
+                    match p.peek() {
+                        Some(Token::Ident(a))
+                            if !RESERVED.contains(&a.to_ascii_uppercase().as_str()) =>
+                        {
+                            let a = a.clone();
+                            p.pos += 1;
+                            Some(a)
+                        }
+                        _ => None,
+                    }
+                };
+                let name = alias.unwrap_or_else(|| match &expr {
+                    Expr::Column(path) => match path.last() {
+                        Some(PathSeg::Field { name, .. }) => name.clone(),
+                        _ => format!("_{}", items.len() + 1),
+                    },
+                    _ => format!("_{}", items.len() + 1),
+                });
+                items.push((expr, name));
+                if !p.eat_sym(",") {
+                    break;
+                }
+            }
+            Projection::Items(items)
+        };
+        if p.pos != from_at {
+            return Err(p.unexpected("FROM"));
+        }
+        let select_aggs = p.aggs.len();
+
+        p.pos = tail;
+        let filter = if p.eat_keyword("WHERE") {
+            Some(p.expr()?)
+        } else {
+            None
+        };
+        if p.aggs.len() != select_aggs {
+            return Err(SelectError::unsupported(
+                "aggregate functions are not allowed in WHERE",
+            ));
+        }
+        let limit = if p.eat_keyword("LIMIT") {
+            match p.next() {
+                Some(Token::Number(Value::Int(n))) if n >= 0 => Some(n as u64),
+                _ => return Err(SelectError::parse("LIMIT expects a non-negative integer")),
+            }
+        } else {
+            None
+        };
+        if p.pos < p.tokens.len() {
+            return Err(p.unexpected("end of query"));
+        }
+
+        if let Projection::Items(items) = &projection
+            && select_aggs > 0
+            && items.iter().any(|(e, _)| !is_aggregate_only(e))
+        {
+            return Err(SelectError::new(
+                "UnsupportedSqlOperation",
+                "aggregate and non-aggregate expressions can't be mixed without GROUP BY",
+            ));
+        }
+
+        Ok(Self {
+            projection,
+            from_path,
+            filter,
+            limit,
+            aggregates: p.aggs,
+        })
+    }
+
+    /// Record path from the FROM clause, e.g. `S3Object[*].items[*]`.
+    pub fn from_path(&self) -> &[PathSeg] {
+        &self.from_path
+    }
+
+    /// Top-level columns the query reads, or `None` when it needs the
+    /// whole record (`SELECT *` or a bare alias). Used to project
+    /// Parquet column chunks.
+    pub fn referenced_columns(&self) -> Option<BTreeSet<PathSeg>> {
+        if self.projection == Projection::Star || !self.from_path.is_empty() {
+            return None;
+        }
+        let mut cols = Vec::new();
+        let mut whole = false;
+        let mut visit = |e: &Expr| {
+            if let Expr::Column(path) = e {
+                match path.first() {
+                    Some(seg @ PathSeg::Field { .. }) => cols.push(seg.clone()),
+                    _ => whole = true,
+                }
+            }
+        };
+        if let Projection::Items(items) = &self.projection {
+            for (e, _) in items {
+                walk(e, &mut visit);
+            }
+        }
+        if let Some(f) = &self.filter {
+            walk(f, &mut visit);
+        }
+        (!whole).then(|| cols.into_iter().collect())
+    }
+
+    /// Start evaluating the query over a record stream.
+    pub fn executor(&self) -> Executor<'_> {
+        Executor {
+            query: self,
+            accumulators: self
+                .aggregates
+                .iter()
+                .map(|&func| Accumulator::new(func))
+                .collect(),
+            emitted: 0,
+        }
+    }
+}
+
+impl Parser {
+    /// `S3Object[[*].path] [[AS] alias]`, then leaves `pos` on WHERE /
+    /// LIMIT / end.
+    fn parse_from(&mut self) -> Result<Vec<PathSeg>, SelectError> {
+        match self.next() {
+            Some(Token::Ident(s)) if s.eq_ignore_ascii_case("S3Object") => {}
+            _ => return Err(SelectError::parse("FROM must name S3Object")),
+        }
+        let mut path = Vec::new();
+        loop {
+            if self.eat_sym("[") {
+                if self.eat_sym("*") {
+                    path.push(PathSeg::Wildcard);
+                } else if let Some(Token::Number(Value::Int(n))) = self.peek().cloned() {
+                    self.pos += 1;
+                    path.push(PathSeg::Index(n.max(0) as usize));
+                } else {
+                    return Err(self.unexpected("'*' or an index"));
+                }
+                self.expect_sym("]")?;
+            } else if self.eat_sym(".") {
+                match self.next() {
+                    Some(Token::Ident(name)) => path.push(PathSeg::Field {
+                        name,
+                        quoted: false,
+                    }),
+                    Some(Token::QuotedIdent(name)) => {
+                        path.push(PathSeg::Field { name, quoted: true })
+                    }
+                    _ => return Err(SelectError::parse("expected field name after '.'")),
+                }
+            } else {
+                break;
+            }
+        }
+        // A lone `S3Object[*]` is the document itself.
+        if path == [PathSeg::Wildcard] {
+            path.clear();
+        }
+        self.eat_keyword("AS");
+        if let Some(Token::Ident(a)) = self.peek()
+            && !RESERVED.contains(&a.to_ascii_uppercase().as_str())
+        {
+            self.alias = Some(a.clone());
+            self.pos += 1;
+        }
+        Ok(path)
+    }
+}
+
+fn is_aggregate_only(e: &Expr) -> bool {
+    match e {
+        Expr::Agg(..) | Expr::Literal(_) => true,
+        Expr::Column(_) => false,
+        _ => {
+            let mut ok = true;
+            children(e, &mut |c| ok &= is_aggregate_only(c));
+            ok
+        }
+    }
+}
+
+fn children(e: &Expr, f: &mut dyn FnMut(&Expr)) {
+    match e {
+        Expr::Literal(_) | Expr::Column(_) => {}
+        Expr::Not(a) | Expr::Neg(a) | Expr::IsNull(a, _) | Expr::Cast(a, _) => f(a),
+        Expr::Binary(_, a, b) => {
+            f(a);
+            f(b);
+        }
+        Expr::Like {
+            expr,
+            pattern,
+            escape,
+            ..
+        } => {
+            f(expr);
+            f(pattern);
+            if let Some(x) = escape {
+                f(x);
+            }
+        }
+        Expr::In { expr, list, .. } => {
+            f(expr);
+            for x in list {
+                f(x);
+            }
+        }
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            f(expr);
+            f(low);
+            f(high);
+        }
+        Expr::Trim { expr, chars, .. } => {
+            f(expr);
+            if let Some(c) = chars {
+                f(c);
+            }
+        }
+        Expr::Case {
+            operand,
+            branches,
+            default,
+        } => {
+            if let Some(o) = operand {
+                f(o);
+            }
+            for (w, t) in branches {
+                f(w);
+                f(t);
+            }
+            if let Some(d) = default {
+                f(d);
+            }
+        }
+        Expr::Func(_, args) => {
+            for x in args {
+                f(x);
+            }
+        }
+        Expr::Agg(_, arg, _) => {
+            if let Some(a) = arg {
+                f(a);
+            }
+        }
+    }
+}
+
+fn walk(e: &Expr, f: &mut dyn FnMut(&Expr)) {
+    f(e);
+    children(e, &mut |c| walk(c, f));
+}
+
+// ---------------------------------------------------------------------------
+// Evaluation
+// ---------------------------------------------------------------------------
+
+/// An output row: column names and values, in SELECT order.
+pub type Row = Vec<(String, Value)>;
+
+#[derive(Debug, Clone)]
+struct Accumulator {
+    func: AggFunc,
+    count: u64,
+    sum: Option<Value>,
+    best: Option<Value>,
+}
+
+/// Streams records through a [`Query`].
+pub struct Executor<'q> {
+    query: &'q Query,
+    accumulators: Vec<Accumulator>,
+    emitted: u64,
+}
+
+impl Executor<'_> {
+    /// True once LIMIT rows have been produced; the caller can stop
+    /// reading input.
+    pub fn done(&self) -> bool {
+        self.query.aggregates.is_empty() && self.query.limit.is_some_and(|l| self.emitted >= l)
+    }
+
+    /// Feed one record. Returns the projected row when it passes the
+    /// WHERE clause (never for aggregate queries).
+    pub fn push(&mut self, record: &Record) -> Result<Option<Row>, SelectError> {
+        if self.done() {
+            return Ok(None);
+        }
+        if let Some(f) = &self.query.filter
+            && eval(f, record, &[])? != Value::Bool(true)
+        {
+            return Ok(None);
+        }
+        match &self.query.projection {
+            Projection::Items(items) if !self.query.aggregates.is_empty() => {
+                for (e, _) in items {
+                    self.accumulate(e, record)?;
+                }
+                Ok(None)
+            }
+            Projection::Items(items) => {
+                self.emitted += 1;
+                items
+                    .iter()
+                    .map(|(e, name)| Ok((name.clone(), eval(e, record, &[])?)))
+                    .collect::<Result<Row, _>>()
+                    .map(Some)
+            }
+            Projection::Star => {
+                self.emitted += 1;
+                Ok(Some(star_row(record)))
+            }
+        }
+    }
+
+    /// Final row of an aggregate query.
+    pub fn finish(self) -> Result<Option<Row>, SelectError> {
+        let Projection::Items(items) = &self.query.projection else {
+            return Ok(None);
+        };
+        if self.query.aggregates.is_empty() || self.query.limit == Some(0) {
+            return Ok(None);
+        }
+        let results: Vec<Value> = self.accumulators.iter().map(Accumulator::result).collect();
+        let empty = Record::Json(Json::Null);
+        items
+            .iter()
+            .map(|(e, name)| Ok((name.clone(), eval(e, &empty, &results)?)))
+            .collect::<Result<Row, _>>()
+            .map(Some)
+    }
+
+    fn accumulate(&mut self, e: &Expr, record: &Record) -> Result<(), SelectError> {
+        if let Expr::Agg(func, arg, slot) = e {
+            let v = match arg {
+                Some(a) => eval(a, record, &[])?,
+                None => Value::Bool(true),
+            };
+            if v.is_null() {
+                return Ok(());
+            }
+            let acc = &mut self.accumulators[*slot];
+            acc.count += 1;
+            match func {
+                AggFunc::Count => {}
+                AggFunc::Sum | AggFunc::Avg => {
+                    let n = v.as_number().ok_or_else(|| {
+                        SelectError::new(
+                            "InvalidDataType",
+                            format!("cannot sum {} value", v.type_name()),
+                        )
+                    })?;
+                    acc.sum = Some(match acc.sum.take() {
+                        None => n,
+                        Some(s) => arith(BinOp::Add, &s, &n)?,
+                    });
+                }
+                AggFunc::Min | AggFunc::Max => {
+                    let want = if *func == AggFunc::Min {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    };
+                    if acc
+                        .best
+                        .as_ref()
+                        .is_none_or(|b| compare(&v, b) == Some(want))
+                    {
+                        acc.best = Some(v);
+                    }
+                }
+            }
+            return Ok(());
+        }
+        let mut result = Ok(());
+        children(e, &mut |c| {
+            if result.is_ok() {
+                result = self.accumulate(c, record);
+            }
+        });
+        result
+    }
+}
+
+impl Accumulator {
+    const fn new(func: AggFunc) -> Self {
+        Self {
+            func,
+            count: 0,
+            sum: None,
+            best: None,
+        }
+    }
+
+    /// Final value; everything but COUNT is NULL over zero rows.
+    fn result(&self) -> Value {
+        match self.func {
+            AggFunc::Count => Value::Int(self.count as i64),
+            AggFunc::Sum => self.sum.clone().unwrap_or(Value::Null),
+            AggFunc::Avg => self
+                .sum
+                .as_ref()
+                .and_then(Value::as_f64)
+                .map_or(Value::Null, |s| Value::Float(s / self.count as f64)),
+            AggFunc::Min | AggFunc::Max => self.best.clone().unwrap_or(Value::Null),
+        }
+    }
+}
+
+fn star_row(record: &Record) -> Row {
+    match record {
+        Record::Csv { fields, header } => fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let name = header
+                    .as_ref()
+                    .and_then(|h| h.get(i).cloned())
+                    .unwrap_or_else(|| format!("_{}", i + 1));
+                (name, Value::Str(f.clone()))
+            })
+            .collect(),
+        Record::Json(Json::Object(map)) => map
+            .iter()
+            .map(|(k, v)| (k.clone(), Value::from_json(v)))
+            .collect(),
+        Record::Json(v) => vec![("_1".to_string(), Value::from_json(v))],
+    }
+}
+
+fn lookup_json<'a>(mut v: &'a Json, path: &[PathSeg]) -> Option<&'a Json> {
+    for seg in path {
+        v = match (seg, v) {
+            (PathSeg::Index(i), Json::Array(a)) => a.get(*i)?,
+            (PathSeg::Field { .. }, Json::Object(map)) => map
+                .get(match seg {
+                    PathSeg::Field { name, .. } => name.as_str(),
+                    _ => unreachable!(),
+                })
+                .or_else(|| map.iter().find(|(k, _)| seg.matches(k)).map(|(_, v)| v))?,
+            _ => return None,
+        };
+    }
+    Some(v)
+}
+
+fn column(record: &Record, path: &[PathSeg]) -> Value {
+    match record {
+        Record::Json(v) => lookup_json(v, path).map_or(Value::Null, Value::from_json),
+        Record::Csv { fields, header } => {
+            let Some(first) = path.first() else {
+                return Value::Json(Json::Array(
+                    fields.iter().map(|f| Json::String(f.clone())).collect(),
+                ));
+            };
+            if path.len() > 1 {
+                return Value::Null;
+            }
+            let idx = match first {
+                PathSeg::Field { name, .. } => header
+                    .as_ref()
+                    .and_then(|h| h.iter().position(|col| first.matches(col)))
+                    .or_else(|| {
+                        name.strip_prefix('_')
+                            .and_then(|n| n.parse::<usize>().ok())
+                            .filter(|n| *n > 0)
+                            .map(|n| n - 1)
+                    }),
+                PathSeg::Index(i) => Some(*i),
+                PathSeg::Wildcard => None,
+            };
+            idx.and_then(|i| fields.get(i))
+                .map_or(Value::Null, |f| Value::Str(f.clone()))
+        }
+    }
+}
+
+/// Expand a record through the FROM path (`S3Object[*].items[*]`).
+pub fn expand_from(v: Json, path: &[PathSeg]) -> Vec<Json> {
+    let Some((seg, rest)) = path.split_first() else {
+        return vec![v];
+    };
+    match (seg, v) {
+        (PathSeg::Wildcard, Json::Array(items)) => items
+            .into_iter()
+            .flat_map(|item| expand_from(item, rest))
+            .collect(),
+        (PathSeg::Wildcard, other) => expand_from(other, rest),
+        (seg, v) => lookup_json(&v, std::slice::from_ref(seg))
+            .cloned()
+            .map_or_else(Vec::new, |next| expand_from(next, rest)),
+    }
+}
+
+/// SQL comparison with S3's numeric coercion for string operands.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Null, _) | (_, Value::Null) => None,
+        (Value::Str(x), Value::Str(y)) => Some(x.cmp(y)),
+        (Value::Bool(x), Value::Bool(y)) => Some(x.cmp(y)),
+        (Value::Bool(x), Value::Str(s)) | (Value::Str(s), Value::Bool(x)) => {
+            let y = s.trim().parse::<bool>().ok()?;
+            let ord = x.cmp(&y);
+            Some(if matches!(a, Value::Bool(_)) {
+                ord
+            } else {
+                ord.reverse()
+            })
+        }
+        (Value::Json(x), Value::Json(y)) => (x == y).then_some(Ordering::Equal),
+        _ => match (a.as_number()?, b.as_number()?) {
+            (Value::Int(x), Value::Int(y)) => Some(x.cmp(&y)),
+            (x, y) => x.as_f64()?.partial_cmp(&y.as_f64()?),
+        },
+    }
+}
+
+fn arith(op: BinOp, a: &Value, b: &Value) -> Result<Value, SelectError> {
+    if a.is_null() || b.is_null() {
+        return Ok(Value::Null);
+    }
+    let (Some(x), Some(y)) = (a.as_number(), b.as_number()) else {
+        return Err(SelectError::new(
+            "InvalidDataType",
+            format!(
+                "arithmetic on {} and {} values",
+                a.type_name(),
+                b.type_name()
+            ),
+        ));
+    };
+    if matches!(op, BinOp::Div | BinOp::Mod) && y.as_f64() == Some(0.0) {
+        return Err(SelectError::new("DivisionByZero", "division by zero"));
+    }
+    if let (Value::Int(x), Value::Int(y)) = (&x, &y) {
+        let r = match op {
+            BinOp::Add => x.checked_add(*y),
+            BinOp::Sub => x.checked_sub(*y),
+            BinOp::Mul => x.checked_mul(*y),
+            BinOp::Div => x.checked_div(*y),
+            BinOp::Mod => x.checked_rem(*y),
+            _ => None,
+        };
+        return r
+            .map(Value::Int)
+            .ok_or_else(|| SelectError::new("IntegerOverflow", "integer overflow"));
+    }
+    let (x, y) = (x.as_f64().unwrap_or(0.0), y.as_f64().unwrap_or(0.0));
+    Ok(Value::Float(match op {
+        BinOp::Add => x + y,
+        BinOp::Sub => x - y,
+        BinOp::Mul => x * y,
+        BinOp::Div => x / y,
+        _ => x % y,
+    }))
+}
+
+fn truth(v: &Value) -> Option<bool> {
+    match v {
+        Value::Bool(b) => Some(*b),
+        Value::Str(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn bool_value(b: Option<bool>) -> Value {
+    b.map_or(Value::Null, Value::Bool)
+}
+
+fn string_arg(v: &Value) -> Option<String> {
+    match v {
+        Value::Null => None,
+        other => Some(other.to_text()),
+    }
+}
+
+/// SQL LIKE with `%` / `_` wildcards and an optional escape character.
+fn like(text: &str, pattern: &str, escape: Option<char>) -> bool {
+    let t: Vec<char> = text.chars().collect();
+    let mut p: Vec<(char, bool)> = Vec::new(); // (char, is_literal)
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if Some(c) == escape {
+            if let Some(n) = chars.next() {
+                p.push((n, true));
+            }
+        } else {
+            p.push((c, false));
+        }
+    }
+    // Iterative wildcard match with backtracking on the last '%'.
+    let (mut ti, mut pi) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        match p.get(pi) {
+            Some(('%', false)) => {
+                star = Some((pi, ti));
+                pi += 1;
+            }
+            Some(('_', false)) => {
+                ti += 1;
+                pi += 1;
+            }
+            Some((c, _)) if *c == t[ti] => {
+                ti += 1;
+                pi += 1;
+            }
+            _ => match star {
+                Some((sp, st)) => {
+                    pi = sp + 1;
+                    ti = st + 1;
+                    star = Some((sp, st + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    p[pi..].iter().all(|&(c, lit)| c == '%' && !lit)
+}
+
+fn cast(v: Value, ty: CastType) -> Result<Value, SelectError> {
+    if v.is_null() {
+        return Ok(Value::Null);
+    }
+    let fail = |v: &Value| {
+        SelectError::new(
+            "CastFailed",
+            format!("cannot cast {} to {ty:?}", v.to_text()),
+        )
+    };
+    Ok(match ty {
+        CastType::String => Value::Str(v.to_text()),
+        CastType::Int => match v.as_number() {
+            Some(Value::Int(i)) => Value::Int(i),
+            Some(Value::Float(f)) if f.is_finite() && f.abs() < 9.2e18 => {
+                Value::Int(f.trunc() as i64)
+            }
+            _ => match v {
+                Value::Bool(b) => Value::Int(i64::from(b)),
+                _ => return Err(fail(&v)),
+            },
+        },
+        CastType::Float => match v.as_f64() {
+            Some(f) => Value::Float(f),
+            None => match v {
+                Value::Bool(b) => Value::Float(if b { 1.0 } else { 0.0 }),
+                _ => return Err(fail(&v)),
+            },
+        },
+        CastType::Bool => match &v {
+            Value::Bool(b) => Value::Bool(*b),
+            Value::Int(i) => Value::Bool(*i != 0),
+            Value::Str(s) => Value::Bool(
+                s.trim()
+                    .to_ascii_lowercase()
+                    .parse()
+                    .map_err(|_| fail(&v))?,
+            ),
+            _ => return Err(fail(&v)),
+        },
+    })
+}
+
+/// Evaluate an expression. `aggs` holds the final aggregate values
+/// when projecting an aggregate query; it is empty otherwise.
+fn eval(e: &Expr, record: &Record, aggs: &[Value]) -> Result<Value, SelectError> {
+    let ev = |x: &Expr| eval(x, record, aggs);
+    Ok(match e {
+        Expr::Literal(v) => v.clone(),
+        Expr::Column(path) => column(record, path),
+        Expr::Agg(_, _, slot) => aggs.get(*slot).cloned().unwrap_or(Value::Null),
+        Expr::Not(a) => bool_value(truth(&ev(a)?).map(|b| !b)),
+        Expr::Neg(a) => arith(BinOp::Sub, &Value::Int(0), &ev(a)?)?,
+        Expr::Binary(BinOp::And, a, b) => {
+            let l = truth(&ev(a)?);
+            if l == Some(false) {
+                return Ok(Value::Bool(false));
+            }
+            match (l, truth(&ev(b)?)) {
+                (_, Some(false)) => Value::Bool(false),
+                (Some(true), Some(true)) => Value::Bool(true),
+                _ => Value::Null,
+            }
+        }
+        Expr::Binary(BinOp::Or, a, b) => {
+            let l = truth(&ev(a)?);
+            if l == Some(true) {
+                return Ok(Value::Bool(true));
+            }
+            match (l, truth(&ev(b)?)) {
+                (_, Some(true)) => Value::Bool(true),
+                (Some(false), Some(false)) => Value::Bool(false),
+                _ => Value::Null,
+            }
+        }
+        Expr::Binary(BinOp::Concat, a, b) => match (string_arg(&ev(a)?), string_arg(&ev(b)?)) {
+            (Some(x), Some(y)) => Value::Str(x + &y),
+            _ => Value::Null,
+        },
+        Expr::Binary(
+            op @ (BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge),
+            a,
+            b,
+        ) => {
+            let ord = compare(&ev(a)?, &ev(b)?);
+            bool_value(ord.map(|o| match op {
+                BinOp::Eq => o == Ordering::Equal,
+                BinOp::Ne => o != Ordering::Equal,
+                BinOp::Lt => o == Ordering::Less,
+                BinOp::Le => o != Ordering::Greater,
+                BinOp::Gt => o == Ordering::Greater,
+                _ => o != Ordering::Less,
+            }))
+        }
+        Expr::Binary(op, a, b) => arith(*op, &ev(a)?, &ev(b)?)?,
+        Expr::Like {
+            expr,
+            pattern,
+            escape,
+            negated,
+        } => {
+            let esc = match escape {
+                Some(x) => {
+                    let s = string_arg(&ev(x)?).unwrap_or_default();
+                    let mut it = s.chars();
+                    match (it.next(), it.next()) {
+                        (Some(c), None) => Some(c),
+                        _ => {
+                            return Err(SelectError::new(
+                                "EvaluatorInvalidEscapeSequence",
+                                "ESCAPE must be a single character",
+                            ));
+                        }
+                    }
+                }
+                None => None,
+            };
+            match (string_arg(&ev(expr)?), string_arg(&ev(pattern)?)) {
+                (Some(t), Some(p)) => Value::Bool(like(&t, &p, esc) != *negated),
+                _ => Value::Null,
+            }
+        }
+        Expr::In {
+            expr,
+            list,
+            negated,
+        } => {
+            let v = ev(expr)?;
+            if v.is_null() {
+                return Ok(Value::Null);
+            }
+            let mut saw_null = false;
+            for item in list {
+                match compare(&v, &ev(item)?) {
+                    Some(Ordering::Equal) => return Ok(Value::Bool(!negated)),
+                    None => saw_null = true,
+                    _ => {}
+                }
+            }
+            if saw_null {
+                Value::Null
+            } else {
+                Value::Bool(*negated)
+            }
+        }
+        Expr::Between {
+            expr,
+            low,
+            high,
+            negated,
+        } => {
+            let v = ev(expr)?;
+            match (compare(&v, &ev(low)?), compare(&v, &ev(high)?)) {
+                (Some(l), Some(h)) => {
+                    Value::Bool((l != Ordering::Less && h != Ordering::Greater) != *negated)
+                }
+                _ => Value::Null,
+            }
+        }
+        Expr::IsNull(a, negated) => Value::Bool(ev(a)?.is_null() != *negated),
+        Expr::Cast(a, ty) => cast(ev(a)?, *ty)?,
+        Expr::Trim { expr, side, chars } => {
+            let Some(s) = string_arg(&ev(expr)?) else {
+                return Ok(Value::Null);
+            };
+            let set: Vec<char> = match chars {
+                Some(c) => string_arg(&ev(c)?).unwrap_or_default().chars().collect(),
+                None => vec![' '],
+            };
+            let f = |c: char| set.contains(&c);
+            Value::Str(
+                match side {
+                    TrimSide::Both => s.trim_matches(f),
+                    TrimSide::Leading => s.trim_start_matches(f),
+                    TrimSide::Trailing => s.trim_end_matches(f),
+                }
+                .to_string(),
+            )
+        }
+        Expr::Case {
+            operand,
+            branches,
+            default,
+        } => {
+            let subject = operand.as_ref().map(|o| ev(o)).transpose()?;
+            for (when, then) in branches {
+                let w = ev(when)?;
+                let hit = match &subject {
+                    Some(s) => compare(s, &w) == Some(Ordering::Equal),
+                    None => truth(&w) == Some(true),
+                };
+                if hit {
+                    return ev(then);
+                }
+            }
+            match default {
+                Some(d) => ev(d)?,
+                None => Value::Null,
+            }
+        }
+        Expr::Func(name, args) => {
+            let vals = args.iter().map(ev).collect::<Result<Vec<_>, _>>()?;
+            call(name, vals)?
+        }
+    })
+}
+
+fn call(name: &str, mut args: Vec<Value>) -> Result<Value, SelectError> {
+    Ok(match name {
+        "COALESCE" => args
+            .into_iter()
+            .find(|v| !v.is_null())
+            .unwrap_or(Value::Null),
+        "NULLIF" => {
+            let b = args.pop().unwrap_or(Value::Null);
+            let a = args.pop().unwrap_or(Value::Null);
+            if compare(&a, &b) == Some(Ordering::Equal) {
+                Value::Null
+            } else {
+                a
+            }
+        }
+        _ => {
+            let Some(s) = args.first().and_then(string_arg) else {
+                return Ok(Value::Null);
+            };
+            match name {
+                "LOWER" => Value::Str(s.to_lowercase()),
+                "UPPER" => Value::Str(s.to_uppercase()),
+                "CHAR_LENGTH" | "CHARACTER_LENGTH" => Value::Int(s.chars().count() as i64),
+                "SUBSTRING" => {
+                    let int_arg = |v: Option<&Value>| -> Result<Option<i64>, SelectError> {
+                        match v {
+                            None | Some(Value::Null) => Ok(None),
+                            Some(v) => match cast(v.clone(), CastType::Int)? {
+                                Value::Int(i) => Ok(Some(i)),
+                                _ => Ok(None),
+                            },
+                        }
+                    };
+                    // SQL positions are 1-based; a start before 1 eats
+                    // into the length, as in the standard.
+                    let start = int_arg(args.get(1))?.unwrap_or(1);
+                    let end = match int_arg(args.get(2))? {
+                        Some(len) if len < 0 => {
+                            return Err(SelectError::new(
+                                "EvaluatorInvalidArguments",
+                                "SUBSTRING length must not be negative",
+                            ));
+                        }
+                        Some(len) => start.saturating_add(len),
+                        None => i64::MAX,
+                    };
+                    let from = (start.max(1) - 1) as usize;
+                    let take = (end.max(1) - 1).saturating_sub(from as i64).max(0) as usize;
+                    Value::Str(s.chars().skip(from).take(take).collect())
+                }
+                other => {
+                    return Err(SelectError::new(
+                        "UnsupportedFunction",
+                        format!("function {other} is not supported"),
+                    ));
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv(fields: &[&str], header: Option<&[&str]>) -> Record {
+        Record::Csv {
+            fields: fields.iter().map(ToString::to_string).collect(),
+            header: header.map(|h| Arc::new(h.iter().map(ToString::to_string).collect())),
+        }
+    }
+
+    fn run(sql: &str, records: &[Record]) -> Vec<Row> {
+        let q = Query::parse(sql).unwrap();
+        let mut ex = q.executor();
+        let mut rows: Vec<Row> = records.iter().filter_map(|r| ex.push(r).unwrap()).collect();
+        rows.extend(ex.finish().unwrap());
+        rows
+    }
+
+    #[test]
+    fn test_csv_filter_and_projection() {
+        let header: &[&str] = &["name", "age", "city"];
+        let records = [
+            csv(&["alice", "34", "Paris"], Some(header)),
+            csv(&["bob", "19", "Oslo"], Some(header)),
+            csv(&["carol", "51", "Rome"], Some(header)),
+        ];
+        let rows = run(
+            "SELECT s.name, UPPER(s.city) AS c FROM S3Object s WHERE CAST(s.age AS INT) > 30",
+            &records,
+        );
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][0], ("name".into(), Value::Str("alice".into())));
+        assert_eq!(rows[1][1], ("c".into(), Value::Str("ROME".into())));
+
+        // positional refs, implicit numeric coercion, LIMIT
+        let rows = run("SELECT _1 FROM S3Object WHERE _2 >= 19 LIMIT 1", &records);
+        assert_eq!(rows, vec![vec![("_1".into(), Value::Str("alice".into()))]]);
+
+        let rows = run(
+            "select * from s3object where name like '_o%' or city in ('Rome')",
+            &records,
+        );
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1][2].1, Value::Str("Rome".into()));
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let records: Vec<Record> = (1..=4)
+            .map(|i| {
+                Record::Json(
+                    serde_json::json!({"v": i, "k": if i % 2 == 0 { "even" } else { "odd" }}),
+                )
+            })
+            .collect();
+        let rows = run(
+            "SELECT COUNT(*), SUM(v), AVG(v), MIN(k), MAX(v) * 10 FROM S3Object WHERE v > 1",
+            &records,
+        );
+        assert_eq!(
+            rows,
+            vec![vec![
+                ("_1".into(), Value::Int(3)),
+                ("_2".into(), Value::Int(9)),
+                ("_3".into(), Value::Float(3.0)),
+                ("_4".into(), Value::Str("even".into())),
+                ("_5".into(), Value::Int(40)),
+            ]]
+        );
+        assert!(Query::parse("SELECT COUNT(*), v FROM S3Object").is_err());
+    }
+
+    #[test]
+    fn test_json_paths_and_from_clause() {
+        let doc = serde_json::json!({"items": [
+            {"id": 1, "tags": ["a", "b"], "meta": {"Owner": "x"}},
+            {"id": 2, "tags": [], "meta": null}
+        ]});
+        let q = Query::parse("SELECT i.id, i.tags[1], i.meta.owner FROM S3Object[*].items[*] i")
+            .unwrap();
+        let records: Vec<Record> = expand_from(doc, q.from_path())
+            .into_iter()
+            .map(Record::Json)
+            .collect();
+        assert_eq!(records.len(), 2);
+        let mut ex = q.executor();
+        let row = ex.push(&records[0]).unwrap().unwrap();
+        assert_eq!(row[1], ("_2".into(), Value::Str("b".into())));
+        assert_eq!(row[2], ("owner".into(), Value::Str("x".into())));
+        let row = ex.push(&records[1]).unwrap().unwrap();
+        assert_eq!(row[1].1, Value::Null);
+    }
+
+    #[test]
+    fn test_null_semantics_and_functions() {
+        let r = Record::Json(serde_json::json!({"a": null, "b": "  hi  ", "n": 7}));
+        let q = |sql: &str| run(sql, std::slice::from_ref(&r));
+        assert!(q("SELECT * FROM S3Object WHERE a = 1").is_empty());
+        assert!(q("SELECT * FROM S3Object WHERE NOT (a = 1)").is_empty());
+        assert_eq!(
+            q("SELECT * FROM S3Object WHERE a IS NULL OR a = 1").len(),
+            1
+        );
+        let row = &q(
+            "SELECT TRIM(b), COALESCE(a, 'd'), NULLIF(n, 7), SUBSTRING('hello', 2, 3), \
+                      SUBSTRING('hello' FROM 4), n BETWEEN 1 AND 10, n % 4, \
+                      CASE WHEN n > 5 THEN 'big' ELSE 'small' END, 'a' || 'b' FROM S3Object",
+        )[0];
+        let vals: Vec<&Value> = row.iter().map(|(_, v)| v).collect();
+        assert_eq!(
+            vals,
+            vec![
+                &Value::Str("hi".into()),
+                &Value::Str("d".into()),
+                &Value::Null,
+                &Value::Str("ell".into()),
+                &Value::Str("lo".into()),
+                &Value::Bool(true),
+                &Value::Int(3),
+                &Value::Str("big".into()),
+                &Value::Str("ab".into()),
+            ]
+        );
+        assert!(like("a%b_c", "a\\%b\\_c", Some('\\')));
+        assert!(like("abcabc", "%bc", None));
+        assert!(!like("abc", "a_", None));
+        assert_eq!(
+            Query::parse("SELECT 1 / 0 FROM S3Object")
+                .unwrap()
+                .executor()
+                .push(&r)
+                .unwrap_err()
+                .code,
+            "DivisionByZero"
+        );
+    }
+
+    #[test]
+    fn test_referenced_columns() {
+        let cols = Query::parse("SELECT s.a, b FROM S3Object s WHERE \"C\" > 1")
+            .unwrap()
+            .referenced_columns()
+            .unwrap();
+        let names: Vec<_> = cols
+            .iter()
+            .map(|c| match c {
+                PathSeg::Field { name, .. } => name.as_str(),
+                _ => "",
+            })
+            .collect();
+        assert_eq!(names, vec!["C", "a", "b"]);
+        assert!(
+            Query::parse("SELECT * FROM S3Object")
+                .unwrap()
+                .referenced_columns()
+                .is_none()
+        );
+        assert!(
+            Query::parse("SELECT s FROM S3Object s")
+                .unwrap()
+                .referenced_columns()
+                .is_none()
+        );
+        assert!(
+            Query::parse("SELECT COUNT(*) FROM S3Object")
+                .unwrap()
+                .referenced_columns()
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
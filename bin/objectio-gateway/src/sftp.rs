@@ -0,0 +1,1052 @@
+//! SFTP frontend for buckets.
+//!
+//! For partners that can only deliver data over SFTP. Users log in with an
+//! access key — SSH user name = access key ID, password = secret key — and
+//! see buckets as directories. File operations become S3 calls through the
+//! same handlers the HTTP API uses, so bucket policies, ACLs, encryption
+//! defaults and Object Lock apply unchanged:
+//!   - read → ranged GetObject, pinned with `If-Match` to the ETag seen at
+//!     open so an overwrite mid-transfer fails instead of mixing versions
+//!   - write → multipart upload, one part per [`PART_SIZE`] of sequential
+//!     data; files smaller than one part become a single PutObject
+//!   - readdir → the meta listing index with delimiter `/`
+//!   - mkdir / rmdir → zero-byte `dir/` marker objects
+//!   - rename → CopyObject + DeleteObject (files only)
+//!
+//! `--sftp-home USER=bucket[/prefix]` confines a user (access key ID or user
+//! ID) to one bucket or prefix, which then appears as `/`. Users without a
+//! mapping see their tenant's buckets at `/`.
+//!
+//! Writes must be sequential (what every SFTP client does for uploads), and
+//! an upload only becomes visible on close. A client that disconnects
+//! mid-upload leaves an incomplete multipart upload behind for the usual
+//! multipart cleanup.
+
+use crate::auth_middleware::AuthState;
+use crate::s3::{self, AppState};
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode as HttpStatus, header};
+use axum::response::Response;
+use axum::{Extension, body::Bytes};
+use objectio_auth::{AuthMode, AuthResult};
+use objectio_proto::metadata::{
+    GetBucketRequest, ListBucketsRequest, ListObjectsRequest, ListObjectsResponse,
+};
+use russh::keys::PrivateKey;
+use russh::server::{Auth, Msg, Session};
+use russh::{Channel, ChannelId, MethodKind, MethodSet};
+use russh_sftp::protocol::{
+    Attrs, Data, File, FileAttributes, Handle, Name, OpenFlags, Status, StatusCode,
+};
+use russh_sftp::server::StatusReply;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Upload part size. Each open upload buffers at most this much.
+pub const PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// How much a read fetches from the object at once; SFTP clients read in
+/// 32–256 KiB requests.
+const READ_WINDOW: u64 = 4 * 1024 * 1024;
+
+/// Directory entries returned per READDIR.
+const LIST_PAGE: u32 = 1000;
+
+/// `USER=bucket[/prefix]`: confine a user to a bucket or prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SftpHome {
+    /// Access key ID or user ID
+    pub user: String,
+    pub bucket: String,
+    /// Key prefix, empty or ending in `/`
+    pub prefix: String,
+}
+
+impl FromStr for SftpHome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (user, path) = s
+            .split_once('=')
+            .ok_or_else(|| format!("invalid SFTP home '{s}': expected USER=bucket[/prefix]"))?;
+        let path = path.trim_matches('/');
+        let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+        if user.is_empty() || bucket.is_empty() {
+            return Err(format!(
+                "invalid SFTP home '{s}': expected USER=bucket[/prefix]"
+            ));
+        }
+        Ok(Self {
+            user: user.to_string(),
+            bucket: bucket.to_string(),
+            prefix: if prefix.is_empty() {
+                String::new()
+            } else {
+                format!("{prefix}/")
+            },
+        })
+    }
+}
+
+/// Load the SSH host key, generating and saving an Ed25519 key on first
+/// start so clients see a stable fingerprint.
+pub fn load_or_create_host_key(path: &std::path::Path) -> anyhow::Result<PrivateKey> {
+    if path.exists() {
+        return russh::keys::load_secret_key(path, None)
+            .map_err(|e| anyhow::anyhow!("Failed to load SFTP host key {}: {e}", path.display()));
+    }
+    let key = PrivateKey::random(
+        &mut russh::keys::ssh_key::rand_core::OsRng,
+        russh::keys::Algorithm::Ed25519,
+    )
+    .map_err(|e| anyhow::anyhow!("Failed to generate SFTP host key: {e}"))?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    key.write_openssh_file(path, russh::keys::ssh_key::LineEnding::LF)
+        .map_err(|e| anyhow::anyhow!("Failed to write SFTP host key {}: {e}", path.display()))?;
+    info!(
+        "Generated SFTP host key {} ({})",
+        path.display(),
+        key.public_key().fingerprint(russh::keys::HashAlg::Sha256)
+    );
+    Ok(key)
+}
+
+/// SSH server settings: password auth only, with `host_key`.
+pub fn server_config(host_key: PrivateKey) -> russh::server::Config {
+    russh::server::Config {
+        methods: MethodSet::from(&[MethodKind::Password][..]),
+        auth_rejection_time: Duration::from_secs(1),
+        auth_rejection_time_initial: Some(Duration::ZERO),
+        inactivity_timeout: Some(Duration::from_secs(3600)),
+        keys: vec![host_key],
+        ..Default::default()
+    }
+}
+
+/// Accepts SSH connections; one [`SshSession`] per client.
+#[derive(Clone)]
+pub struct SftpServer {
+    state: Arc<AppState>,
+    auth: Arc<AuthState>,
+    homes: Arc<Vec<SftpHome>>,
+}
+
+impl SftpServer {
+    pub fn new(state: Arc<AppState>, auth: Arc<AuthState>, homes: Vec<SftpHome>) -> Self {
+        Self {
+            state,
+            auth,
+            homes: Arc::new(homes),
+        }
+    }
+}
+
+impl russh::server::Server for SftpServer {
+    type Handler = SshSession;
+
+    fn new_client(&mut self, peer: Option<SocketAddr>) -> SshSession {
+        SshSession {
+            server: self.clone(),
+            peer,
+            identity: None,
+            channels: HashMap::new(),
+        }
+    }
+}
+
+/// Where `/` points for a logged-in user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Root {
+    /// `/` lists buckets, `/bucket/key` is an object.
+    Buckets,
+    /// `/` is `bucket/prefix`.
+    Home { bucket: String, prefix: String },
+}
+
+/// An object or directory inside a bucket; `key` is empty for the bucket
+/// itself and has no trailing `/` for directories.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    bucket: String,
+    key: String,
+}
+
+impl Location {
+    /// Listing prefix for this location as a directory.
+    fn dir_prefix(&self) -> String {
+        if self.key.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", self.key)
+        }
+    }
+}
+
+/// Resolve `.` and `..` in an SFTP path; relative paths start at `/`.
+fn path_components(path: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            p => parts.push(p),
+        }
+    }
+    parts
+}
+
+impl Root {
+    /// Map an SFTP path to a bucket location; `None` is the bucket list.
+    fn locate(&self, path: &str) -> Option<Location> {
+        let parts = path_components(path);
+        match self {
+            Self::Buckets => {
+                let (bucket, rest) = parts.split_first()?;
+                Some(Location {
+                    bucket: (*bucket).to_string(),
+                    key: rest.join("/"),
+                })
+            }
+            Self::Home { bucket, prefix } => {
+                let rest = parts.join("/");
+                let key = if rest.is_empty() {
+                    prefix.trim_end_matches('/').to_string()
+                } else {
+                    format!("{prefix}{rest}")
+                };
+                Some(Location {
+                    bucket: bucket.clone(),
+                    key,
+                })
+            }
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Per-connection SSH state.
+pub struct SshSession {
+    server: SftpServer,
+    peer: Option<SocketAddr>,
+    identity: Option<(AuthResult, Root)>,
+    channels: HashMap<ChannelId, Channel<Msg>>,
+}
+
+impl russh::server::Handler for SshSession {
+    type Error = russh::Error;
+
+    async fn auth_password(&mut self, user: &str, password: &str) -> Result<Auth, Self::Error> {
+        let cred = match self.server.auth.lookup_credential(user).await {
+            Ok(c) if constant_time_eq(c.secret_access_key.as_bytes(), password.as_bytes()) => c,
+            _ => {
+                warn!("SFTP login failed for {user} from {:?}", self.peer);
+                return Ok(Auth::reject());
+            }
+        };
+        let (group_arns, group_ids) = self.server.auth.lookup_user_groups(&cred.user_id).await;
+        let root = self
+            .server
+            .homes
+            .iter()
+            .find(|h| h.user == cred.access_key_id || h.user == cred.user_id)
+            .map_or(Root::Buckets, |h| Root::Home {
+                bucket: h.bucket.clone(),
+                prefix: h.prefix.clone(),
+            });
+        info!(
+            "SFTP login: {} ({}) from {:?}",
+            cred.user_arn, cred.access_key_id, self.peer
+        );
+        self.identity = Some((
+            AuthResult {
+                user_id: cred.user_id,
+                user_arn: cred.user_arn,
+                access_key_id: cred.access_key_id,
+                group_arns,
+                group_ids,
+                tenant: cred.tenant,
+                auth_mode: AuthMode::Permanent,
+            },
+            root,
+        ));
+        Ok(Auth::Accept)
+    }
+
+    async fn channel_open_session(
+        &mut self,
+        channel: Channel<Msg>,
+        _session: &mut Session,
+    ) -> Result<bool, Self::Error> {
+        self.channels.insert(channel.id(), channel);
+        Ok(true)
+    }
+
+    async fn channel_eof(
+        &mut self,
+        channel: ChannelId,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        session.close(channel)
+    }
+
+    async fn subsystem_request(
+        &mut self,
+        channel_id: ChannelId,
+        name: &str,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let channel = self.channels.remove(&channel_id);
+        match (name, channel, &self.identity) {
+            ("sftp", Some(channel), Some((auth, root))) => {
+                session.channel_success(channel_id)?;
+                let sftp = SftpSession {
+                    state: Arc::clone(&self.server.state),
+                    auth: auth.clone(),
+                    root: root.clone(),
+                    handles: HashMap::new(),
+                    next_handle: 0,
+                };
+                russh_sftp::server::run(channel.into_stream(), sftp).await;
+            }
+            _ => session.channel_failure(channel_id)?,
+        }
+        Ok(())
+    }
+}
+
+fn fail(code: StatusCode, message: impl Into<String>) -> StatusReply {
+    StatusReply::new(code).with_message(message)
+}
+
+/// Map a non-2xx S3 handler response to an SFTP status.
+fn check_response(resp: &Response) -> Result<(), StatusReply> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let code = match status {
+        HttpStatus::NOT_FOUND => StatusCode::NoSuchFile,
+        HttpStatus::FORBIDDEN | HttpStatus::UNAUTHORIZED => StatusCode::PermissionDenied,
+        _ => StatusCode::Failure,
+    };
+    Err(fail(code, format!("storage returned {status}")))
+}
+
+fn ok_status(id: u32) -> Status {
+    Status {
+        id,
+        status_code: StatusCode::Ok,
+        error_message: "Ok".to_string(),
+        language_tag: "en-US".to_string(),
+    }
+}
+
+fn dir_attrs(mtime: u64) -> FileAttributes {
+    FileAttributes {
+        permissions: Some(0o040_755),
+        mtime: u32::try_from(mtime).ok(),
+        atime: u32::try_from(mtime).ok(),
+        ..FileAttributes::default()
+    }
+}
+
+fn file_attrs(size: u64, mtime: u64) -> FileAttributes {
+    FileAttributes {
+        size: Some(size),
+        permissions: Some(0o100_644),
+        mtime: u32::try_from(mtime).ok(),
+        atime: u32::try_from(mtime).ok(),
+        ..FileAttributes::default()
+    }
+}
+
+fn header_str<'a>(resp: &'a Response, name: &str) -> Option<&'a str> {
+    resp.headers().get(name).and_then(|v| v.to_str().ok())
+}
+
+/// Size, ETag and modification time from a HEAD response.
+fn head_info(resp: &Response) -> (u64, String, u64) {
+    let size = header_str(resp, "content-length")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let etag = header_str(resp, "etag").unwrap_or_default().to_string();
+    let mtime = header_str(resp, "last-modified")
+        .and_then(|v| chrono::DateTime::parse_from_rfc2822(v).ok())
+        .and_then(|dt| u64::try_from(dt.timestamp()).ok())
+        .unwrap_or(0);
+    (size, etag, mtime)
+}
+
+/// Text between `<tag>` and `</tag>` in a small XML response.
+fn xml_field(xml: &str, tag: &str) -> Option<String> {
+    let start = xml.find(&format!("<{tag}>"))? + tag.len() + 2;
+    let end = start + xml[start..].find(&format!("</{tag}>"))?;
+    Some(xml[start..end].to_string())
+}
+
+struct DirHandle {
+    location: Option<Location>,
+    continuation: Option<String>,
+    done: bool,
+}
+
+struct ReadHandle {
+    location: Location,
+    size: u64,
+    etag: String,
+    window_start: u64,
+    window: Bytes,
+}
+
+struct WriteHandle {
+    location: Location,
+    upload_id: Option<String>,
+    /// (part number, ETag) of uploaded parts
+    parts: Vec<(u32, String)>,
+    buf: Vec<u8>,
+    written: u64,
+}
+
+enum OpenHandle {
+    Dir(DirHandle),
+    Read(ReadHandle),
+    Write(WriteHandle),
+}
+
+/// One SFTP subsystem channel, acting as the logged-in user.
+struct SftpSession {
+    state: Arc<AppState>,
+    auth: AuthResult,
+    root: Root,
+    handles: HashMap<String, OpenHandle>,
+    next_handle: u64,
+}
+
+impl SftpSession {
+    fn auth(&self) -> Option<Extension<AuthResult>> {
+        Some(Extension(self.auth.clone()))
+    }
+
+    fn add_handle(&mut self, handle: OpenHandle) -> String {
+        self.next_handle += 1;
+        let id = self.next_handle.to_string();
+        self.handles.insert(id.clone(), handle);
+        id
+    }
+
+    /// Resolve a path that must name an object (not `/` or a bucket).
+    fn object_location(&self, path: &str) -> Result<Location, StatusReply> {
+        self.root
+            .locate(path)
+            .filter(|loc| !loc.key.is_empty())
+            .ok_or_else(|| fail(StatusCode::PermissionDenied, "not a file path"))
+    }
+
+    async fn check_policy(
+        &self,
+        action: &str,
+        loc: &Location,
+        key: bool,
+    ) -> Result<(), StatusReply> {
+        let resource = s3::build_s3_arn(&loc.bucket, key.then_some(loc.key.as_str()));
+        match s3::check_bucket_policy(
+            &self.state,
+            &loc.bucket,
+            &self.auth.user_arn,
+            action,
+            &resource,
+            None,
+            self.auth.auth_mode,
+        )
+        .await
+        {
+            None => Ok(()),
+            Some(_) => Err(fail(StatusCode::PermissionDenied, "access denied")),
+        }
+    }
+
+    async fn list(
+        &self,
+        loc: &Location,
+        prefix: &str,
+        delimiter: &str,
+        continuation: Option<String>,
+        max_keys: u32,
+    ) -> Result<ListObjectsResponse, StatusReply> {
+        self.check_policy("s3:ListBucket", loc, false).await?;
+        let mut client = self.state.meta_client.clone();
+        client
+            .list_objects(ListObjectsRequest {
+                bucket: loc.bucket.clone(),
+                prefix: prefix.to_string(),
+                delimiter: delimiter.to_string(),
+                start_after: String::new(),
+                continuation_token: continuation.unwrap_or_default(),
+                max_keys,
+                include_versions: false,
+            })
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(|e| fail(StatusCode::Failure, e.message().to_string()))
+    }
+
+    async fn bucket_exists(&self, bucket: &str) -> bool {
+        let mut client = self.state.meta_client.clone();
+        client
+            .get_bucket(GetBucketRequest {
+                name: bucket.to_string(),
+            })
+            .await
+            .is_ok()
+    }
+
+    /// Whether anything is stored under `loc` as a directory.
+    async fn is_dir(&self, loc: &Location) -> Result<bool, StatusReply> {
+        let page = self.list(loc, &loc.dir_prefix(), "", None, 1).await?;
+        Ok(!page.entries.is_empty() || !page.common_prefixes.is_empty())
+    }
+
+    async fn head(&self, loc: &Location) -> Response {
+        s3::head_object(
+            State(Arc::clone(&self.state)),
+            Path((loc.bucket.clone(), loc.key.clone())),
+            self.auth(),
+            HeaderMap::new(),
+        )
+        .await
+    }
+
+    async fn attrs(&self, path: &str) -> Result<FileAttributes, StatusReply> {
+        let Some(loc) = self.root.locate(path) else {
+            return Ok(dir_attrs(0));
+        };
+        if loc.key.is_empty() {
+            return if self.bucket_exists(&loc.bucket).await {
+                Ok(dir_attrs(0))
+            } else {
+                Err(StatusCode::NoSuchFile.into())
+            };
+        }
+        let resp = self.head(&loc).await;
+        if resp.status().is_success() {
+            let (size, _, mtime) = head_info(&resp);
+            return Ok(file_attrs(size, mtime));
+        }
+        if resp.status() == HttpStatus::NOT_FOUND && self.is_dir(&loc).await? {
+            return Ok(dir_attrs(0));
+        }
+        check_response(&resp).map(|()| dir_attrs(0))
+    }
+
+    async fn put(
+        &self,
+        loc: &Location,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> Result<(), StatusReply> {
+        let resp = s3::put_object(
+            State(Arc::clone(&self.state)),
+            Path((loc.bucket.clone(), loc.key.clone())),
+            self.auth(),
+            headers,
+            body,
+        )
+        .await;
+        check_response(&resp)
+    }
+
+    async fn delete(&self, loc: &Location) -> Result<(), StatusReply> {
+        let resp = s3::delete_object(
+            State(Arc::clone(&self.state)),
+            Path((loc.bucket.clone(), loc.key.clone())),
+            self.auth(),
+            None,
+            HeaderMap::new(),
+        )
+        .await;
+        check_response(&resp)
+    }
+
+    async fn read_window(
+        &self,
+        handle: &ReadHandle,
+        start: u64,
+        end: u64,
+    ) -> Result<Bytes, StatusReply> {
+        let mut headers = HeaderMap::new();
+        if let Ok(v) = HeaderValue::from_str(&format!("bytes={start}-{}", end - 1)) {
+            headers.insert(header::RANGE, v);
+        }
+        if let Ok(v) = HeaderValue::from_str(&handle.etag) {
+            headers.insert(header::IF_MATCH, v);
+        }
+        let resp = s3::get_object(
+            State(Arc::clone(&self.state)),
+            Path((handle.location.bucket.clone(), handle.location.key.clone())),
+            self.auth(),
+            headers,
+        )
+        .await;
+        if resp.status() == HttpStatus::PRECONDITION_FAILED {
+            return Err(fail(StatusCode::Failure, "file changed while being read"));
+        }
+        check_response(&resp)?;
+        axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .map_err(|e| fail(StatusCode::Failure, e.to_string()))
+    }
+
+    async fn upload_part(&self, handle: &mut WriteHandle, data: Bytes) -> Result<(), StatusReply> {
+        let upload_id = match &handle.upload_id {
+            Some(id) => id.clone(),
+            None => {
+                let resp = s3::initiate_multipart_upload_internal(
+                    Arc::clone(&self.state),
+                    handle.location.bucket.clone(),
+                    handle.location.key.clone(),
+                    &HeaderMap::new(),
+                )
+                .await;
+                check_response(&resp)?;
+                let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                    .await
+                    .map_err(|e| fail(StatusCode::Failure, e.to_string()))?;
+                let id = xml_field(&String::from_utf8_lossy(&body), "UploadId")
+                    .ok_or_else(|| fail(StatusCode::Failure, "no upload ID returned"))?;
+                handle.upload_id = Some(id.clone());
+                id
+            }
+        };
+        let part_number = u32::try_from(handle.parts.len() + 1).unwrap_or(u32::MAX);
+        let resp = s3::upload_part_internal(
+            Arc::clone(&self.state),
+            handle.location.bucket.clone(),
+            handle.location.key.clone(),
+            upload_id,
+            part_number,
+            HeaderMap::new(),
+            data,
+        )
+        .await;
+        check_response(&resp)?;
+        let etag = header_str(&resp, "etag").unwrap_or_default().to_string();
+        handle.parts.push((part_number, etag));
+        Ok(())
+    }
+
+    /// Make a closed upload visible: one PutObject for small files,
+    /// otherwise upload the tail and complete the multipart upload.
+    async fn finish_write(&self, mut handle: WriteHandle) -> Result<(), StatusReply> {
+        let Some(upload_id) = handle.upload_id.clone() else {
+            return self
+                .put(&handle.location, HeaderMap::new(), Bytes::from(handle.buf))
+                .await;
+        };
+        let result = async {
+            if !handle.buf.is_empty() {
+                let tail = Bytes::from(std::mem::take(&mut handle.buf));
+                self.upload_part(&mut handle, tail).await?;
+            }
+            let parts: String = handle
+                .parts
+                .iter()
+                .map(|(n, etag)| {
+                    format!("<Part><PartNumber>{n}</PartNumber><ETag>{etag}</ETag></Part>")
+                })
+                .collect();
+            let resp = s3::complete_multipart_upload_internal(
+                Arc::clone(&self.state),
+                handle.location.bucket.clone(),
+                handle.location.key.clone(),
+                upload_id.clone(),
+                Bytes::from(format!(
+                    "<CompleteMultipartUpload>{parts}</CompleteMultipartUpload>"
+                )),
+            )
+            .await;
+            check_response(&resp)
+        }
+        .await;
+        if result.is_err() {
+            let _ = s3::abort_multipart_upload_internal(
+                Arc::clone(&self.state),
+                handle.location.bucket.clone(),
+                handle.location.key.clone(),
+                upload_id,
+            )
+            .await;
+        }
+        result
+    }
+}
+
+impl russh_sftp::server::Handler for SftpSession {
+    type Error = StatusReply;
+
+    fn unimplemented(&self) -> Self::Error {
+        StatusCode::OpUnsupported.into()
+    }
+
+    async fn realpath(&mut self, id: u32, path: String) -> Result<Name, Self::Error> {
+        let path = format!("/{}", path_components(&path).join("/"));
+        Ok(Name {
+            id,
+            files: vec![File::dummy(path)],
+        })
+    }
+
+    async fn stat(&mut self, id: u32, path: String) -> Result<Attrs, Self::Error> {
+        let attrs = self.attrs(&path).await?;
+        Ok(Attrs { id, attrs })
+    }
+
+    async fn lstat(&mut self, id: u32, path: String) -> Result<Attrs, Self::Error> {
+        self.stat(id, path).await
+    }
+
+    async fn fstat(&mut self, id: u32, handle: String) -> Result<Attrs, Self::Error> {
+        let attrs = match self.handles.get(&handle) {
+            Some(OpenHandle::Read(h)) => file_attrs(h.size, 0),
+            Some(OpenHandle::Write(h)) => file_attrs(h.written, 0),
+            Some(OpenHandle::Dir(_)) => dir_attrs(0),
+            None => return Err(StatusCode::Failure.into()),
+        };
+        Ok(Attrs { id, attrs })
+    }
+
+    // Objects have no mode bits or settable times; accept and ignore so
+    // clients that preserve attributes after an upload don't fail.
+    async fn setstat(
+        &mut self,
+        id: u32,
+        _path: String,
+        _attrs: FileAttributes,
+    ) -> Result<Status, Self::Error> {
+        Ok(ok_status(id))
+    }
+
+    async fn fsetstat(
+        &mut self,
+        id: u32,
+        _handle: String,
+        _attrs: FileAttributes,
+    ) -> Result<Status, Self::Error> {
+        Ok(ok_status(id))
+    }
+
+    async fn opendir(&mut self, id: u32, path: String) -> Result<Handle, Self::Error> {
+        let location = self.root.locate(&path);
+        if !self.attrs(&path).await?.is_dir() {
+            return Err(fail(StatusCode::Failure, "not a directory"));
+        }
+        let handle = self.add_handle(OpenHandle::Dir(DirHandle {
+            location,
+            continuation: None,
+            done: false,
+        }));
+        Ok(Handle { id, handle })
+    }
+
+    async fn readdir(&mut self, id: u32, handle: String) -> Result<Name, Self::Error> {
+        let Some(OpenHandle::Dir(dir)) = self.handles.get(&handle) else {
+            return Err(StatusCode::Failure.into());
+        };
+        if dir.done {
+            return Err(StatusCode::Eof.into());
+        }
+        let (files, continuation) = match dir.location.clone() {
+            None => {
+                let mut client = self.state.meta_client.clone();
+                let buckets = client
+                    .list_buckets(ListBucketsRequest {
+                        owner: String::new(),
+                        tenant: self.auth.tenant.clone(),
+                    })
+                    .await
+                    .map_err(|e| fail(StatusCode::Failure, e.message().to_string()))?
+                    .into_inner()
+                    .buckets;
+                let files = buckets
+                    .into_iter()
+                    .map(|b| File::new(b.name, dir_attrs(b.created_at)))
+                    .collect::<Vec<_>>();
+                (files, None)
+            }
+            Some(loc) => {
+                let prefix = loc.dir_prefix();
+                let page = self
+                    .list(&loc, &prefix, "/", dir.continuation.clone(), LIST_PAGE)
+                    .await?;
+                let dirs = page.common_prefixes.iter().filter_map(|p| {
+                    let name = p.strip_prefix(&prefix)?.trim_end_matches('/');
+                    (!name.is_empty()).then(|| File::new(name, dir_attrs(0)))
+                });
+                let files = page.entries.iter().filter_map(|e| {
+                    let name = e.key.strip_prefix(&prefix)?;
+                    (!name.is_empty() && !e.is_delete_marker)
+                        .then(|| File::new(name, file_attrs(e.size, e.modified_at)))
+                });
+                let continuation = (page.is_truncated && !page.next_continuation_token.is_empty())
+                    .then_some(page.next_continuation_token.clone());
+                (dirs.chain(files).collect(), continuation)
+            }
+        };
+        if let Some(OpenHandle::Dir(dir)) = self.handles.get_mut(&handle) {
+            dir.done = continuation.is_none();
+            dir.continuation = continuation;
+        }
+        if files.is_empty() {
+            return Err(StatusCode::Eof.into());
+        }
+        Ok(Name { id, files })
+    }
+
+    async fn open(
+        &mut self,
+        id: u32,
+        filename: String,
+        pflags: OpenFlags,
+        _attrs: FileAttributes,
+    ) -> Result<Handle, Self::Error> {
+        let location = self.object_location(&filename)?;
+        let writing = pflags.intersects(OpenFlags::WRITE | OpenFlags::CREATE | OpenFlags::TRUNCATE);
+        let handle = if writing {
+            if pflags.contains(OpenFlags::APPEND) {
+                return Err(fail(
+                    StatusCode::OpUnsupported,
+                    "appending to objects is not supported",
+                ));
+            }
+            if pflags.contains(OpenFlags::EXCLUDE)
+                && self.head(&location).await.status().is_success()
+            {
+                return Err(fail(StatusCode::Failure, "file exists"));
+            }
+            // Fail at open rather than after the whole upload
+            self.check_policy("s3:PutObject", &location, true).await?;
+            debug!("SFTP upload: {}/{}", location.bucket, location.key);
+            OpenHandle::Write(WriteHandle {
+                location,
+                upload_id: None,
+                parts: Vec::new(),
+                buf: Vec::new(),
+                written: 0,
+            })
+        } else {
+            let resp = self.head(&location).await;
+            check_response(&resp)?;
+            let (size, etag, _) = head_info(&resp);
+            OpenHandle::Read(ReadHandle {
+                location,
+                size,
+                etag,
+                window_start: 0,
+                window: Bytes::new(),
+            })
+        };
+        let handle = self.add_handle(handle);
+        Ok(Handle { id, handle })
+    }
+
+    async fn read(
+        &mut self,
+        id: u32,
+        handle: String,
+        offset: u64,
+        len: u32,
+    ) -> Result<Data, Self::Error> {
+        let Some(OpenHandle::Read(mut file)) = self.handles.remove(&handle) else {
+            return Err(StatusCode::Failure.into());
+        };
+        let result = async {
+            if offset >= file.size {
+                return Err(StatusCode::Eof.into());
+            }
+            let end = (offset + u64::from(len)).min(file.size);
+            let window_end = file.window_start + file.window.len() as u64;
+            if offset < file.window_start || end > window_end {
+                let fetch_end = (offset + READ_WINDOW.max(u64::from(len))).min(file.size);
+                file.window = self.read_window(&file, offset, fetch_end).await?;
+                file.window_start = offset;
+            }
+            let start = usize::try_from(offset - file.window_start).unwrap_or(usize::MAX);
+            let stop = usize::try_from(end - file.window_start).unwrap_or(usize::MAX);
+            file.window
+                .get(start..stop)
+                .map(<[u8]>::to_vec)
+                .ok_or_else(|| fail(StatusCode::Failure, "short read"))
+        }
+        .await;
+        self.handles.insert(handle, OpenHandle::Read(file));
+        Ok(Data { id, data: result? })
+    }
+
+    async fn write(
+        &mut self,
+        id: u32,
+        handle: String,
+        offset: u64,
+        data: Vec<u8>,
+    ) -> Result<Status, Self::Error> {
+        let Some(OpenHandle::Write(mut file)) = self.handles.remove(&handle) else {
+            return Err(StatusCode::Failure.into());
+        };
+        let result = async {
+            if offset != file.written {
+                return Err(fail(
+                    StatusCode::OpUnsupported,
+                    "only sequential writes are supported",
+                ));
+            }
+            file.written += data.len() as u64;
+            file.buf.extend_from_slice(&data);
+            while file.buf.len() >= PART_SIZE {
+                let part = Bytes::from(file.buf.drain(..PART_SIZE).collect::<Vec<u8>>());
+                self.upload_part(&mut file, part).await?;
+            }
+            Ok(())
+        }
+        .await;
+        self.handles.insert(handle, OpenHandle::Write(file));
+        result.map(|()| ok_status(id))
+    }
+
+    async fn close(&mut self, id: u32, handle: String) -> Result<Status, Self::Error> {
+        if let Some(OpenHandle::Write(file)) = self.handles.remove(&handle) {
+            let location = file.location.clone();
+            let written = file.written;
+            self.finish_write(file).await?;
+            info!(
+                "SFTP upload complete: {}/{} ({} bytes) by {}",
+                location.bucket, location.key, written, self.auth.user_arn
+            );
+        }
+        Ok(ok_status(id))
+    }
+
+    async fn remove(&mut self, id: u32, filename: String) -> Result<Status, Self::Error> {
+        let location = self.object_location(&filename)?;
+        self.delete(&location).await?;
+        Ok(ok_status(id))
+    }
+
+    async fn mkdir(
+        &mut self,
+        id: u32,
+        path: String,
+        _attrs: FileAttributes,
+    ) -> Result<Status, Self::Error> {
+        let location = self.object_location(&path)?;
+        let marker = Location {
+            key: location.dir_prefix(),
+            ..location
+        };
+        self.put(&marker, HeaderMap::new(), Bytes::new()).await?;
+        Ok(ok_status(id))
+    }
+
+    async fn rmdir(&mut self, id: u32, path: String) -> Result<Status, Self::Error> {
+        let location = self.object_location(&path)?;
+        let prefix = location.dir_prefix();
+        let page = self.list(&location, &prefix, "", None, 2).await?;
+        if page.entries.iter().any(|e| e.key != prefix) {
+            return Err(fail(StatusCode::Failure, "directory not empty"));
+        }
+        self.delete(&Location {
+            key: prefix,
+            ..location
+        })
+        .await?;
+        Ok(ok_status(id))
+    }
+
+    async fn rename(
+        &mut self,
+        id: u32,
+        oldpath: String,
+        newpath: String,
+    ) -> Result<Status, Self::Error> {
+        let from = self.object_location(&oldpath)?;
+        let to = self.object_location(&newpath)?;
+        if !self.head(&from).await.status().is_success() {
+            return Err(fail(StatusCode::OpUnsupported, "only files can be renamed"));
+        }
+        let source = format!(
+            "/{}/{}",
+            from.bucket,
+            crate::key_encoding::encode_key(from.key.clone(), true)
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-amz-copy-source",
+            HeaderValue::from_str(&source).map_err(|e| fail(StatusCode::Failure, e.to_string()))?,
+        );
+        self.put(&to, headers, Bytes::new()).await?;
+        self.delete(&from).await?;
+        Ok(ok_status(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_home() {
+        assert_eq!(
+            "AKIAPARTNER=inbound/acme/".parse::<SftpHome>(),
+            Ok(SftpHome {
+                user: "AKIAPARTNER".to_string(),
+                bucket: "inbound".to_string(),
+                prefix: "acme/".to_string(),
+            })
+        );
+        assert_eq!("u=drop".parse::<SftpHome>().unwrap().prefix, "");
+        assert!("u=".parse::<SftpHome>().is_err());
+        assert!("inbound/acme".parse::<SftpHome>().is_err());
+    }
+
+    #[test]
+    fn test_locate_paths() {
+        let loc = |bucket: &str, key: &str| {
+            Some(Location {
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+            })
+        };
+
+        let buckets = Root::Buckets;
+        assert_eq!(buckets.locate("/"), None);
+        assert_eq!(buckets.locate("."), None);
+        assert_eq!(buckets.locate("/logs"), loc("logs", ""));
+        assert_eq!(
+            buckets.locate("logs/2024/./a.csv"),
+            loc("logs", "2024/a.csv")
+        );
+        assert_eq!(buckets.locate("/logs/2024/../b.csv"), loc("logs", "b.csv"));
+
+        let home = Root::Home {
+            bucket: "inbound".to_string(),
+            prefix: "acme/".to_string(),
+        };
+        assert_eq!(home.locate("/"), loc("inbound", "acme"));
+        assert_eq!(
+            home.locate("/orders/1.xml"),
+            loc("inbound", "acme/orders/1.xml")
+        );
+        // `..` can't climb out of the home prefix
+        assert_eq!(home.locate("/../../other"), loc("inbound", "acme/other"));
+        assert_eq!(home.locate("/").unwrap().dir_prefix(), "acme/");
+    }
+}
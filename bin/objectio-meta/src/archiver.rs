@@ -0,0 +1,199 @@
+//! Off-node metadata archiver.
+//!
+//! Periodically captures a [`MetaArchive`] of the meta state-machine
+//! tables and uploads it to an S3 bucket — either on this cluster or,
+//! preferably, on a remote one — so total loss of every meta node's
+//! disk is recoverable with `objectio-cli meta restore`. The restore
+//! procedure is documented in `objectio_meta_store::archive`.
+//!
+//! Each upload writes two objects under the configured prefix:
+//!
+//! - `meta-<unix secs>-<raft index>.oioarchive` — the archive itself;
+//! - `latest` — a pointer whose body is the key above, written only
+//!   after the archive PUT succeeded.
+//!
+//! Old archives are never deleted here; put a lifecycle expiration rule
+//! on the archive bucket to bound retention.
+//!
+//! # Config keys (all optional, hot-reloaded every tick)
+//!
+//! - `archive/target` — `s3://bucket/prefix`. Empty disables archiving.
+//! - `archive/endpoint` — S3 endpoint URL. Default `http://localhost:9000`.
+//! - `archive/region` — SigV4 region. Default `us-east-1`.
+//! - `archive/access_key_id`, `archive/secret_access_key` — credentials
+//!   with `s3:PutObject` on the target prefix.
+//! - `archive/interval_seconds` — upload cadence. Default 300.
+//! - `archive/paused` — "true" to skip uploads without clearing config.
+//!
+//! - **Leader-only**: followers hold the same state, so one copy per
+//!   tick is enough. A tick is also skipped when the Raft applied index
+//!   hasn't moved since the last successful upload.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use objectio_meta_store::MetaArchive;
+use objectio_meta_store::archive::{LATEST_POINTER, archive_object_key, join_key, parse_s3_url};
+use tracing::{debug, info, warn};
+
+use crate::service::MetaService;
+
+const DEFAULT_INTERVAL_SECS: u64 = 300;
+const MIN_INTERVAL_SECS: u64 = 30;
+const MAX_INTERVAL_SECS: u64 = 86_400;
+/// Validity of the presigned URLs minted per upload. Only needs to
+/// outlive the PUT itself.
+const PRESIGN_TTL: Duration = Duration::from_mins(15);
+
+/// Unix seconds of the last successful upload (0 = never). Exported on
+/// `/metrics` so a stalled archiver pages someone before it matters.
+pub static LAST_SUCCESS_UNIX: AtomicU64 = AtomicU64::new(0);
+/// Raft index captured by the last successful upload.
+pub static LAST_ARCHIVED_INDEX: AtomicU64 = AtomicU64::new(0);
+/// Failed upload attempts since process start.
+pub static FAILURES_TOTAL: AtomicU64 = AtomicU64::new(0);
+
+/// Per-tick config snapshot.
+#[derive(Clone, Debug)]
+struct Settings {
+    bucket: String,
+    prefix: String,
+    endpoint: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    interval: Duration,
+    paused: bool,
+}
+
+impl Settings {
+    /// `None` when archiving is not configured.
+    fn load(meta: &Arc<MetaService>) -> (Duration, Option<Self>) {
+        let interval = Duration::from_secs(
+            meta.config_parsed::<u64>("archive/interval_seconds", DEFAULT_INTERVAL_SECS)
+                .clamp(MIN_INTERVAL_SECS, MAX_INTERVAL_SECS),
+        );
+        let target = meta.config_str("archive/target", "");
+        if target.is_empty() {
+            return (interval, None);
+        }
+        let Some((bucket, prefix)) = parse_s3_url(&target) else {
+            warn!("archive/target '{target}' is not an s3:// URL; archiving disabled");
+            return (interval, None);
+        };
+        let settings = Self {
+            bucket,
+            prefix,
+            endpoint: meta.config_str("archive/endpoint", "http://localhost:9000"),
+            region: meta.config_str("archive/region", "us-east-1"),
+            access_key_id: meta.config_str("archive/access_key_id", ""),
+            secret_access_key: meta.config_str("archive/secret_access_key", ""),
+            interval,
+            paused: meta
+                .config_str("archive/paused", "false")
+                .eq_ignore_ascii_case("true"),
+        };
+        (interval, Some(settings))
+    }
+}
+
+pub fn spawn(meta: Arc<MetaService>) {
+    tokio::spawn(async move {
+        run(meta).await;
+    });
+    info!("Meta archiver spawned (idle until archive/target is set)");
+}
+
+async fn run(meta: Arc<MetaService>) {
+    let http = match reqwest::Client::builder()
+        .timeout(Duration::from_mins(5))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            warn!("meta archiver: cannot build HTTP client, archiving disabled: {e}");
+            return;
+        }
+    };
+    loop {
+        let (interval, settings) = Settings::load(&meta);
+        tokio::time::sleep(interval).await;
+        let Some(settings) = settings else { continue };
+        if settings.paused || !meta.is_raft_leader() {
+            continue;
+        }
+        if let Err(e) = tick(&meta, &http, &settings).await {
+            FAILURES_TOTAL.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                "meta archive upload to s3://{}/{} failed: {e}",
+                settings.bucket, settings.prefix
+            );
+        }
+    }
+}
+
+async fn tick(meta: &Arc<MetaService>, http: &reqwest::Client, s: &Settings) -> anyhow::Result<()> {
+    let applied = meta
+        .raft_handle()
+        .and_then(|r| r.metrics().borrow().last_applied.map(|l| l.index))
+        .unwrap_or(0);
+    if applied != 0 && applied == LAST_ARCHIVED_INDEX.load(Ordering::Relaxed) {
+        debug!("meta archiver: applied index {applied} unchanged, skipping");
+        return Ok(());
+    }
+    let store = meta
+        .store()
+        .ok_or_else(|| anyhow::anyhow!("meta service has no persistent store"))?;
+
+    // redb iteration is blocking; keep it off the async workers.
+    let (archive, body) = tokio::task::spawn_blocking(move || {
+        let archive = MetaArchive::capture(&store.db(), applied)?;
+        let body = archive.encode()?;
+        Ok::<_, objectio_meta_store::MetaStoreError>((archive, body))
+    })
+    .await??;
+
+    let key = archive_object_key(&s.prefix, &archive);
+    put(http, s, &key, body.clone()).await?;
+    put(
+        http,
+        s,
+        &join_key(&s.prefix, LATEST_POINTER),
+        key.clone().into_bytes(),
+    )
+    .await?;
+
+    LAST_ARCHIVED_INDEX.store(applied, Ordering::Relaxed);
+    LAST_SUCCESS_UNIX.store(archive.created_at, Ordering::Relaxed);
+    info!(
+        "meta archive uploaded: s3://{}/{} ({} tables, {} rows, {} bytes, index {}, next in {}s)",
+        s.bucket,
+        key,
+        archive.tables.len(),
+        archive.row_count(),
+        body.len(),
+        applied,
+        s.interval.as_secs()
+    );
+    Ok(())
+}
+
+async fn put(http: &reqwest::Client, s: &Settings, key: &str, body: Vec<u8>) -> anyhow::Result<()> {
+    let url = objectio_auth::presign::presign_put(
+        &s.endpoint,
+        &s.region,
+        &s.access_key_id,
+        &s.secret_access_key,
+        &s.bucket,
+        key,
+        PRESIGN_TTL,
+    );
+    let resp = http.put(url).body(body).send().await?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        anyhow::bail!("PUT {key} returned {status}: {text}");
+    }
+    Ok(())
+}
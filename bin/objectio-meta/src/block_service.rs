@@ -10,6 +10,7 @@ use objectio_proto::block::{
     AttachVolumeRequest,
     AttachVolumeResponse,
     Attachment,
+    CancelDefragRequest,
     CloneVolumeRequest,
     CloneVolumeResponse,
     // Snapshot operations
@@ -22,12 +23,15 @@ use objectio_proto::block::{
     DeleteSnapshotResponse,
     DeleteVolumeRequest,
     DeleteVolumeResponse,
+    // Defragmentation
+    DefragStatus,
     DetachVolumeRequest,
     DetachVolumeResponse,
     FlushRequest,
     FlushResponse,
     GetClusterMetricsRequest,
     GetClusterMetricsResponse,
+    GetDefragStatusRequest,
     GetIoTraceRequest,
     GetIoTraceResponse,
     // Metrics operations
@@ -54,6 +58,7 @@ use objectio_proto::block::{
     ResizeVolumeResponse,
     Snapshot,
     SnapshotState,
+    StartDefragRequest,
     TargetType,
     TrimRequest,
     TrimResponse,
@@ -1036,6 +1041,36 @@ impl BlockService for BlockMetaService {
         Ok(Response::new(GetVolumeStatsResponse { stats: None }))
     }
 
+    // ============ Defragmentation ============
+
+    async fn start_defrag(
+        &self,
+        _request: Request<StartDefragRequest>,
+    ) -> Result<Response<DefragStatus>, Status> {
+        // Defrag rewrites chunk objects, which only the block gateway does
+        Err(Status::unimplemented(
+            "Defragmentation runs on the block gateway, not the metadata service",
+        ))
+    }
+
+    async fn get_defrag_status(
+        &self,
+        _request: Request<GetDefragStatusRequest>,
+    ) -> Result<Response<DefragStatus>, Status> {
+        Err(Status::unimplemented(
+            "Defragmentation runs on the block gateway, not the metadata service",
+        ))
+    }
+
+    async fn cancel_defrag(
+        &self,
+        _request: Request<CancelDefragRequest>,
+    ) -> Result<Response<DefragStatus>, Status> {
+        Err(Status::unimplemented(
+            "Defragmentation runs on the block gateway, not the metadata service",
+        ))
+    }
+
     // ============ Metrics Operations ============
     // These are typically handled by OSDs or a dedicated metrics aggregator
     // The metadata service provides stubs for completeness
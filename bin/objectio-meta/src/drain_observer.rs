@@ -44,7 +44,7 @@ use crate::service::MetaService;
 const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
 
 /// Per-RPC timeout when talking to an OSD during a sweep.
-const PER_OSD_TIMEOUT: Duration = Duration::from_secs(10);
+pub(crate) const PER_OSD_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Cap the migrator to one shard per Draining OSD per sweep. Low
 /// enough to keep live IO unaffected on a small cluster; Phase 3c
@@ -61,7 +61,7 @@ const SHARDS_PER_SWEEP: usize = 1;
 /// re-spots "drift" every sweep and the migration loop never converges.
 /// Falls back to `fallback_addr` when neither source yields any addresses.
 /// Succeeds if at least one write lands.
-async fn fanout_put_object_meta(
+pub(crate) async fn fanout_put_object_meta(
     meta: &Arc<MetaService>,
     object: &objectio_proto::metadata::ObjectMeta,
     fallback_addr: &str,
@@ -653,7 +653,7 @@ async fn find_affected_objects(
     Ok(resp.into_inner().objects)
 }
 
-async fn open_channel(address: &str) -> anyhow::Result<Channel> {
+pub(crate) async fn open_channel(address: &str) -> anyhow::Result<Channel> {
     let uri = canonical_uri(address);
     let channel = tokio::time::timeout(PER_OSD_TIMEOUT, Channel::from_shared(uri)?.connect())
         .await
@@ -669,7 +669,7 @@ fn canonical_uri(address: &str) -> String {
     }
 }
 
-fn now_unix() -> u64 {
+pub(crate) fn now_unix() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map(|d| d.as_secs())
@@ -0,0 +1,784 @@
+//! EC profile migrator.
+//!
+//! Re-encodes a bucket's existing objects onto the erasure profile of
+//! the pool it was switched to by `StartEcMigration` (4+2 → 8+3,
+//! replication → EC, and so on). The switch itself is atomic with the
+//! job record, so new writes use the target profile from the first
+//! request; this task works through everything written before.
+//!
+//! Per sweep on the Raft leader, for each running job:
+//!
+//!  1. Page the bucket's `OBJECT_LISTINGS` rows from the job cursor.
+//!  2. Fetch each current version's ObjectMeta; skip it when every
+//!     stripe already matches the target profile.
+//!  3. Read and decode each stripe in the encoding it records,
+//!     re-encode under the target profile (re-striping when the target
+//!     stripe is smaller) and write the shards under a fresh shard
+//!     object_id to the target pool's placement.
+//!  4. Re-fetch the ObjectMeta. If the object was overwritten or
+//!     deleted meanwhile, drop the new shards — the new write already
+//!     used the target profile. Otherwise swap the stripes in and fan
+//!     the ObjectMeta out to the new shard hosts, the target placement
+//!     and every host that held the old copy.
+//!  5. Queue the old shards for deletion after a grace period, so GETs
+//!     that fetched the old ObjectMeta just before the swap can finish.
+//!  6. Checkpoint cursor + counters through Raft. A leader failover
+//!     resumes from the last checkpoint; objects redone after a crash
+//!     are skipped as already matching.
+//!
+//! Reads never block on the migration: every stripe records its own
+//! encoding and shard object_id, so a GET decodes whichever layout its
+//! ObjectMeta points at, and `GetPlacement` hands out the source pool's
+//! nodes as `fallback_nodes` for ObjectMeta that hasn't moved yet.
+//!
+//! # Config keys (all optional, hot-reloaded every sweep)
+//!
+//! - `ec_migration/sweep_interval_seconds` — default 10.
+//! - `ec_migration/objects_per_sweep` — per job, default 16, max 1024.
+//! - `ec_migration/gc_grace_seconds` — delay before old shards are
+//!   deleted. Default 300.
+//! - `ec_migration/paused` — "true" halts every job without cancelling.
+//!
+//! # Limits
+//!
+//! - Only the current version of each key is re-encoded. Noncurrent
+//!   versions keep their old encoding and stay readable through the
+//!   source-pool fallback.
+//! - The ObjectMeta swap is read-check-write, like the drain migrator's
+//!   rewrite: an overwrite landing between the re-check and the fan-out
+//!   loses to the migrated copy.
+//! - Pending old-shard deletions are in memory. A leader failover inside
+//!   the grace window leaks those shards (unreferenced, never read).
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use objectio_common::ErasureConfig;
+use objectio_erasure::ErasureCodec;
+use objectio_proto::metadata::metadata_service_server::MetadataService;
+use objectio_proto::metadata::{
+    EcMigrationJob, EcMigrationState, ErasureType, GetBucketRequest, GetPlacementRequest,
+    GetPlacementResponse, NodePlacement, ObjectListingEntry, ObjectMeta, ShardLocation, StripeMeta,
+};
+use objectio_proto::storage::{
+    DeleteShardRequest, GetObjectMetaRequest, ReadShardRequest, ShardId, WriteShardRequest,
+    storage_service_client::StorageServiceClient,
+};
+use prost::Message;
+use tonic::Request;
+use tracing::{debug, info, warn};
+
+use crate::drain_observer::{PER_OSD_TIMEOUT, fanout_put_object_meta, now_unix, open_channel};
+use crate::service::MetaService;
+
+const DEFAULT_SWEEP_SECS: u64 = 10;
+const DEFAULT_OBJECTS_PER_SWEEP: usize = 16;
+const MAX_OBJECTS_PER_SWEEP: usize = 1024;
+const DEFAULT_GC_GRACE_SECS: u64 = 300;
+
+/// Hold off the first sweep of a new job so PUTs that fetched placement
+/// just before the bucket switched pools have registered their listing
+/// rows by the time the scan passes them.
+const START_SETTLE_SECS: u64 = 30;
+
+/// Mirror of the gateway's `MAX_SHARD_SIZE`: one shard must fit in a
+/// 4 MiB storage block.
+const MAX_SHARD_SIZE: usize = 4 * 1024 * 1024 - 4096;
+
+/// One shard on one OSD — a rollback or GC target.
+struct ShardRef {
+    addr: String,
+    shard_id: ShardId,
+}
+
+/// Old shard awaiting deletion once `due` (unix secs) passes.
+struct PendingDelete {
+    due: u64,
+    shard: ShardRef,
+}
+
+enum Outcome {
+    Migrated { bytes: u64 },
+    Skipped,
+}
+
+/// Erasure profile objects are moved onto, read off the target pool's
+/// placement response.
+#[derive(Clone, Copy, Debug)]
+struct Profile {
+    ec_type: ErasureType,
+    k: u32,
+    m: u32,
+    local_parity: u32,
+    global_parity: u32,
+    local_group_size: u32,
+    replicas: u32,
+}
+
+impl Profile {
+    fn from_placement(p: &GetPlacementResponse) -> Self {
+        Self {
+            ec_type: ErasureType::try_from(p.ec_type).unwrap_or(ErasureType::ErasureMds),
+            k: p.ec_k,
+            m: p.ec_m,
+            local_parity: p.ec_local_parity,
+            global_parity: p.ec_global_parity,
+            local_group_size: p.local_group_size,
+            replicas: p.replication_count.max(1),
+        }
+    }
+
+    const fn is_replicated(self) -> bool {
+        matches!(self.ec_type, ErasureType::ErasureReplication)
+    }
+
+    const fn total_shards(self) -> usize {
+        if self.is_replicated() {
+            self.replicas as usize
+        } else {
+            (self.k + self.m) as usize
+        }
+    }
+
+    /// Largest stripe payload whose shards still fit in one block.
+    const fn max_stripe_bytes(self) -> usize {
+        if self.is_replicated() {
+            MAX_SHARD_SIZE
+        } else {
+            MAX_SHARD_SIZE * self.k as usize
+        }
+    }
+
+    /// Whether `stripe` is already encoded under this profile. A
+    /// replicated stripe short of replicas counts as a mismatch, so the
+    /// migration also tops up copies lost at write time.
+    fn matches(self, stripe: &StripeMeta) -> bool {
+        let ec_type = ErasureType::try_from(stripe.ec_type).unwrap_or(ErasureType::ErasureMds);
+        if ec_type != self.ec_type {
+            return false;
+        }
+        match ec_type {
+            ErasureType::ErasureReplication => stripe.shards.len() >= self.replicas as usize,
+            ErasureType::ErasureLrc => {
+                stripe.ec_k == self.k
+                    && stripe.ec_local_parity == self.local_parity
+                    && stripe.ec_global_parity == self.global_parity
+            }
+            ErasureType::ErasureMds => stripe.ec_k == self.k && stripe.ec_m == self.m,
+        }
+    }
+
+    fn encode(self, data: &[u8]) -> anyhow::Result<Vec<Vec<u8>>> {
+        if self.is_replicated() {
+            return Ok(vec![data.to_vec(); self.replicas as usize]);
+        }
+        let config = if self.ec_type == ErasureType::ErasureLrc {
+            ErasureConfig::lrc(
+                self.k as u8,
+                self.local_parity as u8,
+                self.global_parity as u8,
+            )
+        } else {
+            ErasureConfig::new(self.k as u8, self.m as u8)
+        };
+        let codec = ErasureCodec::new(config).map_err(|e| anyhow::anyhow!("codec new: {e}"))?;
+        codec
+            .encode(data)
+            .map_err(|e| anyhow::anyhow!("ec encode: {e}"))
+    }
+}
+
+pub fn spawn(meta: Arc<MetaService>) {
+    tokio::spawn(async move {
+        run(meta).await;
+    });
+    info!("EC migrator spawned (idle until StartEcMigration)");
+}
+
+async fn run(meta: Arc<MetaService>) {
+    let mut pending: Vec<PendingDelete> = Vec::new();
+    loop {
+        let interval = meta
+            .config_parsed::<u64>("ec_migration/sweep_interval_seconds", DEFAULT_SWEEP_SECS)
+            .max(1);
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        if !meta.is_raft_leader() {
+            // Deletions queued under an earlier term belong to whoever
+            // leads now; their shards are unreferenced either way.
+            pending.clear();
+            continue;
+        }
+        collect_garbage(&mut pending).await;
+        if meta
+            .config_str("ec_migration/paused", "false")
+            .eq_ignore_ascii_case("true")
+        {
+            continue;
+        }
+        for job in meta.ec_migrations_snapshot() {
+            if job.state() != EcMigrationState::EcMigrationRunning {
+                continue;
+            }
+            if let Err(e) = sweep_job(&meta, job.clone(), &mut pending).await {
+                warn!("EC migration sweep for bucket '{}' failed: {e}", job.bucket);
+            }
+        }
+    }
+}
+
+/// Process one page of `job`'s bucket and checkpoint the result.
+async fn sweep_job(
+    meta: &Arc<MetaService>,
+    job: EcMigrationJob,
+    pending: &mut Vec<PendingDelete>,
+) -> anyhow::Result<()> {
+    if now_unix() < job.started_at.saturating_add(START_SETTLE_SECS) {
+        return Ok(());
+    }
+    let store = meta
+        .store()
+        .ok_or_else(|| anyhow::anyhow!("meta service has no persistent store"))?;
+    let prev = job.clone();
+    let mut job = job;
+
+    if let Err(status) = meta
+        .get_bucket(Request::new(GetBucketRequest {
+            name: job.bucket.clone(),
+        }))
+        .await
+    {
+        if status.code() != tonic::Code::NotFound {
+            return Err(status.into());
+        }
+        job.set_state(EcMigrationState::EcMigrationCancelled);
+        job.last_error = "bucket deleted".into();
+        job.finished_at = now_unix();
+        job.updated_at = job.finished_at;
+        meta.save_ec_migration(&prev, job).await?;
+        return Ok(());
+    }
+
+    let limit = meta
+        .config_parsed::<usize>("ec_migration/objects_per_sweep", DEFAULT_OBJECTS_PER_SWEEP)
+        .clamp(1, MAX_OBJECTS_PER_SWEEP);
+    let grace = meta.config_parsed::<u64>("ec_migration/gc_grace_seconds", DEFAULT_GC_GRACE_SECS);
+    let (bucket, cursor) = (job.bucket.clone(), job.cursor.clone());
+    // redb iteration is blocking; keep it off the async workers.
+    let (rows, truncated, _) = tokio::task::spawn_blocking(move || {
+        store.list_object_listings(&bucket, "", &cursor, limit)
+    })
+    .await??;
+
+    let bucket_prefix_len = job.bucket.len() + 1;
+    for (full_key, bytes) in rows {
+        job.objects_scanned += 1;
+        let entry = ObjectListingEntry::decode(bytes.as_slice())?;
+        match migrate_object(meta, &job, &entry, grace, pending).await {
+            Ok(Outcome::Migrated { bytes }) => {
+                job.objects_migrated += 1;
+                job.bytes_migrated += bytes;
+                job.last_error.clear();
+            }
+            Ok(Outcome::Skipped) => job.objects_skipped += 1,
+            Err(e) => {
+                job.objects_failed += 1;
+                job.last_error = format!("{}: {e}", entry.key);
+                warn!(
+                    "EC migration of {}/{} failed, left in its old encoding: {e}",
+                    entry.bucket, entry.key
+                );
+            }
+        }
+        // Cursor is the bucket-relative listing key, the form
+        // `list_object_listings` takes back as `start_after`.
+        job.cursor = full_key[bucket_prefix_len..].to_string();
+    }
+
+    job.updated_at = now_unix();
+    if !truncated {
+        job.set_state(EcMigrationState::EcMigrationCompleted);
+        job.finished_at = job.updated_at;
+        info!(
+            "EC migration of bucket '{}' to pool '{}' completed: {} migrated, {} skipped, {} failed",
+            job.bucket,
+            job.target_pool,
+            job.objects_migrated,
+            job.objects_skipped,
+            job.objects_failed
+        );
+    }
+    meta.save_ec_migration(&prev, job).await?;
+    Ok(())
+}
+
+async fn migrate_object(
+    meta: &Arc<MetaService>,
+    job: &EcMigrationJob,
+    entry: &ObjectListingEntry,
+    grace_secs: u64,
+    pending: &mut Vec<PendingDelete>,
+) -> anyhow::Result<Outcome> {
+    if entry.is_delete_marker {
+        return Ok(Outcome::Skipped);
+    }
+    let lookup = meta
+        .get_placement(Request::new(GetPlacementRequest {
+            bucket: entry.bucket.clone(),
+            key: entry.key.clone(),
+            size: 0,
+            storage_class: "STANDARD".to_string(),
+            pool: String::new(),
+        }))
+        .await?
+        .into_inner();
+    let lookup_addrs: Vec<String> = lookup
+        .nodes
+        .iter()
+        .chain(&lookup.fallback_nodes)
+        .map(|n| n.node_address.clone())
+        .collect();
+    let Some((holder, object)) = fetch_object_meta(&lookup_addrs, entry).await? else {
+        return Ok(Outcome::Skipped);
+    };
+    // Noncurrent versions stay put; see the module docs.
+    if object.is_delete_marker
+        || (!entry.version_id.is_empty() && object.version_id != entry.version_id)
+    {
+        return Ok(Outcome::Skipped);
+    }
+
+    let target = meta
+        .get_placement(Request::new(GetPlacementRequest {
+            bucket: entry.bucket.clone(),
+            key: entry.key.clone(),
+            size: object.size,
+            storage_class: "STANDARD".to_string(),
+            pool: job.target_pool.clone(),
+        }))
+        .await?
+        .into_inner();
+    let profile = Profile::from_placement(&target);
+    if object.stripes.iter().all(|s| profile.matches(s)) {
+        return Ok(Outcome::Skipped);
+    }
+    if target.nodes.len() < profile.total_shards() {
+        anyhow::bail!(
+            "target pool '{}' placed {} of {} shards",
+            job.target_pool,
+            target.nodes.len(),
+            profile.total_shards()
+        );
+    }
+
+    let new_object_id = uuid::Uuid::new_v4();
+    let mut written = Vec::new();
+    let stripes = match rewrite_stripes(
+        meta,
+        &object,
+        &target.nodes,
+        profile,
+        new_object_id.as_bytes(),
+        &mut written,
+    )
+    .await
+    {
+        Ok(s) => s,
+        Err(e) => {
+            delete_shards(&written).await;
+            return Err(e);
+        }
+    };
+
+    let fresh = fetch_object_meta(std::slice::from_ref(&holder), entry)
+        .await?
+        .map(|(_, o)| o);
+    let Some(mut updated) = fresh.filter(|f| same_object(&object, f)) else {
+        debug!(
+            "EC migration: {}/{} changed underneath, dropping re-encoded copy",
+            entry.bucket, entry.key
+        );
+        delete_shards(&written).await;
+        return Ok(Outcome::Skipped);
+    };
+    updated.stripes = stripes;
+
+    let mut extra: HashSet<String> = lookup_addrs.into_iter().collect();
+    extra.extend(target.nodes.iter().map(|n| n.node_address.clone()));
+    let mut old_shards = Vec::new();
+    for stripe in &object.stripes {
+        for shard in &stripe.shards {
+            let Some(addr) = node_address(meta, &shard.node_id) else {
+                continue;
+            };
+            extra.insert(addr.clone());
+            old_shards.push(ShardRef {
+                addr,
+                shard_id: ShardId {
+                    object_id: shard_object_id(&object, stripe).to_vec(),
+                    stripe_id: stripe.stripe_id,
+                    position: shard.position,
+                },
+            });
+        }
+    }
+    extra.retain(|a| !a.is_empty());
+    let extra_refs: Vec<&str> = extra.iter().map(String::as_str).collect();
+    if let Err(e) = fanout_put_object_meta(meta, &updated, &holder, &extra_refs).await {
+        delete_shards(&written).await;
+        return Err(e);
+    }
+
+    let due = now_unix().saturating_add(grace_secs);
+    pending.extend(
+        old_shards
+            .into_iter()
+            .map(|shard| PendingDelete { due, shard }),
+    );
+    debug!(
+        "EC migration: {}/{} re-encoded into {} stripes",
+        entry.bucket,
+        entry.key,
+        updated.stripes.len()
+    );
+    Ok(Outcome::Migrated { bytes: object.size })
+}
+
+/// Decode every stripe of `object` and re-encode it under `profile`,
+/// writing shards to `nodes`. Successful writes are appended to
+/// `written` so the caller can roll back on any failure.
+async fn rewrite_stripes(
+    meta: &Arc<MetaService>,
+    object: &ObjectMeta,
+    nodes: &[NodePlacement],
+    profile: Profile,
+    new_object_id: &[u8; 16],
+    written: &mut Vec<ShardRef>,
+) -> anyhow::Result<Vec<StripeMeta>> {
+    let max = profile.max_stripe_bytes();
+    let mut out = Vec::with_capacity(object.stripes.len());
+    for stripe in &object.stripes {
+        let data = read_stripe(meta, object, stripe).await?;
+        // Split oversize stripes at block-aligned offsets so per-stripe
+        // CTR IVs can be carried forward with `iv_at_offset`.
+        let chunks: Vec<&[u8]> = if data.is_empty() {
+            vec![&data[..]]
+        } else {
+            data.chunks(max).collect()
+        };
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let iv = match <[u8; objectio_kms::IV_LEN]>::try_from(stripe.encryption_iv.as_slice()) {
+                Ok(iv) => objectio_kms::iv_at_offset(&iv, (i * max) as u64).to_vec(),
+                // Empty: plaintext, or legacy object-level IV which is
+                // offset-based and unaffected by re-striping.
+                Err(_) => stripe.encryption_iv.clone(),
+            };
+            let stripe_id = out.len() as u64;
+            let shards =
+                write_stripe(nodes, profile, new_object_id, stripe_id, chunk, written).await?;
+            out.push(StripeMeta {
+                stripe_id,
+                ec_k: if profile.is_replicated() {
+                    1
+                } else {
+                    profile.k
+                },
+                ec_m: if profile.is_replicated() {
+                    0
+                } else {
+                    profile.m
+                },
+                shards,
+                ec_type: profile.ec_type.into(),
+                ec_local_parity: profile.local_parity,
+                ec_global_parity: profile.global_parity,
+                local_group_size: profile.local_group_size,
+                data_size: chunk.len() as u64,
+                object_id: new_object_id.to_vec(),
+                encryption_iv: iv,
+            });
+        }
+    }
+    Ok(out)
+}
+
+/// Read enough shards of `stripe` to rebuild its `data_size` bytes,
+/// using the encoding the stripe itself records.
+async fn read_stripe(
+    meta: &Arc<MetaService>,
+    object: &ObjectMeta,
+    stripe: &StripeMeta,
+) -> anyhow::Result<Vec<u8>> {
+    let object_id = shard_object_id(object, stripe);
+    let size = stripe.data_size as usize;
+    let mut positions: Vec<&ShardLocation> = stripe.shards.iter().collect();
+    positions.sort_by_key(|s| s.position);
+
+    let ec_type = ErasureType::try_from(stripe.ec_type).unwrap_or(ErasureType::ErasureMds);
+    if ec_type == ErasureType::ErasureReplication {
+        let mut last_err = anyhow::anyhow!("no replicas recorded");
+        for shard in positions {
+            match read_shard(meta, object_id, stripe.stripe_id, shard).await {
+                Ok(mut data) => {
+                    data.truncate(size);
+                    return Ok(data);
+                }
+                Err(e) => last_err = e,
+            }
+        }
+        return Err(last_err.context(format!("stripe {}: every replica failed", stripe.stripe_id)));
+    }
+
+    let k = stripe.ec_k as usize;
+    let total = k + stripe.ec_m as usize;
+    let mut shards: Vec<Option<Vec<u8>>> = vec![None; total];
+    let mut have = 0usize;
+    // Position order reads data shards first, which lets decode skip
+    // the matrix work entirely on a healthy stripe.
+    for shard in positions {
+        if have >= k {
+            break;
+        }
+        if shard.position as usize >= total {
+            continue;
+        }
+        match read_shard(meta, object_id, stripe.stripe_id, shard).await {
+            Ok(data) => {
+                shards[shard.position as usize] = Some(data);
+                have += 1;
+            }
+            Err(e) => debug!(
+                "EC migration: shard {} of stripe {} unreadable: {e}",
+                shard.position, stripe.stripe_id
+            ),
+        }
+    }
+    if have < k {
+        anyhow::bail!(
+            "stripe {}: only {have} of {k} shards readable",
+            stripe.stripe_id
+        );
+    }
+    let config = if ec_type == ErasureType::ErasureLrc {
+        ErasureConfig::lrc(
+            stripe.ec_k as u8,
+            stripe.ec_local_parity as u8,
+            stripe.ec_global_parity as u8,
+        )
+    } else {
+        ErasureConfig::new(stripe.ec_k as u8, stripe.ec_m as u8)
+    };
+    let codec = ErasureCodec::new(config).map_err(|e| anyhow::anyhow!("codec new: {e}"))?;
+    codec
+        .decode(&mut shards, size)
+        .map_err(|e| anyhow::anyhow!("stripe {}: ec decode: {e}", stripe.stripe_id))
+}
+
+/// Encode `data` under `profile` and write every shard. All shards must
+/// land — a migration must never leave an object less protected than
+/// it found it.
+async fn write_stripe(
+    nodes: &[NodePlacement],
+    profile: Profile,
+    object_id: &[u8; 16],
+    stripe_id: u64,
+    data: &[u8],
+    written: &mut Vec<ShardRef>,
+) -> anyhow::Result<Vec<ShardLocation>> {
+    let shards = profile.encode(data)?;
+    let (ec_k, ec_m) = if profile.is_replicated() {
+        (1, 0)
+    } else {
+        (profile.k, profile.m)
+    };
+    let futs = shards
+        .into_iter()
+        .zip(nodes)
+        .enumerate()
+        .map(|(pos, (bytes, node))| {
+            let shard_id = ShardId {
+                object_id: object_id.to_vec(),
+                stripe_id,
+                position: pos as u32,
+            };
+            async move {
+                let mut client = StorageServiceClient::new(open_channel(&node.node_address).await?);
+                let resp = tokio::time::timeout(
+                    PER_OSD_TIMEOUT,
+                    client.write_shard(WriteShardRequest {
+                        shard_id: Some(shard_id.clone()),
+                        data: bytes,
+                        ec_k,
+                        ec_m,
+                        checksum: None,
+                    }),
+                )
+                .await
+                .map_err(|_| anyhow::anyhow!("write_shard timeout on {}", node.node_address))??
+                .into_inner();
+                Ok::<_, anyhow::Error>((node, shard_id, resp.location.unwrap_or_default()))
+            }
+        });
+
+    let mut locations = Vec::with_capacity(nodes.len());
+    let mut first_err = None;
+    for result in futures::future::join_all(futs).await {
+        match result {
+            Ok((node, shard_id, location)) => {
+                written.push(ShardRef {
+                    addr: node.node_address.clone(),
+                    shard_id: shard_id.clone(),
+                });
+                locations.push(ShardLocation {
+                    position: shard_id.position,
+                    node_id: if location.node_id.is_empty() {
+                        node.node_id.clone()
+                    } else {
+                        location.node_id
+                    },
+                    disk_id: location.disk_id,
+                    offset: location.offset,
+                    shard_type: node.shard_type,
+                    local_group: node.local_group,
+                });
+            }
+            Err(e) => {
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    if let Some(e) = first_err {
+        return Err(e.context(format!("stripe {stripe_id}: shard write failed")));
+    }
+    locations.sort_by_key(|l| l.position);
+    Ok(locations)
+}
+
+async fn read_shard(
+    meta: &Arc<MetaService>,
+    object_id: &[u8],
+    stripe_id: u64,
+    shard: &ShardLocation,
+) -> anyhow::Result<Vec<u8>> {
+    let addr = node_address(meta, &shard.node_id).ok_or_else(|| {
+        anyhow::anyhow!("shard host {} not registered", hex::encode(&shard.node_id))
+    })?;
+    let mut client = StorageServiceClient::new(open_channel(&addr).await?);
+    let resp = tokio::time::timeout(
+        PER_OSD_TIMEOUT,
+        client.read_shard(ReadShardRequest {
+            shard_id: Some(ShardId {
+                object_id: object_id.to_vec(),
+                stripe_id,
+                position: shard.position,
+            }),
+            offset: 0,
+            length: 0,
+        }),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("read_shard timeout on {addr}"))??;
+    Ok(resp.into_inner().data)
+}
+
+/// First ObjectMeta found for the entry's key among `addrs`, with the
+/// address that returned it.
+async fn fetch_object_meta(
+    addrs: &[String],
+    entry: &ObjectListingEntry,
+) -> anyhow::Result<Option<(String, ObjectMeta)>> {
+    let mut last_err = None;
+    let mut seen = HashSet::new();
+    for addr in addrs {
+        if addr.is_empty() || !seen.insert(addr) {
+            continue;
+        }
+        let attempt = async {
+            let mut client = StorageServiceClient::new(open_channel(addr).await?);
+            let resp = tokio::time::timeout(
+                PER_OSD_TIMEOUT,
+                client.get_object_meta(GetObjectMetaRequest {
+                    bucket: entry.bucket.clone(),
+                    key: entry.key.clone(),
+                    version_id: String::new(),
+                }),
+            )
+            .await
+            .map_err(|_| anyhow::anyhow!("get_object_meta timeout on {addr}"))??;
+            Ok::<_, anyhow::Error>(resp.into_inner())
+        };
+        match attempt.await {
+            Ok(resp) if resp.found => {
+                return Ok(resp.object.map(|o| (addr.clone(), o)));
+            }
+            Ok(_) => {}
+            Err(e) => last_err = Some(e),
+        }
+    }
+    // Every reachable host said "not found": the object is gone. Only
+    // fail when nobody answered at all.
+    match last_err {
+        Some(e) if seen.len() == 1 => Err(e),
+        _ => Ok(None),
+    }
+}
+
+async fn delete_shards(shards: &[ShardRef]) {
+    for shard in shards {
+        if let Err(e) = delete_shard(shard).await {
+            warn!(
+                "EC migration: failed to delete shard on {}: {e}",
+                shard.addr
+            );
+        }
+    }
+}
+
+async fn delete_shard(shard: &ShardRef) -> anyhow::Result<()> {
+    let mut client = StorageServiceClient::new(open_channel(&shard.addr).await?);
+    tokio::time::timeout(
+        PER_OSD_TIMEOUT,
+        client.delete_shard(DeleteShardRequest {
+            shard_id: Some(shard.shard_id.clone()),
+        }),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("delete_shard timeout"))??;
+    Ok(())
+}
+
+/// Delete old shards whose grace period has elapsed.
+async fn collect_garbage(pending: &mut Vec<PendingDelete>) {
+    let now = now_unix();
+    let (due, rest): (Vec<_>, Vec<_>) = pending.drain(..).partition(|p| p.due <= now);
+    *pending = rest;
+    let shards: Vec<ShardRef> = due.into_iter().map(|p| p.shard).collect();
+    if !shards.is_empty() {
+        debug!("EC migration: deleting {} superseded shards", shards.len());
+        delete_shards(&shards).await;
+    }
+}
+
+fn node_address(meta: &MetaService, node_id: &[u8]) -> Option<String> {
+    let id = <[u8; 16]>::try_from(node_id).ok()?;
+    meta.osd_address_by_id(&id)
+}
+
+/// Shard object_id for `stripe`: its own when set (multipart parts,
+/// migrated objects), else the object's.
+fn shard_object_id<'a>(object: &'a ObjectMeta, stripe: &'a StripeMeta) -> &'a [u8] {
+    if stripe.object_id.is_empty() {
+        &object.object_id
+    } else {
+        &stripe.object_id
+    }
+}
+
+/// Whether `fresh` is still the object version that was re-encoded.
+fn same_object(read: &ObjectMeta, fresh: &ObjectMeta) -> bool {
+    read.object_id == fresh.object_id
+        && read.etag == fresh.etag
+        && read.version_id == fresh.version_id
+        && read.modified_at == fresh.modified_at
+        && !fresh.is_delete_marker
+}
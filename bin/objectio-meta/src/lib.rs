@@ -4,9 +4,11 @@
 //! subscriber setup. The same `run()` is re-used by
 //! `bin/objectio-aio` to compose meta into a single-process monolith.
 
+pub mod archiver;
 pub mod balancer;
 pub mod block_service;
 pub mod drain_observer;
+pub mod ec_migration;
 pub mod raft_admin;
 pub mod raft_rpc;
 pub mod service;
@@ -293,6 +295,12 @@ pub async fn run(
     // tick. Currently observational (Phase 4a); execution lands with
     // the Phase 5 migration path.
     balancer::spawn(meta_service.clone());
+    // Off-node DR archiver — leader-only, idle until `archive/target`
+    // is set in the config table.
+    archiver::spawn(meta_service.clone());
+    // EC profile migrator — leader-only, idle until a bucket has a
+    // running StartEcMigration job.
+    ec_migration::spawn(meta_service.clone());
     info!(
         "Raft node id={} advertise={} (call POST /init on :{} to bootstrap)",
         node_id, self_addr, args.admin_port
@@ -400,6 +408,40 @@ async fn metrics_handler(
     writeln!(output, "# TYPE objectio_meta_users_total gauge").unwrap();
     writeln!(output, "objectio_meta_users_total {}", stats.user_count).unwrap();
 
+    // Off-node archive freshness
+    writeln!(
+        output,
+        "# HELP objectio_meta_archive_last_success_timestamp_seconds Unix time of the last successful meta archive upload (0 = never)"
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "# TYPE objectio_meta_archive_last_success_timestamp_seconds gauge"
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "objectio_meta_archive_last_success_timestamp_seconds {}",
+        archiver::LAST_SUCCESS_UNIX.load(std::sync::atomic::Ordering::Relaxed)
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "# HELP objectio_meta_archive_failures_total Failed meta archive uploads"
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "# TYPE objectio_meta_archive_failures_total counter"
+    )
+    .unwrap();
+    writeln!(
+        output,
+        "objectio_meta_archive_failures_total {}",
+        archiver::FAILURES_TOTAL.load(std::sync::atomic::Ordering::Relaxed)
+    )
+    .unwrap();
+
     // Get block service stats
     let block_stats = state.block_service.stats();
 
@@ -24,9 +24,12 @@ use objectio_proto::metadata::{
     BucketMeta,
     // Bucket SSE types
     BucketSseConfiguration,
+    CancelEcMigrationRequest,
+    CancelEcMigrationResponse,
     CompleteMultipartUploadRequest,
     CompleteMultipartUploadResponse,
     // Config types
+    ComponentVersion,
     ConfigEntry,
     CreateAccessKeyRequest,
     CreateAccessKeyResponse,
@@ -110,9 +113,13 @@ use objectio_proto::metadata::{
     DetachPolicyRequest,
     DetachPolicyResponse,
     DrainStatus as ProtoDrainStatus,
+    EcMigrationJob,
+    EcMigrationState,
     ErasureType,
     GetAccessKeyForAuthRequest,
     GetAccessKeyForAuthResponse,
+    GetBucketAclRequest,
+    GetBucketAclResponse,
     GetBucketEncryptionRequest,
     GetBucketEncryptionResponse,
     GetBucketLifecycleRequest,
@@ -129,6 +136,8 @@ use objectio_proto::metadata::{
     GetDataFiltersForPrincipalRequest,
     GetDrainStatusRequest,
     GetDrainStatusResponse,
+    GetEcMigrationStatusRequest,
+    GetEcMigrationStatusResponse,
     GetKmsKeyRequest,
     GetKmsKeyResponse,
     GetListingNodesRequest,
@@ -156,6 +165,8 @@ use objectio_proto::metadata::{
     GetUserGroupsResponse,
     GetUserRequest,
     GetUserResponse,
+    GetVersionInfoRequest,
+    GetVersionInfoResponse,
     GroupMeta,
     // Iceberg types
     IcebergCommitTableRequest,
@@ -244,6 +255,8 @@ use objectio_proto::metadata::{
     PlacementGroup,
     PolicyObject,
     PoolConfig,
+    PutBucketAclRequest,
+    PutBucketAclResponse,
     PutBucketEncryptionRequest,
     PutBucketEncryptionResponse,
     PutBucketLifecycleRequest,
@@ -265,6 +278,8 @@ use objectio_proto::metadata::{
     SetOsdAdminStateRequest,
     SetOsdAdminStateResponse,
     ShardType,
+    StartEcMigrationRequest,
+    StartEcMigrationResponse,
     TenantConfig,
     // Unity Catalog types
     UnityCatalog,
@@ -621,6 +636,10 @@ pub struct MetaService {
     /// KMS keys (material already wrapped by gateway's service master key):
     /// key_id -> KmsKey
     kms_keys: RwLock<HashMap<String, KmsKey>>,
+    /// EC profile migration jobs: bucket_name -> EcMigrationJob. Kept
+    /// live on every replica by the apply-listener — GetPlacement reads
+    /// it on the hot path to fill `fallback_nodes`.
+    ec_migrations: RwLock<HashMap<String, EcMigrationJob>>,
     /// Active license — drives node-count + raw-capacity caps enforced on
     /// `register_osd`. Default is Community (`0`/`0`, i.e. unlimited) so an
     /// unconfigured meta never refuses registrations. Reload happens via
@@ -800,6 +819,7 @@ impl MetaService {
             lifecycle_configs: RwLock::new(HashMap::new()),
             bucket_encryption_configs: RwLock::new(HashMap::new()),
             kms_keys: RwLock::new(HashMap::new()),
+            ec_migrations: RwLock::new(HashMap::new()),
             drain_statuses: RwLock::new(HashMap::new()),
             rebalance_progress: RwLock::new(RebalanceProgress::default()),
             license: RwLock::new(Arc::new(objectio_license::License::community())),
@@ -909,6 +929,9 @@ impl MetaService {
                         CasTable::Config => {
                             svc.apply_config_event(&key, new_value.as_deref());
                         }
+                        CasTable::Named(ref name) if name == "ec_migrations" => {
+                            svc.apply_ec_migration_event(&key, new_value.as_deref());
+                        }
                         // Tables not yet covered by a cache refresh:
                         // writers are responsible for mirroring their
                         // own writes on the leader, and followers still
@@ -944,6 +967,22 @@ impl MetaService {
         }
     }
 
+    fn apply_ec_migration_event(&self, key: &str, new_value: Option<&[u8]>) {
+        use prost::Message;
+        let mut m = self.ec_migrations.write();
+        match new_value {
+            Some(bytes) => match EcMigrationJob::decode(bytes) {
+                Ok(job) => {
+                    m.insert(key.to_string(), job);
+                }
+                Err(e) => warn!("apply: decode EcMigrationJob('{key}') failed: {e}"),
+            },
+            None => {
+                m.remove(key);
+            }
+        }
+    }
+
     fn apply_bucket_event(&self, key: &str, new_value: Option<&[u8]>) {
         use prost::Message;
         let mut buckets = self.buckets.write();
@@ -1428,6 +1467,94 @@ impl MetaService {
         f(&mut p);
     }
 
+    /// Source pool of the bucket's EC migration, if one is on record.
+    /// Completed jobs still count: noncurrent versions are never
+    /// re-encoded, so their ObjectMeta stays on the source placement.
+    pub fn ec_migration_source_pool(&self, bucket: &str) -> Option<String> {
+        self.ec_migrations
+            .read()
+            .get(bucket)
+            .map(|j| j.source_pool.clone())
+    }
+
+    /// Clone-snapshot of every EC migration job, ordered by bucket.
+    pub fn ec_migrations_snapshot(&self) -> Vec<EcMigrationJob> {
+        let mut jobs: Vec<EcMigrationJob> = self.ec_migrations.read().values().cloned().collect();
+        jobs.sort_by(|a, b| a.bucket.cmp(&b.bucket));
+        jobs
+    }
+
+    /// Persist a job the migrator has advanced. CAS against `prev`, so
+    /// an operator Cancel that landed mid-batch wins and the migrator
+    /// picks up the new state on its next sweep.
+    pub async fn save_ec_migration(
+        &self,
+        prev: &EcMigrationJob,
+        job: EcMigrationJob,
+    ) -> Result<(), Status> {
+        self.write_ec_migration(None, Some(prev), job, "ec-migration-checkpoint")
+            .await
+    }
+
+    /// Write `job` (and optionally the bucket row that goes with it) in
+    /// one Raft entry, then mirror both into the local caches.
+    async fn write_ec_migration(
+        &self,
+        bucket: Option<(&BucketMeta, BucketMeta)>,
+        prev: Option<&EcMigrationJob>,
+        job: EcMigrationJob,
+        requested_by: &str,
+    ) -> Result<(), Status> {
+        if let Some(raft) = self.raft_handle() {
+            use objectio_meta_store::{CasOp, CasTable, MetaCommand, MetaResponse};
+            let mut ops = Vec::with_capacity(2);
+            if let Some((old, new)) = &bucket {
+                ops.push(CasOp {
+                    table: CasTable::Buckets,
+                    key: new.name.clone(),
+                    expected: Some(old.encode_to_vec()),
+                    new_value: Some(new.encode_to_vec()),
+                });
+            }
+            ops.push(CasOp {
+                table: CasTable::Named("ec_migrations".into()),
+                key: job.bucket.clone(),
+                expected: prev.map(Message::encode_to_vec),
+                new_value: Some(job.encode_to_vec()),
+            });
+            let cmd = MetaCommand::MultiCas {
+                ops,
+                requested_by: requested_by.to_string(),
+            };
+            match raft.client_write(cmd).await {
+                Ok(r) => match r.data {
+                    MetaResponse::MultiCasOk => {}
+                    MetaResponse::MultiCasConflict { .. } => {
+                        return Err(Status::aborted(
+                            "bucket or EC migration changed since read; retry",
+                        ));
+                    }
+                    other => {
+                        error!("unexpected raft response for {requested_by}: {:?}", other);
+                        return Err(Status::internal("raft commit wrong variant"));
+                    }
+                },
+                Err(e) => return Err(raft_write_to_status(&e)),
+            }
+        } else if let Some(store) = &self.store {
+            if let Some((_, new)) = &bucket {
+                store.put_bucket(&new.name, new);
+            }
+            store.put_ec_migration(&job.bucket, &job.encode_to_vec());
+        }
+
+        if let Some((_, new)) = bucket {
+            self.buckets.write().insert(new.name.clone(), new);
+        }
+        self.ec_migrations.write().insert(job.bucket.clone(), job);
+        Ok(())
+    }
+
     /// Fetch a config value as a string, falling back to `default` if
     /// the key is absent, un-UTF-8, or the stored bytes are empty.
     /// Used by background tasks (balancer, drain observer) to hot-read
@@ -2193,6 +2320,21 @@ impl MetaService {
             info!("Loaded {} bucket encryption configs from store", map.len());
         }
 
+        // EC migration jobs
+        {
+            let entries = store.load_all_ec_migrations();
+            let mut map = self.ec_migrations.write();
+            for (bucket, bytes) in entries {
+                match EcMigrationJob::decode(bytes.as_slice()) {
+                    Ok(job) => {
+                        map.insert(bucket, job);
+                    }
+                    Err(e) => error!("Failed to decode EC migration job: {}", e),
+                }
+            }
+            info!("Loaded {} EC migration jobs from store", map.len());
+        }
+
         // KMS keys
         {
             let entries = store.load_all_kms_keys();
@@ -2546,193 +2688,499 @@ impl MetaService {
     }
 
     /// Legacy placement algorithm (fallback when no CRUSH topology)
-    async fn get_placement_legacy(
+    /// Placement for `req` under `pool`, or the bucket's pool when
+    /// `None`. Body of `get_placement`, split out so the EC-migration
+    /// fallback can place the same key under two pools.
+    async fn placement_in_pool(
         &self,
-        req: &GetPlacementRequest,
+        req: GetPlacementRequest,
+        pool: Option<String>,
     ) -> Result<Response<GetPlacementResponse>, Status> {
-        let nodes = self.osd_nodes.read();
-
-        // Determine number of shards and EC type based on config
-        let (total_shards, ec_type, replication_count) = match &self.default_ec {
-            EcConfig::Mds { k, m } => ((*k + *m) as usize, ErasureType::ErasureMds, 0u32),
-            EcConfig::Lrc { k, l, g } => ((*k + *l + *g) as usize, ErasureType::ErasureLrc, 0u32),
-            EcConfig::Replication { count } => (
-                *count as usize,
-                ErasureType::ErasureReplication,
-                *count as u32,
-            ),
+        // Check if we have any nodes in the topology
+        let active_node_count = {
+            let topology = self.topology.read();
+            topology.active_nodes().count()
         };
 
-        if nodes.is_empty() {
-            return Err(Status::unavailable("no storage nodes available"));
+        if active_node_count == 0 {
+            // Fall back to legacy placement if no CRUSH topology
+            return self.get_placement_legacy(&req).await;
         }
 
-        // Collect all available disk placements (node, disk pairs)
-        let mut all_disks: Vec<(&OsdNode, &[u8; 16])> = nodes
-            .iter()
-            .flat_map(|node| node.disk_ids.iter().map(move |disk_id| (node, disk_id)))
-            .collect();
-
-        // Use object key hash for deterministic placement
-        let hash_seed = {
-            let key_bytes = format!("{}/{}", req.bucket, req.key);
-            key_bytes
-                .bytes()
-                .fold(0u64, |acc, b| acc.wrapping_add(b as u64))
+        // Create object ID from bucket/key for deterministic placement
+        let object_id = {
+            let key_str = format!("{}/{}", req.bucket, req.key);
+            let hash = xxhash_rust::xxh64::xxh64(key_str.as_bytes(), 0);
+            let mut bytes = [0u8; 16];
+            bytes[..8].copy_from_slice(&hash.to_le_bytes());
+            bytes[8..16].copy_from_slice(&hash.to_be_bytes());
+            objectio_common::ObjectId::from_uuid(Uuid::from_bytes(bytes))
         };
 
-        // Rotate the disk list based on hash for distribution
-        if !all_disks.is_empty() {
-            let rotation = (hash_seed as usize) % all_disks.len();
-            all_disks.rotate_left(rotation);
-        }
-
-        // Select disks for each shard position, spreading across nodes
-        let mut placements: Vec<NodePlacement> = Vec::with_capacity(total_shards);
-        let mut used_nodes: std::collections::HashSet<[u8; 16]> = std::collections::HashSet::new();
-
-        // First pass: try to use different nodes for each shard
-        for pos in 0..total_shards {
-            if placements.len() >= total_shards {
-                break;
-            }
-
-            let disk_opt = all_disks
-                .iter()
-                .find(|(node, _)| !used_nodes.contains(&node.node_id));
+        // Resolve pool for this bucket — use pool-specific EC config if available
+        let pool_name = pool.unwrap_or_else(|| {
+            let buckets = self.buckets.read();
+            buckets
+                .get(&req.bucket)
+                .map(|b| b.pool.clone())
+                .unwrap_or_default()
+        });
+        let (pool_ec, pool_pg_count) = if !pool_name.is_empty() {
+            self.pools
+                .read()
+                .get(&pool_name)
+                .map(|p| {
+                    (
+                        Some((
+                            p.ec_type(),
+                            p.ec_k,
+                            p.ec_m,
+                            p.ec_local_parity,
+                            p.ec_global_parity,
+                            p.replication_count,
+                        )),
+                        p.pg_count,
+                    )
+                })
+                .unwrap_or((None, 0))
+        } else {
+            (None, 0)
+        };
 
-            if let Some((node, disk_id)) = disk_opt {
-                let pos_u32 = pos as u32;
-                placements.push(NodePlacement {
-                    position: pos_u32,
-                    node_id: node.node_id.to_vec(),
-                    node_address: node.address.clone(),
-                    disk_id: disk_id.to_vec(),
-                    shard_type: if pos_u32 < self.default_ec_k {
-                        ShardType::ShardData.into()
-                    } else {
-                        ShardType::ShardGlobalParity.into()
-                    },
-                    local_group: 0,
-                });
-                used_nodes.insert(node.node_id);
+        // Select placement template based on pool EC config or global default
+        let (
+            template,
+            ec_type,
+            ec_k,
+            ec_local_parity,
+            ec_global_parity,
+            local_group_size,
+            replication_count,
+        ) = if let Some((p_ec_type, p_k, p_m, p_lp, p_gp, p_rep)) = pool_ec {
+            match p_ec_type {
+                ErasureType::ErasureLrc => (
+                    PlacementTemplate::lrc(p_k as u8, p_lp as u8, p_gp as u8),
+                    ErasureType::ErasureLrc,
+                    p_k,
+                    p_lp,
+                    p_gp,
+                    p_k.checked_div(p_lp).unwrap_or(0),
+                    0u32,
+                ),
+                ErasureType::ErasureReplication => (
+                    PlacementTemplate::mds(p_rep as u8, 0),
+                    ErasureType::ErasureReplication,
+                    1u32,
+                    0u32,
+                    0u32,
+                    0u32,
+                    p_rep,
+                ),
+                _ => (
+                    PlacementTemplate::mds(p_k as u8, p_m as u8),
+                    ErasureType::ErasureMds,
+                    p_k,
+                    0u32,
+                    p_m,
+                    0u32,
+                    0u32,
+                ),
             }
-        }
-
-        // Second pass: reuse nodes with different disks if needed
-        if placements.len() < total_shards {
-            for (node, disk_id) in all_disks.iter() {
-                if placements.len() >= total_shards {
-                    break;
-                }
-                let disk_used = placements.iter().any(|p| p.disk_id == disk_id.to_vec());
-                if !disk_used {
-                    let pos = placements.len() as u32;
-                    placements.push(NodePlacement {
-                        position: pos,
-                        node_id: node.node_id.to_vec(),
-                        node_address: node.address.clone(),
-                        disk_id: disk_id.to_vec(),
-                        shard_type: if pos < self.default_ec_k {
-                            ShardType::ShardData.into()
-                        } else {
-                            ShardType::ShardGlobalParity.into()
-                        },
-                        local_group: 0,
-                    });
-                }
+        } else {
+            // Fall back to global default EC config
+            match &self.default_ec {
+                EcConfig::Mds { k, m } => (
+                    PlacementTemplate::mds(*k, *m),
+                    ErasureType::ErasureMds,
+                    *k as u32,
+                    0u32,
+                    *m as u32,
+                    0u32,
+                    0u32,
+                ),
+                EcConfig::Lrc { k, l, g } => (
+                    PlacementTemplate::lrc(*k, *l, *g),
+                    ErasureType::ErasureLrc,
+                    *k as u32,
+                    *l as u32,
+                    *g as u32,
+                    (*k / *l) as u32,
+                    0u32,
+                ),
+                EcConfig::Replication { count } => (
+                    PlacementTemplate::mds(*count, 0),
+                    ErasureType::ErasureReplication,
+                    1u32,
+                    0u32,
+                    0u32,
+                    0u32,
+                    *count as u32,
+                ),
             }
-        }
+        };
 
-        // Third pass: allow disk reuse for single disk mode
-        while placements.len() < total_shards {
-            let idx = placements.len() % all_disks.len().max(1);
-            if let Some((node, disk_id)) = all_disks.get(idx) {
-                let pos = placements.len() as u32;
-                placements.push(NodePlacement {
-                    position: pos,
-                    node_id: node.node_id.to_vec(),
-                    node_address: node.address.clone(),
-                    disk_id: disk_id.to_vec(),
-                    shard_type: if pos < self.default_ec_k {
-                        ShardType::ShardData.into()
-                    } else {
-                        ShardType::ShardGlobalParity.into()
-                    },
-                    local_group: 0,
-                });
-            } else {
-                break;
+        // Placement-group fast path. When the bucket's pool has a
+        // non-zero pg_count we route object_id -> pg_id via jump
+        // consistent hash and read the PG's committed osd_ids in one
+        // in-memory lookup. Falls through to CRUSH2 if the PG row is
+        // missing (pre-allocation still in progress on a fresh pool)
+        // or if the PG's shard count disagrees with the current EC
+        // config (topology mid-reconfigure).
+        if pool_pg_count > 0 && !pool_name.is_empty() {
+            let key_str = format!("{}/{}", req.bucket, req.key);
+            let key_hash = xxhash_rust::xxh64::xxh64(key_str.as_bytes(), 0);
+            let pg_id =
+                objectio_placement::jump_consistent_hash(key_hash, pool_pg_count as i32) as u32;
+            if let Some(pg) = self.placement_group(&pool_name, pg_id) {
+                let expected_shards = match ec_type {
+                    ErasureType::ErasureMds => ec_k as usize + ec_global_parity as usize,
+                    ErasureType::ErasureLrc => {
+                        ec_k as usize + ec_local_parity as usize + ec_global_parity as usize
+                    }
+                    ErasureType::ErasureReplication => replication_count as usize,
+                };
+                if pg.osd_ids.len() == expected_shards && expected_shards > 0 {
+                    let nodes_snap = self.osd_nodes.read();
+                    let placements: Vec<NodePlacement> = pg
+                        .osd_ids
+                        .iter()
+                        .enumerate()
+                        .map(|(pos, osd_bytes)| {
+                            let node = nodes_snap
+                                .iter()
+                                .find(|n| n.node_id.as_slice() == osd_bytes.as_slice());
+                            let (node_address, disk_id) = match node {
+                                Some(n) => (
+                                    n.address.clone(),
+                                    n.disk_ids
+                                        .first()
+                                        .map(|d| d.to_vec())
+                                        .unwrap_or_else(|| vec![0u8; 16]),
+                                ),
+                                None => (String::new(), vec![0u8; 16]),
+                            };
+                            let shard_type = pg_position_shard_type(
+                                ec_type,
+                                pos,
+                                ec_k as usize,
+                                ec_local_parity as usize,
+                                local_group_size as usize,
+                            );
+                            let local_group = pg_position_local_group(
+                                ec_type,
+                                pos,
+                                ec_k as usize,
+                                ec_local_parity as usize,
+                                local_group_size as usize,
+                            );
+                            NodePlacement {
+                                position: pos as u32,
+                                node_id: osd_bytes.clone(),
+                                node_address,
+                                disk_id,
+                                shard_type: shard_type.into(),
+                                local_group,
+                            }
+                        })
+                        .collect();
+                    debug!(
+                        "PG placement for {}/{}: pool={}, pg_id={}, {} shards",
+                        req.bucket,
+                        req.key,
+                        pool_name,
+                        pg_id,
+                        placements.len()
+                    );
+                    return Ok(Response::new(GetPlacementResponse {
+                        storage_class: req.storage_class.clone(),
+                        ec_k,
+                        ec_m: ec_local_parity + ec_global_parity,
+                        nodes: placements,
+                        ec_type: ec_type.into(),
+                        ec_local_parity,
+                        ec_global_parity,
+                        local_group_size,
+                        replication_count,
+                        pg_id,
+                        pg_version: pg.version,
+                        pool: pool_name.clone(),
+                        fallback_nodes: Vec::new(),
+                    }));
+                }
+                warn!(
+                    "PG {}/{}: osd_ids={} doesn't match expected shards={}; falling back to CRUSH",
+                    pool_name,
+                    pg_id,
+                    pg.osd_ids.len(),
+                    expected_shards
+                );
             }
         }
 
+        // Use CRUSH 2.0 for placement
+        let crush = self.crush.read();
+        let hrw_placements = crush.select_placement(&object_id, &template);
+        drop(crush);
+
+        // Convert HRW placements to NodePlacement responses
+        let nodes = self.osd_nodes.read();
+        let placements: Vec<NodePlacement> = hrw_placements
+            .iter()
+            .map(|hrw| {
+                // Find the OSD node by NodeId
+                let node = nodes
+                    .iter()
+                    .find(|n| NodeId::from_bytes(n.node_id) == hrw.node_id);
+
+                let (node_address, disk_id) = match node {
+                    Some(n) => {
+                        let disk = n
+                            .disk_ids
+                            .first()
+                            .map(|d| d.to_vec())
+                            .unwrap_or_else(|| vec![0u8; 16]);
+                        (n.address.clone(), disk)
+                    }
+                    None => {
+                        // Node not found in legacy list, use placeholder
+                        warn!("Node {} not found in OSD list", hrw.node_id);
+                        (String::new(), hrw.node_id.as_bytes().to_vec())
+                    }
+                };
+
+                let shard_type = match hrw.role {
+                    ShardRole::Data => ShardType::ShardData.into(),
+                    ShardRole::LocalParity => ShardType::ShardLocalParity.into(),
+                    ShardRole::GlobalParity => ShardType::ShardGlobalParity.into(),
+                };
+
+                NodePlacement {
+                    position: hrw.position as u32,
+                    node_id: hrw.node_id.as_bytes().to_vec(),
+                    node_address,
+                    disk_id,
+                    shard_type,
+                    local_group: hrw.local_group.unwrap_or(0) as u32,
+                }
+            })
+            .collect();
+
         debug!(
-            "Legacy placement for {}/{}: {} shards across {} nodes",
+            "CRUSH 2.0 placement for {}/{}: {} shards using {:?}",
             req.bucket,
             req.key,
             placements.len(),
-            used_nodes.len()
+            ec_type
         );
 
         Ok(Response::new(GetPlacementResponse {
-            storage_class: "STANDARD".to_string(),
-            ec_k: self.default_ec_k,
-            ec_m: self.default_ec_m,
+            storage_class: req.storage_class.clone(),
+            ec_k,
+            ec_m: ec_local_parity + ec_global_parity,
             nodes: placements,
             ec_type: ec_type.into(),
-            ec_local_parity: 0,
-            ec_global_parity: self.default_ec_m,
-            local_group_size: 0,
+            ec_local_parity,
+            ec_global_parity,
+            local_group_size,
             replication_count,
-            // Legacy path: no PG, pool blank. Phase 3 fills these.
+            // Filled by Phase 3 once the PG lookup replaces
+            // per-object CRUSH. Leaving zeros keeps pre-migration
+            // clients safe (gateway treats 0 as legacy).
             pg_id: 0,
             pg_version: 0,
             pool: String::new(),
+            fallback_nodes: Vec::new(),
         }))
     }
-}
 
-#[tonic::async_trait]
-impl MetadataService for MetaService {
-    async fn create_bucket(
+    async fn get_placement_legacy(
         &self,
-        request: Request<CreateBucketRequest>,
-    ) -> Result<Response<CreateBucketResponse>, Status> {
-        let req = request.into_inner();
+        req: &GetPlacementRequest,
+    ) -> Result<Response<GetPlacementResponse>, Status> {
+        let nodes = self.osd_nodes.read();
 
-        if req.name.is_empty() {
-            return Err(Status::invalid_argument("bucket name is required"));
-        }
+        // Determine number of shards and EC type based on config
+        let (total_shards, ec_type, replication_count) = match &self.default_ec {
+            EcConfig::Mds { k, m } => ((*k + *m) as usize, ErasureType::ErasureMds, 0u32),
+            EcConfig::Lrc { k, l, g } => ((*k + *l + *g) as usize, ErasureType::ErasureLrc, 0u32),
+            EcConfig::Replication { count } => (
+                *count as usize,
+                ErasureType::ErasureReplication,
+                *count as u32,
+            ),
+        };
 
-        // Check if bucket already exists
-        if self.buckets.read().contains_key(&req.name) {
-            return Err(Status::already_exists("bucket already exists"));
+        if nodes.is_empty() {
+            return Err(Status::unavailable("no storage nodes available"));
         }
 
-        // Validate tenant exists if specified
-        let tenant = req.tenant.clone();
-        if !tenant.is_empty() && !self.tenants.read().contains_key(&tenant) {
-            return Err(Status::not_found(format!("tenant '{}' not found", tenant)));
+        // Collect all available disk placements (node, disk pairs)
+        let mut all_disks: Vec<(&OsdNode, &[u8; 16])> = nodes
+            .iter()
+            .flat_map(|node| node.disk_ids.iter().map(move |disk_id| (node, disk_id)))
+            .collect();
+
+        // Use object key hash for deterministic placement
+        let hash_seed = {
+            let key_bytes = format!("{}/{}", req.bucket, req.key);
+            key_bytes
+                .bytes()
+                .fold(0u64, |acc, b| acc.wrapping_add(b as u64))
+        };
+
+        // Rotate the disk list based on hash for distribution
+        if !all_disks.is_empty() {
+            let rotation = (hash_seed as usize) % all_disks.len();
+            all_disks.rotate_left(rotation);
         }
 
-        // Enforce tenant bucket quota
-        if !tenant.is_empty()
-            && let Some(tc) = self.tenants.read().get(&tenant)
-            && tc.quota_buckets > 0
-        {
-            let count = self
-                .buckets
-                .read()
-                .values()
-                .filter(|b| b.tenant == tenant)
-                .count() as u64;
-            if count >= tc.quota_buckets {
-                return Err(Status::resource_exhausted(format!(
-                    "tenant '{}' bucket quota exceeded ({}/{})",
-                    tenant, count, tc.quota_buckets
-                )));
+        // Select disks for each shard position, spreading across nodes
+        let mut placements: Vec<NodePlacement> = Vec::with_capacity(total_shards);
+        let mut used_nodes: std::collections::HashSet<[u8; 16]> = std::collections::HashSet::new();
+
+        // First pass: try to use different nodes for each shard
+        for pos in 0..total_shards {
+            if placements.len() >= total_shards {
+                break;
             }
-        }
+
+            let disk_opt = all_disks
+                .iter()
+                .find(|(node, _)| !used_nodes.contains(&node.node_id));
+
+            if let Some((node, disk_id)) = disk_opt {
+                let pos_u32 = pos as u32;
+                placements.push(NodePlacement {
+                    position: pos_u32,
+                    node_id: node.node_id.to_vec(),
+                    node_address: node.address.clone(),
+                    disk_id: disk_id.to_vec(),
+                    shard_type: if pos_u32 < self.default_ec_k {
+                        ShardType::ShardData.into()
+                    } else {
+                        ShardType::ShardGlobalParity.into()
+                    },
+                    local_group: 0,
+                });
+                used_nodes.insert(node.node_id);
+            }
+        }
+
+        // Second pass: reuse nodes with different disks if needed
+        if placements.len() < total_shards {
+            for (node, disk_id) in all_disks.iter() {
+                if placements.len() >= total_shards {
+                    break;
+                }
+                let disk_used = placements.iter().any(|p| p.disk_id == disk_id.to_vec());
+                if !disk_used {
+                    let pos = placements.len() as u32;
+                    placements.push(NodePlacement {
+                        position: pos,
+                        node_id: node.node_id.to_vec(),
+                        node_address: node.address.clone(),
+                        disk_id: disk_id.to_vec(),
+                        shard_type: if pos < self.default_ec_k {
+                            ShardType::ShardData.into()
+                        } else {
+                            ShardType::ShardGlobalParity.into()
+                        },
+                        local_group: 0,
+                    });
+                }
+            }
+        }
+
+        // Third pass: allow disk reuse for single disk mode
+        while placements.len() < total_shards {
+            let idx = placements.len() % all_disks.len().max(1);
+            if let Some((node, disk_id)) = all_disks.get(idx) {
+                let pos = placements.len() as u32;
+                placements.push(NodePlacement {
+                    position: pos,
+                    node_id: node.node_id.to_vec(),
+                    node_address: node.address.clone(),
+                    disk_id: disk_id.to_vec(),
+                    shard_type: if pos < self.default_ec_k {
+                        ShardType::ShardData.into()
+                    } else {
+                        ShardType::ShardGlobalParity.into()
+                    },
+                    local_group: 0,
+                });
+            } else {
+                break;
+            }
+        }
+
+        debug!(
+            "Legacy placement for {}/{}: {} shards across {} nodes",
+            req.bucket,
+            req.key,
+            placements.len(),
+            used_nodes.len()
+        );
+
+        Ok(Response::new(GetPlacementResponse {
+            storage_class: "STANDARD".to_string(),
+            ec_k: self.default_ec_k,
+            ec_m: self.default_ec_m,
+            nodes: placements,
+            ec_type: ec_type.into(),
+            ec_local_parity: 0,
+            ec_global_parity: self.default_ec_m,
+            local_group_size: 0,
+            replication_count,
+            // Legacy path: no PG, pool blank. Phase 3 fills these.
+            pg_id: 0,
+            pg_version: 0,
+            pool: String::new(),
+            fallback_nodes: Vec::new(),
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl MetadataService for MetaService {
+    async fn create_bucket(
+        &self,
+        request: Request<CreateBucketRequest>,
+    ) -> Result<Response<CreateBucketResponse>, Status> {
+        let req = request.into_inner();
+
+        if req.name.is_empty() {
+            return Err(Status::invalid_argument("bucket name is required"));
+        }
+
+        // Check if bucket already exists
+        if self.buckets.read().contains_key(&req.name) {
+            return Err(Status::already_exists("bucket already exists"));
+        }
+
+        // Validate tenant exists if specified
+        let tenant = req.tenant.clone();
+        if !tenant.is_empty() && !self.tenants.read().contains_key(&tenant) {
+            return Err(Status::not_found(format!("tenant '{}' not found", tenant)));
+        }
+
+        // Enforce tenant bucket quota
+        if !tenant.is_empty()
+            && let Some(tc) = self.tenants.read().get(&tenant)
+            && tc.quota_buckets > 0
+        {
+            let count = self
+                .buckets
+                .read()
+                .values()
+                .filter(|b| b.tenant == tenant)
+                .count() as u64;
+            if count >= tc.quota_buckets {
+                return Err(Status::resource_exhausted(format!(
+                    "tenant '{}' bucket quota exceeded ({}/{})",
+                    tenant, count, tc.quota_buckets
+                )));
+            }
+        }
 
         let bucket = BucketMeta {
             name: req.name.clone(),
@@ -2749,6 +3197,7 @@ impl MetadataService for MetaService {
             quota_bytes: 0,
             quota_objects: 0,
             object_lock: None,
+            acl: req.acl,
         };
 
         // Replicate through Raft so followers see the new bucket at the
@@ -3041,382 +3490,109 @@ impl MetadataService for MetaService {
 
     /// Linearizable listing via a B-tree scan of OBJECT_LISTINGS in
     /// Meta's redb. Replaces the old scatter-gather-then-merge path
-    /// on the gateway. Continuation token is the bucket-relative
-    /// form of the last key returned.
-    async fn list_objects(
-        &self,
-        request: Request<ListObjectsRequest>,
-    ) -> Result<Response<ListObjectsResponse>, Status> {
-        let req = request.into_inner();
-        if req.bucket.is_empty() {
-            return Err(Status::invalid_argument("bucket required"));
-        }
-        let max_keys = if req.max_keys == 0 {
-            1000
-        } else {
-            req.max_keys.min(1000) as usize
-        };
-        let start_after = if !req.continuation_token.is_empty() {
-            req.continuation_token.clone()
-        } else {
-            req.start_after.clone()
-        };
-
-        let Some(store) = &self.store else {
-            // No persistent store = no Raft backend — return empty.
-            return Ok(Response::new(ListObjectsResponse::default()));
-        };
-        let (rows, is_truncated, next_token) = store
-            .list_object_listings(&req.bucket, &req.prefix, &start_after, max_keys)
-            .map_err(|e| {
-                error!("list_object_listings failed: {e}");
-                Status::internal(format!("list failed: {e}"))
-            })?;
-
-        let mut entries = Vec::with_capacity(rows.len());
-        for (_k, bytes) in rows {
-            match <ObjectListingEntry as prost::Message>::decode(bytes.as_slice()) {
-                Ok(e) => entries.push(e),
-                Err(err) => {
-                    warn!("decode ObjectListingEntry failed: {err}");
-                }
-            }
-        }
-
-        // Common prefixes (delimiter handling) — keep the existing
-        // shape the gateway expects. Our store scan returns fully
-        // expanded keys; applying the delimiter here keeps the client
-        // contract stable across the migration.
-        let mut common_prefixes: Vec<String> = Vec::new();
-        if !req.delimiter.is_empty() {
-            use std::collections::BTreeSet;
-            let mut prefixes: BTreeSet<String> = BTreeSet::new();
-            entries.retain(|e| {
-                let key = &e.key;
-                if let Some(tail) = key.strip_prefix(&req.prefix)
-                    && let Some(idx) = tail.find(&req.delimiter)
-                {
-                    let end = req.prefix.len() + idx + req.delimiter.len();
-                    prefixes.insert(key[..end].to_string());
-                    return false;
-                }
-                true
-            });
-            common_prefixes = prefixes.into_iter().collect();
-        }
-
-        let key_count = entries.len() as u32 + common_prefixes.len() as u32;
-        Ok(Response::new(ListObjectsResponse {
-            objects: Vec::new(),
-            common_prefixes,
-            next_continuation_token: next_token,
-            is_truncated,
-            key_count,
-            entries,
-        }))
-    }
-
-    async fn get_placement(
-        &self,
-        request: Request<GetPlacementRequest>,
-    ) -> Result<Response<GetPlacementResponse>, Status> {
-        let req = request.into_inner();
-
-        // Check if we have any nodes in the topology
-        let active_node_count = {
-            let topology = self.topology.read();
-            topology.active_nodes().count()
-        };
-
-        if active_node_count == 0 {
-            // Fall back to legacy placement if no CRUSH topology
-            return self.get_placement_legacy(&req).await;
-        }
-
-        // Create object ID from bucket/key for deterministic placement
-        let object_id = {
-            let key_str = format!("{}/{}", req.bucket, req.key);
-            let hash = xxhash_rust::xxh64::xxh64(key_str.as_bytes(), 0);
-            let mut bytes = [0u8; 16];
-            bytes[..8].copy_from_slice(&hash.to_le_bytes());
-            bytes[8..16].copy_from_slice(&hash.to_be_bytes());
-            objectio_common::ObjectId::from_uuid(Uuid::from_bytes(bytes))
-        };
-
-        // Resolve pool for this bucket — use pool-specific EC config if available
-        let pool_name = {
-            let buckets = self.buckets.read();
-            buckets
-                .get(&req.bucket)
-                .map(|b| b.pool.clone())
-                .unwrap_or_default()
-        };
-        let (pool_ec, pool_pg_count) = if !pool_name.is_empty() {
-            self.pools
-                .read()
-                .get(&pool_name)
-                .map(|p| {
-                    (
-                        Some((
-                            p.ec_type(),
-                            p.ec_k,
-                            p.ec_m,
-                            p.ec_local_parity,
-                            p.ec_global_parity,
-                            p.replication_count,
-                        )),
-                        p.pg_count,
-                    )
-                })
-                .unwrap_or((None, 0))
-        } else {
-            (None, 0)
-        };
-
-        // Select placement template based on pool EC config or global default
-        let (
-            template,
-            ec_type,
-            ec_k,
-            ec_local_parity,
-            ec_global_parity,
-            local_group_size,
-            replication_count,
-        ) = if let Some((p_ec_type, p_k, p_m, p_lp, p_gp, p_rep)) = pool_ec {
-            match p_ec_type {
-                ErasureType::ErasureLrc => (
-                    PlacementTemplate::lrc(p_k as u8, p_lp as u8, p_gp as u8),
-                    ErasureType::ErasureLrc,
-                    p_k,
-                    p_lp,
-                    p_gp,
-                    if p_lp > 0 { p_k / p_lp } else { 0 },
-                    0u32,
-                ),
-                ErasureType::ErasureReplication => (
-                    PlacementTemplate::mds(p_rep as u8, 0),
-                    ErasureType::ErasureReplication,
-                    1u32,
-                    0u32,
-                    0u32,
-                    0u32,
-                    p_rep,
-                ),
-                _ => (
-                    PlacementTemplate::mds(p_k as u8, p_m as u8),
-                    ErasureType::ErasureMds,
-                    p_k,
-                    0u32,
-                    p_m,
-                    0u32,
-                    0u32,
-                ),
-            }
-        } else {
-            // Fall back to global default EC config
-            match &self.default_ec {
-                EcConfig::Mds { k, m } => (
-                    PlacementTemplate::mds(*k, *m),
-                    ErasureType::ErasureMds,
-                    *k as u32,
-                    0u32,
-                    *m as u32,
-                    0u32,
-                    0u32,
-                ),
-                EcConfig::Lrc { k, l, g } => (
-                    PlacementTemplate::lrc(*k, *l, *g),
-                    ErasureType::ErasureLrc,
-                    *k as u32,
-                    *l as u32,
-                    *g as u32,
-                    (*k / *l) as u32,
-                    0u32,
-                ),
-                EcConfig::Replication { count } => (
-                    PlacementTemplate::mds(*count, 0),
-                    ErasureType::ErasureReplication,
-                    1u32,
-                    0u32,
-                    0u32,
-                    0u32,
-                    *count as u32,
-                ),
-            }
-        };
-
-        // Placement-group fast path. When the bucket's pool has a
-        // non-zero pg_count we route object_id -> pg_id via jump
-        // consistent hash and read the PG's committed osd_ids in one
-        // in-memory lookup. Falls through to CRUSH2 if the PG row is
-        // missing (pre-allocation still in progress on a fresh pool)
-        // or if the PG's shard count disagrees with the current EC
-        // config (topology mid-reconfigure).
-        if pool_pg_count > 0 && !pool_name.is_empty() {
-            let key_str = format!("{}/{}", req.bucket, req.key);
-            let key_hash = xxhash_rust::xxh64::xxh64(key_str.as_bytes(), 0);
-            let pg_id =
-                objectio_placement::jump_consistent_hash(key_hash, pool_pg_count as i32) as u32;
-            if let Some(pg) = self.placement_group(&pool_name, pg_id) {
-                let expected_shards = match ec_type {
-                    ErasureType::ErasureMds => ec_k as usize + ec_global_parity as usize,
-                    ErasureType::ErasureLrc => {
-                        ec_k as usize + ec_local_parity as usize + ec_global_parity as usize
-                    }
-                    ErasureType::ErasureReplication => replication_count as usize,
-                };
-                if pg.osd_ids.len() == expected_shards && expected_shards > 0 {
-                    let nodes_snap = self.osd_nodes.read();
-                    let placements: Vec<NodePlacement> = pg
-                        .osd_ids
-                        .iter()
-                        .enumerate()
-                        .map(|(pos, osd_bytes)| {
-                            let node = nodes_snap
-                                .iter()
-                                .find(|n| n.node_id.as_slice() == osd_bytes.as_slice());
-                            let (node_address, disk_id) = match node {
-                                Some(n) => (
-                                    n.address.clone(),
-                                    n.disk_ids
-                                        .first()
-                                        .map(|d| d.to_vec())
-                                        .unwrap_or_else(|| vec![0u8; 16]),
-                                ),
-                                None => (String::new(), vec![0u8; 16]),
-                            };
-                            let shard_type = pg_position_shard_type(
-                                ec_type,
-                                pos,
-                                ec_k as usize,
-                                ec_local_parity as usize,
-                                local_group_size as usize,
-                            );
-                            let local_group = pg_position_local_group(
-                                ec_type,
-                                pos,
-                                ec_k as usize,
-                                ec_local_parity as usize,
-                                local_group_size as usize,
-                            );
-                            NodePlacement {
-                                position: pos as u32,
-                                node_id: osd_bytes.clone(),
-                                node_address,
-                                disk_id,
-                                shard_type: shard_type.into(),
-                                local_group,
-                            }
-                        })
-                        .collect();
-                    debug!(
-                        "PG placement for {}/{}: pool={}, pg_id={}, {} shards",
-                        req.bucket,
-                        req.key,
-                        pool_name,
-                        pg_id,
-                        placements.len()
-                    );
-                    return Ok(Response::new(GetPlacementResponse {
-                        storage_class: req.storage_class.clone(),
-                        ec_k,
-                        ec_m: ec_local_parity + ec_global_parity,
-                        nodes: placements,
-                        ec_type: ec_type.into(),
-                        ec_local_parity,
-                        ec_global_parity,
-                        local_group_size,
-                        replication_count,
-                        pg_id,
-                        pg_version: pg.version,
-                        pool: pool_name.clone(),
-                    }));
-                }
-                warn!(
-                    "PG {}/{}: osd_ids={} doesn't match expected shards={}; falling back to CRUSH",
-                    pool_name,
-                    pg_id,
-                    pg.osd_ids.len(),
-                    expected_shards
-                );
-            }
-        }
-
-        // Use CRUSH 2.0 for placement
-        let crush = self.crush.read();
-        let hrw_placements = crush.select_placement(&object_id, &template);
-        drop(crush);
-
-        // Convert HRW placements to NodePlacement responses
-        let nodes = self.osd_nodes.read();
-        let placements: Vec<NodePlacement> = hrw_placements
-            .iter()
-            .map(|hrw| {
-                // Find the OSD node by NodeId
-                let node = nodes
-                    .iter()
-                    .find(|n| NodeId::from_bytes(n.node_id) == hrw.node_id);
-
-                let (node_address, disk_id) = match node {
-                    Some(n) => {
-                        let disk = n
-                            .disk_ids
-                            .first()
-                            .map(|d| d.to_vec())
-                            .unwrap_or_else(|| vec![0u8; 16]);
-                        (n.address.clone(), disk)
-                    }
-                    None => {
-                        // Node not found in legacy list, use placeholder
-                        warn!("Node {} not found in OSD list", hrw.node_id);
-                        (String::new(), hrw.node_id.as_bytes().to_vec())
-                    }
-                };
+    /// on the gateway. Continuation token is the bucket-relative
+    /// form of the last key returned.
+    async fn list_objects(
+        &self,
+        request: Request<ListObjectsRequest>,
+    ) -> Result<Response<ListObjectsResponse>, Status> {
+        let req = request.into_inner();
+        if req.bucket.is_empty() {
+            return Err(Status::invalid_argument("bucket required"));
+        }
+        let max_keys = if req.max_keys == 0 {
+            1000
+        } else {
+            req.max_keys.min(1000) as usize
+        };
+        let start_after = if !req.continuation_token.is_empty() {
+            req.continuation_token.clone()
+        } else {
+            req.start_after.clone()
+        };
 
-                let shard_type = match hrw.role {
-                    ShardRole::Data => ShardType::ShardData.into(),
-                    ShardRole::LocalParity => ShardType::ShardLocalParity.into(),
-                    ShardRole::GlobalParity => ShardType::ShardGlobalParity.into(),
-                };
+        let Some(store) = &self.store else {
+            // No persistent store = no Raft backend — return empty.
+            return Ok(Response::new(ListObjectsResponse::default()));
+        };
+        let (rows, is_truncated, next_token) = store
+            .list_object_listings(&req.bucket, &req.prefix, &start_after, max_keys)
+            .map_err(|e| {
+                error!("list_object_listings failed: {e}");
+                Status::internal(format!("list failed: {e}"))
+            })?;
 
-                NodePlacement {
-                    position: hrw.position as u32,
-                    node_id: hrw.node_id.as_bytes().to_vec(),
-                    node_address,
-                    disk_id,
-                    shard_type,
-                    local_group: hrw.local_group.unwrap_or(0) as u32,
+        let mut entries = Vec::with_capacity(rows.len());
+        for (_k, bytes) in rows {
+            match <ObjectListingEntry as prost::Message>::decode(bytes.as_slice()) {
+                Ok(e) => entries.push(e),
+                Err(err) => {
+                    warn!("decode ObjectListingEntry failed: {err}");
                 }
-            })
-            .collect();
+            }
+        }
 
-        debug!(
-            "CRUSH 2.0 placement for {}/{}: {} shards using {:?}",
-            req.bucket,
-            req.key,
-            placements.len(),
-            ec_type
-        );
+        // Common prefixes (delimiter handling) — keep the existing
+        // shape the gateway expects. Our store scan returns fully
+        // expanded keys; applying the delimiter here keeps the client
+        // contract stable across the migration.
+        let mut common_prefixes: Vec<String> = Vec::new();
+        if !req.delimiter.is_empty() {
+            use std::collections::BTreeSet;
+            let mut prefixes: BTreeSet<String> = BTreeSet::new();
+            entries.retain(|e| {
+                let key = &e.key;
+                if let Some(tail) = key.strip_prefix(&req.prefix)
+                    && let Some(idx) = tail.find(&req.delimiter)
+                {
+                    let end = req.prefix.len() + idx + req.delimiter.len();
+                    prefixes.insert(key[..end].to_string());
+                    return false;
+                }
+                true
+            });
+            common_prefixes = prefixes.into_iter().collect();
+        }
 
-        Ok(Response::new(GetPlacementResponse {
-            storage_class: req.storage_class.clone(),
-            ec_k,
-            ec_m: ec_local_parity + ec_global_parity,
-            nodes: placements,
-            ec_type: ec_type.into(),
-            ec_local_parity,
-            ec_global_parity,
-            local_group_size,
-            replication_count,
-            // Filled by Phase 3 once the PG lookup replaces
-            // per-object CRUSH. Leaving zeros keeps pre-migration
-            // clients safe (gateway treats 0 as legacy).
-            pg_id: 0,
-            pg_version: 0,
-            pool: String::new(),
+        let key_count = entries.len() as u32 + common_prefixes.len() as u32;
+        Ok(Response::new(ListObjectsResponse {
+            objects: Vec::new(),
+            common_prefixes,
+            next_continuation_token: next_token,
+            is_truncated,
+            key_count,
+            entries,
         }))
     }
 
+    async fn get_placement(
+        &self,
+        request: Request<GetPlacementRequest>,
+    ) -> Result<Response<GetPlacementResponse>, Status> {
+        let req = request.into_inner();
+        if !req.pool.is_empty() {
+            let pool = req.pool.clone();
+            return self.placement_in_pool(req, Some(pool)).await;
+        }
+
+        // Bucket mid (or post) EC migration: objects written before the
+        // pool switch keep their ObjectMeta on the source pool's
+        // placement until re-encoded, so hand those nodes back for
+        // lookups to fall through to.
+        let Some(source_pool) = self.ec_migration_source_pool(&req.bucket) else {
+            return self.placement_in_pool(req, None).await;
+        };
+        let mut resp = self
+            .placement_in_pool(req.clone(), None)
+            .await?
+            .into_inner();
+        match self.placement_in_pool(req, Some(source_pool)).await {
+            Ok(source) => resp.fallback_nodes = source.into_inner().nodes,
+            Err(e) => debug!("EC migration fallback placement unavailable: {e}"),
+        }
+        Ok(Response::new(resp))
+    }
+
     async fn create_multipart_upload(
         &self,
         request: Request<CreateMultipartUploadRequest>,
@@ -3698,6 +3874,7 @@ impl MetadataService for MetaService {
             kms_key_id: upload.kms_key_id.clone(),
             encrypted_dek: upload.encrypted_dek.clone(),
             encryption_iv: Vec::new(),
+            sse_customer_key_md5: upload.customer_key_md5.clone(),
             ..Default::default()
         };
 
@@ -3801,20 +3978,51 @@ impl MetadataService for MetaService {
                 .then_with(|| a.upload_id.cmp(&b.upload_id))
         });
 
-        let is_truncated = uploads.len() > max_uploads as usize;
-        let uploads: Vec<MultipartUpload> =
-            uploads.into_iter().take(max_uploads as usize).collect();
-
-        let (next_key_marker, next_upload_id_marker) = uploads
-            .last()
-            .map(|u| (u.key.clone(), u.upload_id.clone()))
-            .unwrap_or_default();
+        // Walk in order, rolling keys that contain the delimiter after the
+        // prefix into common prefixes. Each prefix counts once toward
+        // max_uploads; a key marker naming a prefix skips that prefix.
+        let mut page = Vec::new();
+        let mut common_prefixes: Vec<String> = Vec::new();
+        let mut next_key_marker = String::new();
+        let mut next_upload_id_marker = String::new();
+        let mut is_truncated = false;
+        for u in uploads {
+            let rolled_up = (!req.delimiter.is_empty())
+                .then(|| {
+                    u.key[req.prefix.len()..]
+                        .find(&req.delimiter)
+                        .map(|i| u.key[..req.prefix.len() + i + req.delimiter.len()].to_string())
+                })
+                .flatten();
+            if let Some(cp) = &rolled_up
+                && (common_prefixes.last() == Some(cp) || *cp == req.key_marker)
+            {
+                continue;
+            }
+            if page.len() + common_prefixes.len() == max_uploads as usize {
+                is_truncated = true;
+                break;
+            }
+            match rolled_up {
+                Some(cp) => {
+                    next_key_marker.clone_from(&cp);
+                    next_upload_id_marker.clear();
+                    common_prefixes.push(cp);
+                }
+                None => {
+                    next_key_marker.clone_from(&u.key);
+                    next_upload_id_marker.clone_from(&u.upload_id);
+                    page.push(u);
+                }
+            }
+        }
 
         Ok(Response::new(ListMultipartUploadsResponse {
-            uploads,
+            uploads: page,
             next_key_marker,
             next_upload_id_marker,
             is_truncated,
+            common_prefixes,
         }))
     }
 
@@ -7182,6 +7390,142 @@ impl MetadataService for MetaService {
         }))
     }
 
+    async fn start_ec_migration(
+        &self,
+        request: Request<StartEcMigrationRequest>,
+    ) -> Result<Response<StartEcMigrationResponse>, Status> {
+        let req = request.into_inner();
+        if req.target_pool.is_empty() {
+            return Err(Status::invalid_argument("target_pool is required"));
+        }
+        let bucket = self
+            .buckets
+            .read()
+            .get(&req.bucket)
+            .cloned()
+            .ok_or_else(|| Status::not_found(format!("bucket '{}' not found", req.bucket)))?;
+        if !self.pools.read().contains_key(&req.target_pool) {
+            return Err(Status::not_found(format!(
+                "pool '{}' not found",
+                req.target_pool
+            )));
+        }
+
+        let now = Self::current_timestamp();
+        let existing = self.ec_migrations.read().get(&req.bucket).cloned();
+        let job = match &existing {
+            Some(j)
+                if j.target_pool == req.target_pool
+                    && j.state() == EcMigrationState::EcMigrationRunning =>
+            {
+                return Ok(Response::new(StartEcMigrationResponse { job: existing }));
+            }
+            // Resume a cancelled job from its cursor.
+            Some(j)
+                if j.target_pool == req.target_pool
+                    && j.state() == EcMigrationState::EcMigrationCancelled =>
+            {
+                let mut job = j.clone();
+                job.set_state(EcMigrationState::EcMigrationRunning);
+                job.finished_at = 0;
+                job.updated_at = now;
+                job.requested_by = req.requested_by.clone();
+                job
+            }
+            Some(j) if j.state() != EcMigrationState::EcMigrationCompleted => {
+                return Err(Status::already_exists(format!(
+                    "bucket '{}' has an unfinished migration to pool '{}'; resume it first",
+                    req.bucket, j.target_pool
+                )));
+            }
+            _ => {
+                if bucket.pool == req.target_pool {
+                    return Err(Status::invalid_argument(format!(
+                        "bucket '{}' already uses pool '{}'",
+                        req.bucket, req.target_pool
+                    )));
+                }
+                EcMigrationJob {
+                    bucket: req.bucket.clone(),
+                    source_pool: bucket.pool.clone(),
+                    target_pool: req.target_pool.clone(),
+                    state: EcMigrationState::EcMigrationRunning.into(),
+                    started_at: now,
+                    updated_at: now,
+                    requested_by: req.requested_by.clone(),
+                    ..Default::default()
+                }
+            }
+        };
+
+        // Switch the bucket in the same Raft entry, so there's no window
+        // where a job is queued but new writes still land on the old
+        // profile.
+        let bucket_switch = (bucket.pool != req.target_pool).then(|| {
+            let mut switched = bucket.clone();
+            switched.pool = req.target_pool.clone();
+            switched
+        });
+        self.write_ec_migration(
+            bucket_switch.map(|new| (&bucket, new)),
+            existing.as_ref(),
+            job.clone(),
+            "start-ec-migration",
+        )
+        .await?;
+        info!(
+            "EC migration started: bucket '{}' {} -> {} (by {})",
+            job.bucket,
+            if job.source_pool.is_empty() {
+                "<default>"
+            } else {
+                &job.source_pool
+            },
+            job.target_pool,
+            job.requested_by
+        );
+        Ok(Response::new(StartEcMigrationResponse { job: Some(job) }))
+    }
+
+    async fn get_ec_migration_status(
+        &self,
+        request: Request<GetEcMigrationStatusRequest>,
+    ) -> Result<Response<GetEcMigrationStatusResponse>, Status> {
+        let bucket = request.into_inner().bucket;
+        let jobs = self
+            .ec_migrations_snapshot()
+            .into_iter()
+            .filter(|j| bucket.is_empty() || j.bucket == bucket)
+            .collect();
+        Ok(Response::new(GetEcMigrationStatusResponse { jobs }))
+    }
+
+    async fn cancel_ec_migration(
+        &self,
+        request: Request<CancelEcMigrationRequest>,
+    ) -> Result<Response<CancelEcMigrationResponse>, Status> {
+        let bucket = request.into_inner().bucket;
+        let prev = self
+            .ec_migrations
+            .read()
+            .get(&bucket)
+            .cloned()
+            .ok_or_else(|| Status::not_found(format!("no EC migration for bucket '{bucket}'")))?;
+        // Cancelling a finished or already-cancelled job is a no-op.
+        if prev.state() != EcMigrationState::EcMigrationRunning {
+            return Ok(Response::new(CancelEcMigrationResponse { job: Some(prev) }));
+        }
+        let now = Self::current_timestamp();
+        let mut job = prev.clone();
+        job.set_state(EcMigrationState::EcMigrationCancelled);
+        job.updated_at = now;
+        job.finished_at = now;
+        self.write_ec_migration(None, Some(&prev), job.clone(), "cancel-ec-migration")
+            .await?;
+        info!("EC migration cancelled: bucket '{bucket}'");
+        Ok(Response::new(CancelEcMigrationResponse { job: Some(job) }))
+    }
+
     async fn list_config(
         &self,
         request: Request<ListConfigRequest>,
@@ -7684,6 +8028,7 @@ impl MetadataService for MetaService {
             quota_bytes: 0,
             quota_objects: 0,
             object_lock: None,
+            acl: None,
         };
         let bucket_bytes = bucket.encode_to_vec();
 
@@ -7889,6 +8234,7 @@ impl MetadataService for MetaService {
             quota_bytes: 0,
             quota_objects: 0,
             object_lock: None,
+            acl: None,
         };
         let bucket_bytes = bucket.encode_to_vec();
 
@@ -9690,6 +10036,79 @@ impl MetadataService for MetaService {
         }))
     }
 
+    // ============================================================
+    // Bucket ACL
+    // ============================================================
+
+    async fn put_bucket_acl(
+        &self,
+        request: Request<PutBucketAclRequest>,
+    ) -> Result<Response<PutBucketAclResponse>, Status> {
+        let req = request.into_inner();
+        let acl = req
+            .acl
+            .ok_or_else(|| Status::invalid_argument("missing access control policy"))?;
+
+        let (expected_bytes, new_bucket, new_bytes) = {
+            let buckets = self.buckets.read();
+            let current = buckets
+                .get(&req.bucket)
+                .cloned()
+                .ok_or_else(|| Status::not_found(format!("bucket '{}' not found", req.bucket)))?;
+            let expected = current.encode_to_vec();
+            let mut new_bucket = current;
+            new_bucket.acl = Some(acl);
+            let new_bytes = new_bucket.encode_to_vec();
+            (expected, new_bucket, new_bytes)
+        };
+
+        if let Some(raft) = self.raft_handle() {
+            use objectio_meta_store::{CasOp, CasTable, MetaCommand, MetaResponse};
+            let cmd = MetaCommand::MultiCas {
+                ops: vec![CasOp {
+                    table: CasTable::Buckets,
+                    key: req.bucket.clone(),
+                    expected: Some(expected_bytes),
+                    new_value: Some(new_bytes),
+                }],
+                requested_by: "put-bucket-acl".into(),
+            };
+            match raft.client_write(cmd).await {
+                Ok(r) => match r.data {
+                    MetaResponse::MultiCasOk => {}
+                    MetaResponse::MultiCasConflict { .. } => {
+                        return Err(Status::aborted("bucket changed since read; retry"));
+                    }
+                    other => {
+                        error!("unexpected raft response for put_bucket_acl: {:?}", other);
+                        return Err(Status::internal("raft commit wrong variant"));
+                    }
+                },
+                Err(e) => return Err(raft_write_to_status(&e)),
+            }
+        } else if let Some(store) = &self.store {
+            store.put_bucket(&req.bucket, &new_bucket);
+        }
+
+        self.buckets.write().insert(req.bucket.clone(), new_bucket);
+        info!("Set ACL for bucket '{}'", req.bucket);
+        Ok(Response::new(PutBucketAclResponse { success: true }))
+    }
+
+    async fn get_bucket_acl(
+        &self,
+        request: Request<GetBucketAclRequest>,
+    ) -> Result<Response<GetBucketAclResponse>, Status> {
+        let bucket_name = request.into_inner().bucket;
+        let buckets = self.buckets.read();
+        let bucket = buckets
+            .get(&bucket_name)
+            .ok_or_else(|| Status::not_found(format!("bucket '{}' not found", bucket_name)))?;
+        Ok(Response::new(GetBucketAclResponse {
+            acl: bucket.acl.clone(),
+        }))
+    }
+
     // ============================================================
     // Object Lock Configuration
     // ============================================================
@@ -10533,4 +10952,73 @@ impl MetadataService for MetaService {
         let policy_names = attachments.get(&key).cloned().unwrap_or_default();
         Ok(Response::new(ListAttachedPoliciesResponse { policy_names }))
     }
+
+    /// Build fingerprint of this meta node plus every registered OSD.
+    /// OSDs are queried concurrently; one that can't be reached still gets
+    /// an entry, with `error` set, so it shows up in the report.
+    async fn get_version_info(
+        &self,
+        _request: Request<GetVersionInfoRequest>,
+    ) -> Result<Response<GetVersionInfoResponse>, Status> {
+        use crate::drain_observer::{PER_OSD_TIMEOUT, open_channel};
+        use objectio_common::build_info;
+        use objectio_erasure::BackendFactory;
+        use objectio_proto::storage::storage_service_client::StorageServiceClient;
+
+        let mut features: Vec<String> = objectio_auth::enabled_features()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        if BackendFactory::available_backends()
+            .iter()
+            .any(|c| c.name == "isal")
+        {
+            features.insert(0, "isal".to_string());
+        }
+        let mut components = vec![ComponentVersion {
+            component: "meta".to_string(),
+            version: build_info::VERSION.to_string(),
+            git_hash: build_info::GIT_HASH.to_string(),
+            features,
+            ec_backend: BackendFactory::current_backend().name().to_string(),
+            storage_protocol: build_info::STORAGE_PROTOCOL_VERSION,
+            metadata_protocol: build_info::METADATA_PROTOCOL_VERSION,
+            ..Default::default()
+        }];
+
+        let nodes: Vec<([u8; 16], String)> = self
+            .osd_nodes
+            .read()
+            .iter()
+            .map(|n| (n.node_id, n.address.clone()))
+            .collect();
+        let futs = nodes.into_iter().map(|(node_id, address)| async move {
+            let result = async {
+                let mut client = StorageServiceClient::new(open_channel(&address).await?);
+                let resp = tokio::time::timeout(
+                    PER_OSD_TIMEOUT,
+                    client.get_version_info(GetVersionInfoRequest {}),
+                )
+                .await
+                .map_err(|_| anyhow::anyhow!("get_version_info timeout"))??;
+                resp.into_inner()
+                    .components
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("empty version response"))
+            }
+            .await;
+            let mut entry = result.unwrap_or_else(|e| ComponentVersion {
+                component: "osd".to_string(),
+                error: e.to_string(),
+                ..Default::default()
+            });
+            entry.node_id = hex::encode(node_id);
+            entry.address = address;
+            entry
+        });
+        components.extend(futures::future::join_all(futs).await);
+
+        Ok(Response::new(GetVersionInfoResponse { components }))
+    }
 }
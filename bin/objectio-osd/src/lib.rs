@@ -91,6 +91,12 @@ pub struct Args {
     /// Metrics server port (Prometheus)
     #[arg(long, default_value = "9201")]
     pub metrics_port: u16,
+
+    /// Shard data cache size in MiB (0 disables). Keeps recently read
+    /// shards in memory so hot objects (e.g. ones warmed through the
+    /// gateway's `/_admin/prefetch`) skip the disk.
+    #[arg(long, default_value_t = 256)]
+    pub shard_cache_mb: u64,
 }
 
 /// Configuration file structure
@@ -341,7 +347,7 @@ pub async fn run(
     info!("Data directory: {}", data_dir);
     let disk_paths = disks.clone();
     let osd_service = match OsdService::new(disk_paths, block_size as u32, data_path) {
-        Ok(s) => s,
+        Ok(s) => s.with_shard_cache(args.shard_cache_mb * 1024 * 1024),
         Err(e) => {
             error!("Failed to initialize OSD: {}", e);
             std::process::exit(1);
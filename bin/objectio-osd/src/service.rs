@@ -1,11 +1,17 @@
 //! OSD gRPC service implementation
 
 use futures::stream::Stream;
-use objectio_proto::metadata::ObjectMeta;
+use objectio_common::{ByteCache, build_info};
+use objectio_erasure::BackendFactory;
+use objectio_proto::metadata::{
+    ComponentVersion, GetVersionInfoRequest, GetVersionInfoResponse, ObjectMeta,
+};
 use objectio_proto::storage::{
     AffectedObject,
     AffectedShardRef,
     BlockLocation,
+    CacheTierStats,
+    CacheWritePolicy as ProtoCacheWritePolicy,
     Checksum,
     CopyObjectMetaRequest,
     CopyObjectMetaResponse,
@@ -16,6 +22,8 @@ use objectio_proto::storage::{
     DiskStatus,
     FindObjectsReferencingNodeRequest,
     FindObjectsReferencingNodeResponse,
+    GetCacheStatsRequest,
+    GetCacheStatsResponse,
     GetObjectMetaRequest,
     GetObjectMetaResponse,
     GetShardMetaRequest,
@@ -36,13 +44,17 @@ use objectio_proto::storage::{
     PutObjectMetaResponse,
     ReadShardRequest,
     ReadShardResponse,
+    ShardCacheStats,
+    TuneCacheRequest,
     WriteShardRequest,
     WriteShardResponse,
     health_check_response::Status as HealthStatus,
     storage_service_server::StorageService,
 };
 use objectio_storage::DiskManager;
-use objectio_storage::metadata::{MetadataKey, MetadataStore, MetadataStoreConfig};
+use objectio_storage::metadata::{
+    CacheTierCounters, CacheWritePolicy, MetadataKey, MetadataStore, MetadataStoreConfig,
+};
 use parking_lot::RwLock;
 use prost::Message;
 use std::collections::HashMap;
@@ -270,6 +282,9 @@ pub struct OsdService {
     next_block: Vec<std::sync::atomic::AtomicU64>,
     /// gRPC metrics collector
     grpc_metrics: Arc<GrpcMetrics>,
+    /// Recently read shard blocks, keyed like `shard_index`. Shard data
+    /// goes to disk with O_DIRECT, so this is the only read cache.
+    shard_cache: ByteCache<String>,
 }
 
 /// Resolve the OSD's stable node_id + cluster_uuid from (in priority order):
@@ -508,9 +523,17 @@ impl OsdService {
             next_disk: RwLock::new(0),
             next_block,
             grpc_metrics: Arc::new(GrpcMetrics::default()),
+            shard_cache: ByteCache::new(0),
         })
     }
 
+    /// Enable the shard data cache with the given capacity in bytes.
+    #[must_use]
+    pub fn with_shard_cache(mut self, capacity_bytes: u64) -> Self {
+        self.shard_cache = ByteCache::new(capacity_bytes);
+        self
+    }
+
     /// Get gRPC metrics
     pub fn grpc_metrics(&self) -> &Arc<GrpcMetrics> {
         &self.grpc_metrics
@@ -628,6 +651,45 @@ impl OsdService {
         format!("{}:{}:{}", hex::encode(object_id), stripe_id, position)
     }
 
+    /// Build the GetCacheStats/TuneCache response from the meta store.
+    fn cache_stats_response(&self) -> GetCacheStatsResponse {
+        let stats = self.meta_store.cache_tier_stats();
+        let tier = |name: &str, c: &CacheTierCounters| CacheTierStats {
+            name: name.to_string(),
+            entries: c.entries as u64,
+            ghost_entries: c.ghost_entries as u64,
+            hits: c.hits,
+            evictions: c.evictions,
+            ghost_hits: c.ghost_hits,
+        };
+        let write_policy = match stats.write_policy {
+            CacheWritePolicy::WriteThrough => ProtoCacheWritePolicy::WriteThrough,
+            CacheWritePolicy::WriteAround => ProtoCacheWritePolicy::WriteAround,
+        };
+        GetCacheStatsResponse {
+            capacity: stats.capacity as u64,
+            target_recent_size: stats.target_t1_size as u64,
+            write_policy: write_policy.into(),
+            misses: stats.misses,
+            hit_ratio: self.meta_store.cache_hit_ratio(),
+            tiers: vec![
+                tier("recent", &stats.recent),
+                tier("frequent", &stats.frequent),
+            ],
+            shard_cache: Some({
+                let s = self.shard_cache.stats();
+                ShardCacheStats {
+                    capacity_bytes: s.capacity_bytes,
+                    bytes: s.bytes,
+                    entries: s.entries,
+                    hits: s.hits,
+                    misses: s.misses,
+                    evictions: s.evictions,
+                }
+            }),
+        }
+    }
+
     /// Build the MetadataStore key we persist a ShardLocation under.
     fn shard_loc_meta_key(shard_key: &str) -> objectio_storage::MetadataKey {
         let mut bytes = Vec::with_capacity(SHARD_LOC_PREFIX.len() + shard_key.len());
@@ -777,6 +839,7 @@ impl StorageService for OsdService {
             );
         }
         self.shard_index.write().insert(key.clone(), loc);
+        self.shard_cache.remove(&key);
 
         info!(
             "Wrote shard: disk={}, block={}, size={}, crc32c={:08x}",
@@ -835,21 +898,27 @@ impl StorageService for OsdService {
             Status::not_found("shard not found")
         })?;
 
-        let disk = &self.disks[location.disk_idx];
+        let data = if let Some(cached) = self.shard_cache.get(&key) {
+            cached.to_vec()
+        } else {
+            let disk = &self.disks[location.disk_idx];
 
-        // Async read — same semantics, reactor stays free during I/O.
-        let (_header, data) = disk
-            .read_block_async(location.block_num)
-            .await
-            .map_err(|e| {
-                self.grpc_metrics.read_shard.record(
-                    false,
-                    start.elapsed().as_micros() as u64,
-                    bytes_in,
-                    0,
-                );
-                Status::internal(format!("read failed: {}", e))
-            })?;
+            // Async read — same semantics, reactor stays free during I/O.
+            let (_header, data) = disk
+                .read_block_async(location.block_num)
+                .await
+                .map_err(|e| {
+                    self.grpc_metrics.read_shard.record(
+                        false,
+                        start.elapsed().as_micros() as u64,
+                        bytes_in,
+                        0,
+                    );
+                    Status::internal(format!("read failed: {}", e))
+                })?;
+            self.shard_cache.insert(key, &data);
+            data
+        };
 
         debug!(
             "ReadShard: object={}, stripe={}, pos={}, size={}",
@@ -893,6 +962,7 @@ impl StorageService for OsdService {
         let key = Self::shard_key(&shard_id.object_id, shard_id.stripe_id, shard_id.position);
 
         let removed = self.shard_index.write().remove(&key).is_some();
+        self.shard_cache.remove(&key);
         // Mirror the removal in the persistent index so a future
         // restart doesn't resurrect the deleted shard.
         if removed && let Err(e) = Self::forget_shard_location(&self.meta_store, &key) {
@@ -1545,4 +1615,79 @@ impl StorageService for OsdService {
             is_truncated,
         }))
     }
+
+    async fn get_cache_stats(
+        &self,
+        _request: Request<GetCacheStatsRequest>,
+    ) -> Result<Response<GetCacheStatsResponse>, Status> {
+        Ok(Response::new(self.cache_stats_response()))
+    }
+
+    async fn tune_cache(
+        &self,
+        request: Request<TuneCacheRequest>,
+    ) -> Result<Response<GetCacheStatsResponse>, Status> {
+        let req = request.into_inner();
+
+        let policy = match ProtoCacheWritePolicy::try_from(req.write_policy) {
+            Ok(ProtoCacheWritePolicy::Unspecified) => None,
+            Ok(ProtoCacheWritePolicy::WriteThrough) => Some(CacheWritePolicy::WriteThrough),
+            Ok(ProtoCacheWritePolicy::WriteAround) => Some(CacheWritePolicy::WriteAround),
+            Err(_) => {
+                return Err(Status::invalid_argument(format!(
+                    "unknown write_policy {}",
+                    req.write_policy
+                )));
+            }
+        };
+        let capacity = usize::try_from(req.capacity)
+            .map_err(|_| Status::invalid_argument("capacity out of range"))?;
+
+        if capacity > 0 {
+            self.meta_store.resize_cache(capacity);
+        }
+        if let Some(policy) = policy {
+            self.meta_store.set_cache_write_policy(policy);
+        }
+        if req.flush || req.drop_entries {
+            self.meta_store
+                .flush_cache(req.drop_entries)
+                .map_err(|e| Status::internal(format!("cache flush failed: {e}")))?;
+        }
+        if req.reset_stats {
+            self.meta_store.reset_cache_stats();
+        }
+
+        Ok(Response::new(self.cache_stats_response()))
+    }
+
+    async fn get_version_info(
+        &self,
+        _request: Request<GetVersionInfoRequest>,
+    ) -> Result<Response<GetVersionInfoResponse>, Status> {
+        let mut features = Vec::new();
+        if BackendFactory::available_backends()
+            .iter()
+            .any(|c| c.name == "isal")
+        {
+            features.push("isal".to_string());
+        }
+        if cfg!(feature = "io-uring") {
+            features.push("io-uring".to_string());
+        }
+        Ok(Response::new(GetVersionInfoResponse {
+            components: vec![ComponentVersion {
+                component: "osd".to_string(),
+                node_id: hex::encode(self.node_id),
+                address: String::new(),
+                version: build_info::VERSION.to_string(),
+                git_hash: build_info::GIT_HASH.to_string(),
+                features,
+                ec_backend: BackendFactory::current_backend().name().to_string(),
+                storage_protocol: build_info::STORAGE_PROTOCOL_VERSION,
+                metadata_protocol: build_info::METADATA_PROTOCOL_VERSION,
+                error: String::new(),
+            }],
+        }))
+    }
 }
@@ -74,3 +74,14 @@ pub use providers::{OidcConfig, OidcProvider, TokenResponse};
 // Re-export OpenFGA types (feature-gated)
 #[cfg(feature = "openfga")]
 pub use evaluators::{OpenFgaConfig, OpenFgaEvaluator, OpenFgaTupleManager, TupleKey};
+
+/// Optional auth features compiled into this build, for version reports.
+pub fn enabled_features() -> Vec<&'static str> {
+    [
+        ("oidc", cfg!(feature = "oidc")),
+        ("openfga", cfg!(feature = "openfga")),
+    ]
+    .into_iter()
+    .filter_map(|(name, on)| on.then_some(name))
+    .collect()
+}
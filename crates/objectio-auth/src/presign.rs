@@ -2,6 +2,8 @@
 //!
 //! Generates pre-signed GET URLs for S3-compatible object storage.
 //! The generated URLs are verified by the existing SigV4 auth middleware.
+//! Also signs outbound header-authenticated requests to AWS JSON APIs
+//! (used by the AWS KMS backend).
 //!
 //! Reference: https://docs.aws.amazon.com/AmazonS3/latest/API/sigv4-query-string-auth.html
 
@@ -59,6 +61,57 @@ pub fn presign_get(
     bucket: &str,
     key: &str,
     expires_in: Duration,
+) -> String {
+    presign_object(
+        "GET",
+        endpoint,
+        region,
+        access_key_id,
+        secret_access_key,
+        bucket,
+        key,
+        expires_in,
+    )
+}
+
+/// Generate a presigned S3 PUT URL.
+///
+/// Same arguments as [`presign_get`]. The payload is unsigned, so the
+/// caller can `PUT` any body to the returned URL without extra headers.
+#[must_use]
+pub fn presign_put(
+    endpoint: &str,
+    region: &str,
+    access_key_id: &str,
+    secret_access_key: &str,
+    bucket: &str,
+    key: &str,
+    expires_in: Duration,
+) -> String {
+    presign_object(
+        "PUT",
+        endpoint,
+        region,
+        access_key_id,
+        secret_access_key,
+        bucket,
+        key,
+        expires_in,
+    )
+}
+
+/// Shared body of the object-level presigners; `method` goes into the
+/// canonical request verbatim.
+#[allow(clippy::too_many_arguments)]
+fn presign_object(
+    method: &str,
+    endpoint: &str,
+    region: &str,
+    access_key_id: &str,
+    secret_access_key: &str,
+    bucket: &str,
+    key: &str,
+    expires_in: Duration,
 ) -> String {
     let now = Utc::now();
     let date_str = now.format("%Y%m%d").to_string();
@@ -97,9 +150,9 @@ pub fn presign_get(
     let canonical_headers = format!("host:{host}\n");
     let signed_headers = "host";
 
-    // Canonical request (payload is UNSIGNED for presigned GETs)
+    // Canonical request (payload is UNSIGNED for presigned URLs)
     let canonical_request = format!(
-        "GET\n{uri}\n{qs}\n{headers}\n{signed_hdr}\nUNSIGNED-PAYLOAD",
+        "{method}\n{uri}\n{qs}\n{headers}\n{signed_hdr}\nUNSIGNED-PAYLOAD",
         uri = canonical_uri,
         qs = canonical_qs,
         headers = canonical_headers,
@@ -233,6 +286,92 @@ pub fn presign_list_objects_v2(
     )
 }
 
+/// Credentials for signing an outbound request.
+#[derive(Debug, Clone, Copy)]
+pub struct SigningCredentials<'a> {
+    pub access_key_id: &'a str,
+    pub secret_access_key: &'a str,
+    /// STS session token, sent as `x-amz-security-token` when present.
+    pub session_token: Option<&'a str>,
+}
+
+/// Sign a request with header-based SigV4 (`Authorization` header).
+///
+/// `headers` are the request headers to sign in addition to `host` and
+/// `x-amz-date`; the returned headers (`x-amz-date`, optionally
+/// `x-amz-security-token`, and `authorization`) must be added to the
+/// request as-is. `path` must already be URI-encoded, including any
+/// `?query` suffix.
+#[must_use]
+#[allow(clippy::too_many_arguments)]
+pub fn sign_request_headers(
+    method: &str,
+    host: &str,
+    path: &str,
+    service: &str,
+    region: &str,
+    creds: &SigningCredentials<'_>,
+    headers: &[(&str, &str)],
+    payload: &[u8],
+) -> Vec<(String, String)> {
+    let now = Utc::now();
+    let date_str = now.format("%Y%m%d").to_string();
+    let datetime_str = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let credential_scope = format!("{date_str}/{region}/{service}/aws4_request");
+
+    let mut signed: Vec<(String, String)> = headers
+        .iter()
+        .map(|(k, v)| (k.to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+    signed.push(("host".to_string(), host.to_string()));
+    signed.push(("x-amz-date".to_string(), datetime_str.clone()));
+    if let Some(token) = creds.session_token {
+        signed.push(("x-amz-security-token".to_string(), token.to_string()));
+    }
+    signed.sort();
+
+    let canonical_headers: String = signed.iter().map(|(k, v)| format!("{k}:{v}\n")).collect();
+    let signed_headers = signed
+        .iter()
+        .map(|(k, _)| k.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let payload_hash = hex::encode(Sha256::digest(payload));
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let mut params: Vec<(&str, &str)> = query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|p| p.split_once('=').unwrap_or((p, "")))
+        .collect();
+    params.sort_unstable();
+    let canonical_qs = params
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("&");
+    let canonical_request = format!(
+        "{method}\n{path}\n{canonical_qs}\n{canonical_headers}\n{signed_headers}\n{payload_hash}"
+    );
+
+    let cr_hash = hex::encode(Sha256::digest(canonical_request.as_bytes()));
+    let string_to_sign = format!("AWS4-HMAC-SHA256\n{datetime_str}\n{credential_scope}\n{cr_hash}");
+    let signing_key = derive_signing_key(creds.secret_access_key, &date_str, region, service);
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    let mut out = vec![("x-amz-date".to_string(), datetime_str)];
+    if let Some(token) = creds.session_token {
+        out.push(("x-amz-security-token".to_string(), token.to_string()));
+    }
+    out.push((
+        "authorization".to_string(),
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{credential_scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            creds.access_key_id
+        ),
+    ));
+    out
+}
+
 /// Derive the SigV4 signing key from the secret access key and scope components.
 fn derive_signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
     let k_date = hmac_sha256(format!("AWS4{secret}").as_bytes(), date.as_bytes());
@@ -268,6 +407,39 @@ mod tests {
         assert!(url.contains("X-Amz-Expires=3600"));
     }
 
+    #[test]
+    fn test_presign_put_differs_from_get() {
+        let args = (
+            "http://localhost:9000",
+            "us-east-1",
+            "AKID",
+            "secret",
+            "backups",
+            "meta/latest",
+        );
+        let get = presign_get(
+            args.0,
+            args.1,
+            args.2,
+            args.3,
+            args.4,
+            args.5,
+            Duration::from_secs(60),
+        );
+        let put = presign_put(
+            args.0,
+            args.1,
+            args.2,
+            args.3,
+            args.4,
+            args.5,
+            Duration::from_secs(60),
+        );
+        assert!(put.starts_with("http://localhost:9000/backups/meta/latest?"));
+        let sig = |u: &str| u.rsplit("X-Amz-Signature=").next().unwrap().to_string();
+        assert_ne!(sig(&get), sig(&put));
+    }
+
     #[test]
     fn test_presign_list_objects_v2_includes_required_params() {
         let url = presign_list_objects_v2(
@@ -304,4 +476,35 @@ mod tests {
         );
         assert!(url.contains("continuation-token=opaqueToken%3D%3D"));
     }
+
+    #[test]
+    fn test_sign_request_headers() {
+        let creds = SigningCredentials {
+            access_key_id: "AKID",
+            secret_access_key: "secret",
+            session_token: Some("tok"),
+        };
+        let headers = sign_request_headers(
+            "POST",
+            "kms.us-east-1.amazonaws.com",
+            "/",
+            "kms",
+            "us-east-1",
+            &creds,
+            &[("X-Amz-Target", "TrentService.Decrypt")],
+            b"{}",
+        );
+        let get = |name: &str| {
+            headers
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.clone())
+                .unwrap()
+        };
+        assert_eq!(get("x-amz-security-token"), "tok");
+        let auth = get("authorization");
+        assert!(auth.starts_with("AWS4-HMAC-SHA256 Credential=AKID/"));
+        assert!(auth.contains("/us-east-1/kms/aws4_request"));
+        assert!(auth.contains("SignedHeaders=host;x-amz-date;x-amz-security-token;x-amz-target"));
+    }
 }
@@ -409,10 +409,8 @@ impl OidcProvider {
                             .filter_map(|s| s.as_str().map(String::from))
                             .collect(),
                     )
-                } else if let Some(s) = v.as_str() {
-                    Some(vec![s.to_string()])
                 } else {
-                    None
+                    v.as_str().map(|s| vec![s.to_string()])
                 }
             })
             .unwrap_or_default()
@@ -292,6 +292,14 @@ impl WriteCache {
         Some(result)
     }
 
+    /// Whether a chunk has unflushed writes
+    pub fn is_dirty(&self, volume_id: &str, chunk_id: ChunkId) -> bool {
+        self.caches
+            .read()
+            .get(volume_id)
+            .is_some_and(|c| c.dirty_chunks.contains_key(&chunk_id))
+    }
+
     /// Add a clean chunk to the read cache
     pub fn add_clean(&self, volume_id: &str, chunk_id: ChunkId, data: Bytes) {
         let mut caches = self.caches.write();
@@ -136,6 +136,20 @@ impl ChunkMapper {
     }
 }
 
+/// Length of `data` with trailing zero LBAs dropped
+///
+/// A chunk stored at this length reads back identically once padded with
+/// zeros to the chunk size, which is how defragmentation shrinks
+/// partially-filled chunks. Returns 0 for an all-zero chunk.
+pub fn dense_len(data: &[u8]) -> usize {
+    data.iter()
+        .rposition(|&b| b != 0)
+        .map_or(0, |last| {
+            (last + 1).div_ceil(LBA_SIZE as usize) * LBA_SIZE as usize
+        })
+        .min(data.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,6 +245,24 @@ mod tests {
         assert_eq!(mapper.chunks_for_size(4 * 1024 * 1024 + 1), 2);
     }
 
+    #[test]
+    fn test_dense_len() {
+        let mut data = vec![0u8; 4096];
+        assert_eq!(dense_len(&data), 0);
+
+        data[0] = 1;
+        assert_eq!(dense_len(&data), 512);
+
+        data[512] = 1;
+        assert_eq!(dense_len(&data), 1024);
+
+        data[4095] = 1;
+        assert_eq!(dense_len(&data), 4096);
+
+        // Unaligned tail never rounds past the buffer
+        assert_eq!(dense_len(&[0, 7, 0]), 3);
+    }
+
     #[test]
     fn test_empty_range() {
         let mapper = ChunkMapper::default();
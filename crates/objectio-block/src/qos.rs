@@ -720,11 +720,7 @@ impl IoStats {
             // Extrapolate to per-second
             let ops = self.window_ops.load(Ordering::Relaxed);
             let elapsed_ms = elapsed.as_millis() as u64;
-            if elapsed_ms > 0 {
-                (ops * 1000) / elapsed_ms
-            } else {
-                0
-            }
+            (ops * 1000).checked_div(elapsed_ms).unwrap_or(0)
         }
     }
 
@@ -0,0 +1,211 @@
+//! Minimal DNS stub resolver.
+//!
+//! Sends A and AAAA queries over UDP straight to configured nameservers,
+//! for networks where internal service names only resolve through a
+//! specific DNS server that the host's resolv.conf doesn't point at.
+//! No caching, no search domains and no TCP fallback: internal service
+//! names resolve to a handful of addresses, well within a UDP response.
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+
+static NEXT_ID: AtomicU16 = AtomicU16::new(0);
+
+fn next_id() -> u16 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    NEXT_ID.fetch_add(1, Ordering::Relaxed) ^ (nanos as u16)
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Encode a recursive query for `host`.
+pub(crate) fn build_query(id: u16, host: &str, qtype: u16) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(host.len() + 18);
+    buf.extend_from_slice(&id.to_be_bytes());
+    buf.extend_from_slice(&0x0100u16.to_be_bytes()); // RD
+    buf.extend_from_slice(&1u16.to_be_bytes()); // QDCOUNT
+    buf.extend_from_slice(&[0; 6]); // AN/NS/AR counts
+    for label in host.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(invalid(format!("invalid host name '{host}'")));
+        }
+        buf.push(label.len() as u8);
+        buf.extend_from_slice(label.as_bytes());
+    }
+    buf.push(0);
+    buf.extend_from_slice(&qtype.to_be_bytes());
+    buf.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(buf)
+}
+
+/// Skip an encoded name starting at `pos`, returning the offset after it.
+fn skip_name(buf: &[u8], mut pos: usize) -> io::Result<usize> {
+    loop {
+        let len = *buf.get(pos).ok_or_else(|| invalid("truncated name"))?;
+        match len {
+            0 => return Ok(pos + 1),
+            // Compression pointer: two bytes, ends the name
+            l if l & 0xC0 == 0xC0 => return Ok(pos + 2),
+            l => pos += 1 + l as usize,
+        }
+    }
+}
+
+fn read_u16(buf: &[u8], pos: usize) -> io::Result<u16> {
+    buf.get(pos..pos + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+        .ok_or_else(|| invalid("truncated message"))
+}
+
+/// Extract the A/AAAA addresses from a response to query `id`.
+pub(crate) fn parse_response(id: u16, buf: &[u8]) -> io::Result<Vec<IpAddr>> {
+    if read_u16(buf, 0)? != id {
+        return Err(invalid("response id mismatch"));
+    }
+    let flags = read_u16(buf, 2)?;
+    if flags & 0x0200 != 0 {
+        return Err(invalid("truncated response"));
+    }
+    match flags & 0x000F {
+        0 => {}
+        3 => return Ok(Vec::new()), // NXDOMAIN
+        rcode => return Err(io::Error::other(format!("DNS error rcode {rcode}"))),
+    }
+    let qdcount = read_u16(buf, 4)?;
+    let ancount = read_u16(buf, 6)?;
+
+    let mut pos = 12;
+    for _ in 0..qdcount {
+        pos = skip_name(buf, pos)? + 4;
+    }
+    let mut addrs = Vec::new();
+    for _ in 0..ancount {
+        pos = skip_name(buf, pos)?;
+        let rtype = read_u16(buf, pos)?;
+        let rdlen = read_u16(buf, pos + 8)? as usize;
+        pos += 10;
+        let rdata = buf
+            .get(pos..pos + rdlen)
+            .ok_or_else(|| invalid("truncated record"))?;
+        match (rtype, rdlen) {
+            (TYPE_A, 4) => addrs.push(IpAddr::V4(Ipv4Addr::new(
+                rdata[0], rdata[1], rdata[2], rdata[3],
+            ))),
+            (TYPE_AAAA, 16) => {
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(rdata);
+                addrs.push(IpAddr::V6(Ipv6Addr::from(octets)));
+            }
+            _ => {} // CNAMEs etc.; the server already chased them
+        }
+        pos += rdlen;
+    }
+    Ok(addrs)
+}
+
+async fn query(
+    nameserver: SocketAddr,
+    host: &str,
+    qtype: u16,
+    timeout: Duration,
+) -> io::Result<Vec<IpAddr>> {
+    let bind: SocketAddr = if nameserver.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(bind).await?;
+    socket.connect(nameserver).await?;
+    let id = next_id();
+    socket.send(&build_query(id, host, qtype)?).await?;
+
+    let mut buf = [0u8; 1232];
+    tokio::time::timeout(timeout, async {
+        loop {
+            let n = socket.recv(&mut buf).await?;
+            // Ignore stray datagrams carrying someone else's id
+            if buf[..n].starts_with(&id.to_be_bytes()) {
+                return parse_response(id, &buf[..n]);
+            }
+        }
+    })
+    .await
+    .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "DNS query timed out"))?
+}
+
+/// Resolve `host` through the first nameserver that answers.
+pub(crate) async fn lookup(
+    host: &str,
+    nameservers: &[SocketAddr],
+    timeout: Duration,
+) -> io::Result<Vec<IpAddr>> {
+    let mut last_err = io::Error::new(io::ErrorKind::NotFound, "no nameservers configured");
+    for &ns in nameservers {
+        let (a, aaaa) = tokio::join!(
+            query(ns, host, TYPE_A, timeout),
+            query(ns, host, TYPE_AAAA, timeout)
+        );
+        match (a, aaaa) {
+            (Err(e), Err(_)) => last_err = e,
+            (a, aaaa) => {
+                let mut addrs = a.unwrap_or_default();
+                addrs.extend(aaaa.unwrap_or_default());
+                if addrs.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("{host} not found via {ns}"),
+                    ));
+                }
+                return Ok(addrs);
+            }
+        }
+    }
+    Err(last_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_response_with_compression() {
+        let mut resp = build_query(0x1234, "osd-1.storage.internal", TYPE_A).unwrap();
+        resp[2] = 0x81; // QR + RD
+        resp[3] = 0x80; // RA
+        resp[7] = 2; // ANCOUNT
+        // CNAME answer pointing back at the question name
+        resp.extend_from_slice(&[0xC0, 12, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xC0, 12]);
+        // A record for the same name
+        resp.extend_from_slice(&[0xC0, 12, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 10, 0, 3, 7]);
+
+        assert_eq!(
+            parse_response(0x1234, &resp).unwrap(),
+            vec![IpAddr::V4(Ipv4Addr::new(10, 0, 3, 7))]
+        );
+        assert!(parse_response(0x4321, &resp).is_err());
+
+        // NXDOMAIN is an empty answer, not an error
+        resp[3] = 0x83;
+        assert!(parse_response(0x1234, &resp).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_build_query_rejects_bad_names() {
+        assert!(build_query(1, "a..b", TYPE_A).is_err());
+        assert!(build_query(1, &"x".repeat(64), TYPE_A).is_err());
+        let q = build_query(1, "meta.", TYPE_AAAA).unwrap();
+        assert_eq!(&q[12..18], b"\x04meta\x00");
+    }
+}
@@ -2,9 +2,12 @@
 //!
 //! This crate provides gRPC clients for inter-service communication.
 
+mod dns;
 pub mod meta;
+pub mod net;
 pub mod osd;
 
 // Re-exports
 pub use meta::MetaClient;
+pub use net::{NetConfig, NetConfigError, Proxy, Route};
 pub use osd::OsdClient;
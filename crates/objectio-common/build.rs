@@ -0,0 +1,24 @@
+//! Stamp the build with the commit it came from (see `build_info`).
+
+use std::process::Command;
+
+fn main() {
+    // Container builds usually have no `.git`; they pass the hash in.
+    let hash = std::env::var("OBJECTIO_GIT_HASH")
+        .ok()
+        .filter(|h| !h.is_empty())
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "--short=12", "HEAD"])
+                .output()
+                .ok()
+                .filter(|o| o.status.success())
+                .and_then(|o| String::from_utf8(o.stdout).ok())
+                .map(|h| h.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=OBJECTIO_GIT_HASH={hash}");
+    println!("cargo:rerun-if-env-changed=OBJECTIO_GIT_HASH");
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/refs/heads");
+}
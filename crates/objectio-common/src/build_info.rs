@@ -0,0 +1,18 @@
+//! Build fingerprint reported by every component.
+//!
+//! Each binary adds its own compiled-in features and erasure backend;
+//! the values here are the ones shared across the workspace.
+
+/// Workspace version.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Short git commit hash, or `unknown` when built outside a checkout
+/// without `OBJECTIO_GIT_HASH` set.
+pub const GIT_HASH: &str = env!("OBJECTIO_GIT_HASH");
+
+/// Revision of the gateway ↔ OSD `StorageService` wire contract. Bump
+/// when a change needs both sides upgraded together.
+pub const STORAGE_PROTOCOL_VERSION: u32 = 1;
+
+/// Revision of the `MetadataService` wire contract.
+pub const METADATA_PROTOCOL_VERSION: u32 = 1;
@@ -0,0 +1,221 @@
+//! Byte-bounded LRU cache for object data.
+//!
+//! Used by the gateway (decoded stripes) and the OSD (shard blocks) to
+//! keep hot object data in memory. Capacity is in bytes rather than
+//! entries since values range from a few KiB to a full 4 MiB stripe. A
+//! capacity of 0 disables the cache: `insert` is a no-op and every `get`
+//! misses.
+
+use bytes::Bytes;
+use parking_lot::Mutex;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+struct Entry {
+    data: Bytes,
+    tick: u64,
+}
+
+struct Inner<K> {
+    entries: HashMap<K, Entry>,
+    /// Access tick → key, oldest first.
+    order: BTreeMap<u64, K>,
+    next_tick: u64,
+    bytes: u64,
+}
+
+/// Point-in-time view of a cache's size and counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ByteCacheStats {
+    pub capacity_bytes: u64,
+    pub bytes: u64,
+    pub entries: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// LRU cache bounded by the total size of its values.
+pub struct ByteCache<K> {
+    capacity: u64,
+    inner: Mutex<Inner<K>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl<K: Hash + Eq + Clone> ByteCache<K> {
+    /// Create a cache holding at most `capacity_bytes` of values.
+    #[must_use]
+    pub fn new(capacity_bytes: u64) -> Self {
+        Self {
+            capacity: capacity_bytes,
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+                next_tick: 0,
+                bytes: 0,
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether the cache can hold anything at all.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Look up `key`, marking it most recently used.
+    pub fn get<Q>(&self, key: &Q) -> Option<Bytes>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if !self.is_enabled() {
+            return None;
+        }
+        let mut inner = self.inner.lock();
+        let tick = inner.next_tick;
+        let Some(entry) = inner.entries.get_mut(key) else {
+            drop(inner);
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        let old_tick = std::mem::replace(&mut entry.tick, tick);
+        let data = entry.data.clone();
+        inner.next_tick += 1;
+        if let Some(k) = inner.order.remove(&old_tick) {
+            inner.order.insert(tick, k);
+        }
+        drop(inner);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(data)
+    }
+
+    /// Whether `key` is cached, without touching its recency or the
+    /// hit/miss counters.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.lock().entries.contains_key(key)
+    }
+
+    /// Cache a copy of `value` under `key`, evicting least recently used
+    /// entries to make room. Values larger than the whole cache are
+    /// skipped.
+    pub fn insert(&self, key: K, value: &[u8]) {
+        let size = value.len() as u64;
+        if !self.is_enabled() || size > self.capacity {
+            return;
+        }
+        let data = Bytes::copy_from_slice(value);
+        let mut inner = self.inner.lock();
+        if let Some(old) = inner.entries.remove(&key) {
+            inner.order.remove(&old.tick);
+            inner.bytes -= old.data.len() as u64;
+        }
+        let mut evicted = 0;
+        while inner.bytes + size > self.capacity {
+            let Some((_, oldest)) = inner.order.pop_first() else {
+                break;
+            };
+            if let Some(old) = inner.entries.remove(&oldest) {
+                inner.bytes -= old.data.len() as u64;
+                evicted += 1;
+            }
+        }
+        let tick = inner.next_tick;
+        inner.next_tick += 1;
+        inner.order.insert(tick, key.clone());
+        inner.entries.insert(key, Entry { data, tick });
+        inner.bytes += size;
+        drop(inner);
+        if evicted > 0 {
+            self.evictions.fetch_add(evicted, Ordering::Relaxed);
+        }
+    }
+
+    /// Drop `key` from the cache.
+    pub fn remove<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut inner = self.inner.lock();
+        if let Some(old) = inner.entries.remove(key) {
+            inner.order.remove(&old.tick);
+            inner.bytes -= old.data.len() as u64;
+        }
+    }
+
+    /// Current size and counters.
+    pub fn stats(&self) -> ByteCacheStats {
+        let inner = self.inner.lock();
+        ByteCacheStats {
+            capacity_bytes: self.capacity,
+            bytes: inner.bytes,
+            entries: inner.entries.len() as u64,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = ByteCache::new(10);
+        cache.insert("a", &[0; 4]);
+        cache.insert("b", &[1; 4]);
+        // Touch "a" so "b" is the eviction candidate
+        assert!(cache.get("a").is_some());
+        cache.insert("c", &[2; 4]);
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("c"));
+
+        let stats = cache.stats();
+        assert_eq!(stats.bytes, 8);
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.hits, 1);
+    }
+
+    #[test]
+    fn test_replace_remove_and_oversize() {
+        let cache = ByteCache::new(10);
+        cache.insert("a", &[0; 4]);
+        cache.insert("a", &[1; 6]);
+        assert_eq!(cache.get("a").unwrap().as_ref(), &[1; 6]);
+        assert_eq!(cache.stats().bytes, 6);
+
+        cache.insert("big", &[0; 11]);
+        assert!(!cache.contains("big"));
+        assert!(cache.contains("a"));
+
+        cache.remove("a");
+        assert_eq!(cache.stats().bytes, 0);
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.stats().misses, 1);
+    }
+
+    #[test]
+    fn test_zero_capacity_is_disabled() {
+        let cache = ByteCache::new(0);
+        assert!(!cache.is_enabled());
+        cache.insert("a", &[]);
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.stats(), ByteCacheStats::default());
+    }
+}
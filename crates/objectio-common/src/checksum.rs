@@ -1,9 +1,13 @@
 //! Checksum utilities for ObjectIO
 //!
 //! Provides multi-algorithm checksum calculation and verification
-//! for data integrity.
+//! for data integrity, plus the S3 additional checksum algorithms
+//! (`x-amz-checksum-*`) clients can ask for alongside the MD5 ETag.
 
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use serde::{Deserialize, Serialize};
+use sha1::Sha1;
 use sha2::{Digest, Sha256};
 
 /// Checksum values computed for a block of data
@@ -147,6 +151,149 @@ impl Default for ChecksumCalculator {
     }
 }
 
+/// S3 additional checksum algorithm.
+///
+/// Values travel base64-encoded (big-endian for the CRCs), as in the
+/// `x-amz-checksum-*` headers. A multipart object's checksum is composite:
+/// the checksum of the concatenated raw part checksums, suffixed with
+/// `-<part count>` like a multipart ETag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ChecksumAlgorithm {
+    Crc32,
+    Crc32c,
+    Sha1,
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    pub const ALL: [Self; 4] = [Self::Crc32, Self::Crc32c, Self::Sha1, Self::Sha256];
+
+    /// Name as used in `x-amz-checksum-algorithm` and XML bodies.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Crc32 => "CRC32",
+            Self::Crc32c => "CRC32C",
+            Self::Sha1 => "SHA1",
+            Self::Sha256 => "SHA256",
+        }
+    }
+
+    /// Header carrying a value of this algorithm.
+    #[must_use]
+    pub const fn header_name(self) -> &'static str {
+        match self {
+            Self::Crc32 => "x-amz-checksum-crc32",
+            Self::Crc32c => "x-amz-checksum-crc32c",
+            Self::Sha1 => "x-amz-checksum-sha1",
+            Self::Sha256 => "x-amz-checksum-sha256",
+        }
+    }
+
+    /// XML element carrying a value of this algorithm, e.g. in
+    /// CompleteMultipartUploadResult.
+    #[must_use]
+    pub const fn xml_element(self) -> &'static str {
+        match self {
+            Self::Crc32 => "ChecksumCRC32",
+            Self::Crc32c => "ChecksumCRC32C",
+            Self::Sha1 => "ChecksumSHA1",
+            Self::Sha256 => "ChecksumSHA256",
+        }
+    }
+
+    /// Start a streaming checksum.
+    #[must_use]
+    pub fn hasher(self) -> ChecksumHasher {
+        match self {
+            Self::Crc32 => ChecksumHasher::Crc32(crc32fast::Hasher::new()),
+            Self::Crc32c => ChecksumHasher::Crc32c(0),
+            Self::Sha1 => ChecksumHasher::Sha1(Sha1::new()),
+            Self::Sha256 => ChecksumHasher::Sha256(Sha256::new()),
+        }
+    }
+
+    /// Base64 checksum of `data`.
+    #[must_use]
+    pub fn checksum(self, data: &[u8]) -> String {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Composite checksum of a multipart object from its parts' base64
+    /// checksums, in part order. `None` if a part checksum isn't valid
+    /// base64.
+    #[must_use]
+    pub fn composite<'a>(self, parts: impl IntoIterator<Item = &'a str>) -> Option<String> {
+        let mut hasher = self.hasher();
+        let mut count = 0usize;
+        for part in parts {
+            hasher.update(&BASE64.decode(part).ok()?);
+            count += 1;
+        }
+        Some(format!("{}-{count}", hasher.finalize()))
+    }
+}
+
+impl std::fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ChecksumAlgorithm {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "CRC32" => Ok(Self::Crc32),
+            "CRC32C" => Ok(Self::Crc32c),
+            "SHA1" => Ok(Self::Sha1),
+            "SHA256" => Ok(Self::Sha256),
+            _ => Err("expected one of: CRC32, CRC32C, SHA1, SHA256"),
+        }
+    }
+}
+
+/// Streaming [`ChecksumAlgorithm`] calculator.
+#[derive(Clone)]
+pub enum ChecksumHasher {
+    Crc32(crc32fast::Hasher),
+    Crc32c(u32),
+    Sha1(Sha1),
+    Sha256(Sha256),
+}
+
+impl ChecksumHasher {
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Crc32(h) => h.update(data),
+            Self::Crc32c(crc) => *crc = crc32c::crc32c_append(*crc, data),
+            Self::Sha1(h) => h.update(data),
+            Self::Sha256(h) => h.update(data),
+        }
+    }
+
+    /// Raw checksum bytes.
+    #[must_use]
+    pub fn finalize_bytes(self) -> Vec<u8> {
+        match self {
+            Self::Crc32(h) => h.finalize().to_be_bytes().to_vec(),
+            Self::Crc32c(crc) => crc.to_be_bytes().to_vec(),
+            Self::Sha1(h) => h.finalize().to_vec(),
+            Self::Sha256(h) => h.finalize().to_vec(),
+        }
+    }
+
+    /// Base64 checksum, as sent in `x-amz-checksum-*` headers.
+    #[must_use]
+    pub fn finalize(self) -> String {
+        BASE64.encode(self.finalize_bytes())
+    }
+}
+
 /// Quick CRC32C verification
 #[inline]
 #[must_use]
@@ -245,6 +392,43 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_s3_checksum_algorithms() {
+        // Reference values from the AWS SDKs for "hello world"
+        let data = b"hello world";
+        assert_eq!(ChecksumAlgorithm::Crc32.checksum(data), "DUoRhQ==");
+        assert_eq!(ChecksumAlgorithm::Crc32c.checksum(data), "yZRlqg==");
+        assert_eq!(
+            ChecksumAlgorithm::Sha1.checksum(data),
+            "Kq5sNclPz7QV2+lfQIuc6R7oRu0="
+        );
+        assert_eq!(
+            ChecksumAlgorithm::Sha256.checksum(data),
+            "uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek="
+        );
+
+        let mut hasher = ChecksumAlgorithm::Crc32c.hasher();
+        hasher.update(b"hello ");
+        hasher.update(b"world");
+        assert_eq!(hasher.finalize(), "yZRlqg==");
+
+        assert_eq!("crc32c".parse(), Ok(ChecksumAlgorithm::Crc32c));
+        assert!("md5".parse::<ChecksumAlgorithm>().is_err());
+    }
+
+    #[test]
+    fn test_composite_checksum() {
+        let alg = ChecksumAlgorithm::Crc32;
+        let parts = [alg.checksum(b"hello "), alg.checksum(b"world")];
+        let mut concatenated = BASE64.decode(&parts[0]).unwrap();
+        concatenated.extend(BASE64.decode(&parts[1]).unwrap());
+        assert_eq!(
+            alg.composite(parts.iter().map(String::as_str)),
+            Some(format!("{}-2", alg.checksum(&concatenated)))
+        );
+        assert_eq!(alg.composite(["not base64!"]), None);
+    }
+
     #[test]
     fn test_hex_encode() {
         assert_eq!(hex_encode(&[0x12, 0xab, 0xcd]), "12abcd");
@@ -3,12 +3,15 @@
 //! This crate provides common types, error definitions, and utilities
 //! used across all ObjectIO components.
 
+pub mod build_info;
+pub mod byte_cache;
 pub mod checksum;
 pub mod config;
 pub mod error;
 pub mod types;
 
-pub use checksum::{Checksum, ChecksumCalculator};
+pub use byte_cache::{ByteCache, ByteCacheStats};
+pub use checksum::{Checksum, ChecksumAlgorithm, ChecksumCalculator, ChecksumHasher};
 pub use config::Config;
 pub use error::{Error, Result};
 pub use types::*;
@@ -186,6 +186,20 @@ pub fn decrypt_in_place(dek: &[u8; DEK_LEN], iv: &[u8; IV_LEN], offset: u64, buf
     cipher.apply_keystream(buf);
 }
 
+/// IV whose keystream starts `offset` bytes into the stream begun by `iv`.
+///
+/// Lets a stripe be split at a block boundary: the second half
+/// decrypts from offset 0 under the derived IV. `offset` must be a
+/// multiple of the 16-byte AES block.
+#[must_use]
+pub fn iv_at_offset(iv: &[u8; IV_LEN], offset: u64) -> [u8; IV_LEN] {
+    debug_assert_eq!(offset % 16, 0, "iv_at_offset needs a block-aligned offset");
+    // Ctr128BE treats the whole IV as one big-endian counter.
+    u128::from_be_bytes(*iv)
+        .wrapping_add(u128::from(offset / 16))
+        .to_be_bytes()
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum KmsError {
     #[error("master key env var {0} not set")]
@@ -407,6 +421,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn iv_at_offset_continues_keystream() {
+        let dek = generate_dek();
+        // All-ones low bytes force a carry across the counter words.
+        let mut iv = generate_iv();
+        iv[8..].fill(0xff);
+        let plaintext: Vec<u8> = (0..=255u8).cycle().take(256).collect();
+        let mut ciphertext = plaintext.clone();
+        encrypt_in_place(&dek, &iv, &mut ciphertext);
+
+        let mut tail = ciphertext[64..].to_vec();
+        decrypt_in_place(&dek, &iv_at_offset(&iv, 64), 0, &mut tail);
+        assert_eq!(tail, &plaintext[64..]);
+        assert_eq!(iv_at_offset(&iv, 0), iv);
+    }
+
     #[test]
     fn master_key_debug_never_leaks_bytes() {
         let mk = MasterKey::generate_random();
@@ -1,5 +1,6 @@
 //! ObjectIO Metadata Store — persistent metadata backed by redb.
 
+pub mod archive;
 pub mod raft;
 pub mod raft_network;
 pub mod raft_storage;
@@ -7,6 +8,7 @@ pub mod store;
 pub mod tables;
 pub mod types;
 
+pub use archive::MetaArchive;
 pub use raft::{ApplyEvent, CasOp, CasTable, MetaCommand, MetaResponse, MetaTypeConfig};
 pub use raft_network::{MetaRaftNetwork, MetaRaftNetworkFactory};
 pub use raft_storage::MetaRaftStorage;
@@ -107,6 +107,7 @@ impl MetaStore {
             let _t = write_txn.open_table(tables::LIFECYCLE_CONFIGS)?;
             let _t = write_txn.open_table(tables::BUCKET_ENCRYPTION_CONFIGS)?;
             let _t = write_txn.open_table(tables::KMS_KEYS)?;
+            let _t = write_txn.open_table(tables::EC_MIGRATIONS)?;
         }
         write_txn.commit()?;
 
@@ -1481,6 +1482,39 @@ impl MetaStore {
         result
     }
 
+    // ---- EC profile migration jobs (prost-encoded EcMigrationJob) ----
+
+    pub fn put_ec_migration(&self, bucket: &str, data: &[u8]) {
+        if let Err(e) = self.put_bytes(tables::EC_MIGRATIONS, bucket, data) {
+            error!("Failed to persist EC migration for '{}': {}", bucket, e);
+        }
+    }
+
+    pub fn load_all_ec_migrations(&self) -> Vec<(String, Vec<u8>)> {
+        let read_txn = match self.db.begin_read() {
+            Ok(t) => t,
+            Err(e) => {
+                error!("Failed to begin read txn for EC migrations: {}", e);
+                return Vec::new();
+            }
+        };
+        let table = match read_txn.open_table(tables::EC_MIGRATIONS) {
+            Ok(t) => t,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Vec::new(),
+            Err(e) => {
+                error!("Failed to open EC migrations table: {}", e);
+                return Vec::new();
+            }
+        };
+        let mut result = Vec::new();
+        if let Ok(iter) = table.iter() {
+            for entry in iter.flatten() {
+                result.push((entry.0.value().to_string(), entry.1.value().to_vec()));
+            }
+        }
+        result
+    }
+
     // ---- KMS keys (prost-encoded KmsKey; key material already wrapped) ----
 
     pub fn put_kms_key(&self, key_id: &str, data: &[u8]) {
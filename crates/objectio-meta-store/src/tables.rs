@@ -122,6 +122,10 @@ pub const LIFECYCLE_CONFIGS: TableDefinition<&str, &[u8]> =
 pub const BUCKET_ENCRYPTION_CONFIGS: TableDefinition<&str, &[u8]> =
     TableDefinition::new("bucket_encryption_configs");
 
+// EC profile migration jobs
+// Key: bucket name, Value: prost-encoded EcMigrationJob
+pub const EC_MIGRATIONS: TableDefinition<&str, &[u8]> = TableDefinition::new("ec_migrations");
+
 // KMS keys (service-master-key-wrapped key material)
 // Key: key_id, Value: prost-encoded KmsKey
 pub const KMS_KEYS: TableDefinition<&str, &[u8]> = TableDefinition::new("kms_keys");
@@ -407,7 +407,7 @@ impl Crush2 {
             .collect();
 
         // Sort by score descending
-        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        scored.sort_by_key(|b| std::cmp::Reverse(b.1));
 
         // Take top N
         scored.truncate(count);
@@ -229,6 +229,15 @@ struct GatewayMetrics {
     scatter_gather_latency_us: AtomicU64,
 }
 
+/// Request-shaping counters for one operation class
+#[derive(Debug, Default)]
+struct ShapingMetrics {
+    /// Requests currently holding a slot in the class pool
+    in_flight: AtomicU64,
+    /// Requests turned away with 503 SlowDown after queueing
+    rejected: AtomicU64,
+}
+
 /// Iceberg policy decision tracking key
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct PolicyDecisionKey {
@@ -254,6 +263,9 @@ pub struct S3Metrics {
     /// `TopologyDistance::as_str()` (same-host, same-rack, same-datacenter,
     /// same-zone, same-region, remote, unknown).
     locality_read_bytes: RwLock<HashMap<String, AtomicU64>>,
+    /// Request-shaping pool state per operation class (read, write,
+    /// list, delete). Empty when shaping is disabled.
+    shaping: RwLock<HashMap<String, ShapingMetrics>>,
     /// Gateway metrics
     gateway: GatewayMetrics,
     /// Start time for uptime calculation
@@ -271,6 +283,7 @@ impl S3Metrics {
             unity_operations: RwLock::new(HashMap::new()),
             iceberg_policy_decisions: RwLock::new(HashMap::new()),
             locality_read_bytes: RwLock::new(HashMap::new()),
+            shaping: RwLock::new(HashMap::new()),
             gateway: GatewayMetrics::default(),
             start_time: Instant::now(),
             protection: RwLock::new(None),
@@ -300,6 +313,38 @@ impl S3Metrics {
             .fetch_add(bytes, Ordering::Relaxed);
     }
 
+    fn with_shaping_class(&self, class: &str, f: impl Fn(&ShapingMetrics)) {
+        let map = self.shaping.read().unwrap();
+        if let Some(m) = map.get(class) {
+            f(m);
+            return;
+        }
+        drop(map);
+        let mut map = self.shaping.write().unwrap();
+        f(map.entry(class.to_string()).or_default());
+    }
+
+    /// A request took a slot in its operation class's shaping pool.
+    pub fn shaping_started(&self, class: &str) {
+        self.with_shaping_class(class, |m| {
+            m.in_flight.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+
+    /// A request released its shaping slot.
+    pub fn shaping_finished(&self, class: &str) {
+        self.with_shaping_class(class, |m| {
+            m.in_flight.fetch_sub(1, Ordering::Relaxed);
+        });
+    }
+
+    /// A request timed out waiting for a shaping slot.
+    pub fn record_shaping_rejection(&self, class: &str) {
+        self.with_shaping_class(class, |m| {
+            m.rejected.fetch_add(1, Ordering::Relaxed);
+        });
+    }
+
     /// Record an S3 operation
     pub fn record_operation(
         &self,
@@ -505,6 +550,47 @@ impl S3Metrics {
             }
         }
 
+        // Request shaping — per-class pool occupancy and rejections.
+        {
+            let map = self.shaping.read().unwrap();
+            if !map.is_empty() {
+                writeln!(
+                    output,
+                    "# HELP objectio_gateway_shaping_in_flight Requests holding a request-shaping slot, by operation class"
+                )
+                .unwrap();
+                writeln!(output, "# TYPE objectio_gateway_shaping_in_flight gauge").unwrap();
+                for (class, m) in map.iter() {
+                    writeln!(
+                        output,
+                        "objectio_gateway_shaping_in_flight{{class=\"{}\"}} {}",
+                        class,
+                        m.in_flight.load(Ordering::Relaxed)
+                    )
+                    .unwrap();
+                }
+                writeln!(
+                    output,
+                    "# HELP objectio_gateway_shaping_rejected_total Requests rejected with SlowDown by request shaping, by operation class"
+                )
+                .unwrap();
+                writeln!(
+                    output,
+                    "# TYPE objectio_gateway_shaping_rejected_total counter"
+                )
+                .unwrap();
+                for (class, m) in map.iter() {
+                    writeln!(
+                        output,
+                        "objectio_gateway_shaping_rejected_total{{class=\"{}\"}} {}",
+                        class,
+                        m.rejected.load(Ordering::Relaxed)
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
         // Protection configuration metrics
         if let Some(ref prot) = *self.protection.read().unwrap() {
             writeln!(
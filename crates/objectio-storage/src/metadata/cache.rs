@@ -23,6 +23,14 @@ pub struct CacheStats {
     pub evictions: AtomicU64,
     pub t1_hits: AtomicU64,
     pub t2_hits: AtomicU64,
+    /// Evictions out of T1 (recency tier) into B1
+    pub t1_evictions: AtomicU64,
+    /// Evictions out of T2 (frequency tier) into B2
+    pub t2_evictions: AtomicU64,
+    /// Puts that found their key in B1 (T1 was too small)
+    pub b1_ghost_hits: AtomicU64,
+    /// Puts that found their key in B2 (T2 was too small)
+    pub b2_ghost_hits: AtomicU64,
 }
 
 impl CacheStats {
@@ -41,6 +49,10 @@ impl CacheStats {
         self.evictions.store(0, Ordering::Relaxed);
         self.t1_hits.store(0, Ordering::Relaxed);
         self.t2_hits.store(0, Ordering::Relaxed);
+        self.t1_evictions.store(0, Ordering::Relaxed);
+        self.t2_evictions.store(0, Ordering::Relaxed);
+        self.b1_ghost_hits.store(0, Ordering::Relaxed);
+        self.b2_ghost_hits.store(0, Ordering::Relaxed);
     }
 }
 
@@ -194,6 +206,7 @@ impl ArcCache {
 
         // Case 2: Key is in B1 (ghost - was recently evicted from T1)
         if state.b1.contains(&key) {
+            self.stats.b1_ghost_hits.fetch_add(1, Ordering::Relaxed);
             // Adapt: increase target size for T1
             let delta = std::cmp::max(1, state.b2.len() / state.b1.len().max(1));
             state.p = std::cmp::min(capacity, state.p + delta);
@@ -212,6 +225,7 @@ impl ArcCache {
 
         // Case 3: Key is in B2 (ghost - was recently evicted from T2)
         if state.b2.contains(&key) {
+            self.stats.b2_ghost_hits.fetch_add(1, Ordering::Relaxed);
             // Adapt: decrease target size for T1
             let delta = std::cmp::max(1, state.b1.len() / state.b2.len().max(1));
             state.p = state.p.saturating_sub(delta);
@@ -243,6 +257,7 @@ impl ArcCache {
                     state.cache.remove(&evicted);
                     state.b1.push_back(evicted);
                     self.stats.evictions.fetch_add(1, Ordering::Relaxed);
+                    self.stats.t1_evictions.fetch_add(1, Ordering::Relaxed);
                 }
             }
         } else if l1_size < capacity && state.cache_size() + state.ghost_size() >= capacity {
@@ -302,6 +317,7 @@ impl ArcCache {
                 state.cache.remove(&evicted);
                 state.b1.push_back(evicted);
                 self.stats.evictions.fetch_add(1, Ordering::Relaxed);
+                self.stats.t1_evictions.fetch_add(1, Ordering::Relaxed);
             }
         } else {
             // Evict from T2 -> B2
@@ -309,6 +325,7 @@ impl ArcCache {
                 state.cache.remove(&evicted);
                 state.b2.push_back(evicted);
                 self.stats.evictions.fetch_add(1, Ordering::Relaxed);
+                self.stats.t2_evictions.fetch_add(1, Ordering::Relaxed);
             }
         }
     }
@@ -324,6 +341,25 @@ impl ArcCache {
         state.p = 0;
     }
 
+    /// Change the capacity at runtime.
+    ///
+    /// Shrinking evicts through the normal replacement path (so evicted
+    /// keys land in the ghost lists) until the cache fits, then trims the
+    /// ghost lists to the new bound.
+    pub fn resize(&self, capacity: usize) {
+        let mut state = self.state.lock();
+        state.capacity = capacity;
+        state.p = state.p.min(capacity);
+
+        while state.cache_size() > capacity {
+            self.replace(&mut state, false);
+        }
+        while state.t1.len() + state.b1.len() > capacity && state.b1.pop_front().is_some() {}
+        while state.cache_size() + state.ghost_size() > 2 * capacity
+            && state.b2.pop_front().is_some()
+        {}
+    }
+
     /// Get cache statistics
     pub fn stats(&self) -> &CacheStats {
         &self.stats
@@ -484,4 +520,46 @@ mod tests {
 
         assert_eq!(cache.get(&MetadataKey::block(1)), Some(b"v1_new".to_vec()));
     }
+
+    #[test]
+    fn test_arc_tier_stats() {
+        let cache = ArcCache::new(2);
+
+        cache.put(MetadataKey::block(1), b"v1".to_vec());
+        cache.put(MetadataKey::block(2), b"v2".to_vec());
+        cache.put(MetadataKey::block(3), b"v3".to_vec()); // Evicts key 1 from T1
+        assert_eq!(cache.stats().t1_evictions.load(Ordering::Relaxed), 1);
+
+        cache.put(MetadataKey::block(1), b"v1".to_vec()); // Ghost hit in B1
+        assert_eq!(cache.stats().b1_ghost_hits.load(Ordering::Relaxed), 1);
+
+        cache.stats().reset();
+        assert_eq!(cache.stats().t1_evictions.load(Ordering::Relaxed), 0);
+        assert_eq!(cache.stats().b1_ghost_hits.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_arc_resize() {
+        let cache = ArcCache::new(4);
+        for i in 0..4 {
+            cache.put(MetadataKey::block(i), vec![i as u8]);
+        }
+        assert_eq!(cache.len(), 4);
+
+        cache.resize(2);
+        assert_eq!(cache.capacity(), 2);
+        assert_eq!(cache.len(), 2);
+        let info = cache.debug_info();
+        assert!(info.t1_len + info.b1_len <= 2);
+        assert!(info.t1_len + info.t2_len + info.b1_len + info.b2_len <= 4);
+
+        // Most recent entries survive the shrink
+        assert!(cache.contains(&MetadataKey::block(3)));
+
+        cache.resize(8);
+        for i in 10..16 {
+            cache.put(MetadataKey::block(i), vec![0]);
+        }
+        assert_eq!(cache.len(), 8);
+    }
 }
@@ -52,6 +52,8 @@ mod types;
 mod wal;
 
 pub use cache::{ArcCache, CacheStats as MetaCacheStats};
-pub use store::{MetadataStore, MetadataStoreConfig};
+pub use store::{
+    CacheTierCounters, CacheTierStats, CacheWritePolicy, MetadataStore, MetadataStoreConfig,
+};
 pub use types::{MetadataEntry, MetadataKey, MetadataOp, ShardMeta};
 pub use wal::MetadataWal;
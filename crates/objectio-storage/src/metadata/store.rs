@@ -61,6 +61,26 @@ impl MetadataStoreConfig {
     }
 }
 
+/// How writes interact with the ARC cache
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CacheWritePolicy {
+    /// Writes update the cache so a read-after-write is a hit
+    #[default]
+    WriteThrough,
+    /// Writes invalidate the cached entry and leave population to reads,
+    /// so write-heavy workloads don't flush the hot read set
+    WriteAround,
+}
+
+impl CacheWritePolicy {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::WriteThrough => "write-through",
+            Self::WriteAround => "write-around",
+        }
+    }
+}
+
 /// Unified metadata store for OSD
 pub struct MetadataStore {
     /// Write-ahead log
@@ -69,6 +89,8 @@ pub struct MetadataStore {
     index: Arc<BTreeIndex>,
     /// ARC cache
     cache: Arc<ArcCache>,
+    /// Cache write policy (true = write-around)
+    write_around: AtomicBool,
     /// Configuration
     config: MetadataStoreConfig,
     /// Compaction lock
@@ -102,6 +124,7 @@ impl MetadataStore {
             wal,
             index,
             cache,
+            write_around: AtomicBool::new(false),
             config,
             compaction_lock: Mutex::new(()),
             shutdown: Arc::new(AtomicBool::new(false)),
@@ -150,6 +173,7 @@ impl MetadataStore {
             wal,
             index,
             cache,
+            write_around: AtomicBool::new(false),
             config,
             compaction_lock: Mutex::new(()),
             shutdown: Arc::new(AtomicBool::new(false)),
@@ -191,7 +215,7 @@ impl MetadataStore {
         self.index.put(key.clone(), value.clone(), lsn);
 
         // 3. Update cache
-        self.cache.put(key, value);
+        self.cache_write(key, value);
 
         debug!("put: lsn={}", lsn);
         Ok(lsn)
@@ -273,13 +297,22 @@ impl MetadataStore {
 
         // 3. Update cache
         for (key, value) in entries {
-            self.cache.put(key, value);
+            self.cache_write(key, value);
         }
 
         debug!("batch_put: {} entries, lsn={}", ops.len(), lsn);
         Ok(lsn)
     }
 
+    /// Apply a write to the cache according to the write policy
+    fn cache_write(&self, key: MetadataKey, value: Vec<u8>) {
+        if self.write_around.load(Ordering::Relaxed) {
+            self.cache.remove(&key);
+        } else {
+            self.cache.put(key, value);
+        }
+    }
+
     /// Scan entries with a key prefix
     pub fn scan_prefix(&self, prefix: &MetadataKey) -> Vec<(MetadataKey, Vec<u8>)> {
         self.index.scan_prefix(prefix)
@@ -425,6 +458,72 @@ impl MetadataStore {
         self.cache.stats().hit_ratio()
     }
 
+    /// Current cache write policy
+    pub fn cache_write_policy(&self) -> CacheWritePolicy {
+        if self.write_around.load(Ordering::Relaxed) {
+            CacheWritePolicy::WriteAround
+        } else {
+            CacheWritePolicy::WriteThrough
+        }
+    }
+
+    /// Switch the cache write policy at runtime
+    pub fn set_cache_write_policy(&self, policy: CacheWritePolicy) {
+        self.write_around
+            .store(policy == CacheWritePolicy::WriteAround, Ordering::Relaxed);
+        info!("Metadata cache write policy set to {}", policy.as_str());
+    }
+
+    /// Resize the cache at runtime (number of entries)
+    pub fn resize_cache(&self, capacity: usize) {
+        self.cache.resize(capacity);
+        info!("Metadata cache resized to {} entries", capacity);
+    }
+
+    /// Sync the WAL and optionally drop every cached entry
+    ///
+    /// The cache never holds dirty data (the WAL is written first), so
+    /// flushing only needs to make the WAL durable; dropping entries is
+    /// for operators who want to re-warm from a clean slate.
+    pub fn flush_cache(&self, drop_entries: bool) -> Result<()> {
+        self.wal.sync()?;
+        if drop_entries {
+            self.cache.clear();
+        }
+        Ok(())
+    }
+
+    /// Reset cache hit/miss/eviction counters
+    pub fn reset_cache_stats(&self) {
+        self.cache.stats().reset();
+    }
+
+    /// Per-tier cache statistics
+    pub fn cache_tier_stats(&self) -> CacheTierStats {
+        let info = self.cache.debug_info();
+        let stats = self.cache.stats();
+        CacheTierStats {
+            capacity: info.capacity,
+            target_t1_size: info.p,
+            write_policy: self.cache_write_policy(),
+            misses: stats.misses.load(Ordering::Relaxed),
+            recent: CacheTierCounters {
+                entries: info.t1_len,
+                ghost_entries: info.b1_len,
+                hits: stats.t1_hits.load(Ordering::Relaxed),
+                evictions: stats.t1_evictions.load(Ordering::Relaxed),
+                ghost_hits: stats.b1_ghost_hits.load(Ordering::Relaxed),
+            },
+            frequent: CacheTierCounters {
+                entries: info.t2_len,
+                ghost_entries: info.b2_len,
+                hits: stats.t2_hits.load(Ordering::Relaxed),
+                evictions: stats.t2_evictions.load(Ordering::Relaxed),
+                ghost_hits: stats.b2_ghost_hits.load(Ordering::Relaxed),
+            },
+        }
+    }
+
     /// Get statistics
     pub fn stats(&self) -> MetadataStoreStats {
         MetadataStoreStats {
@@ -470,6 +569,39 @@ pub struct MetadataStoreStats {
     pub cache_misses: u64,
 }
 
+/// Snapshot of the ARC cache, split into its recency (T1) and
+/// frequency (T2) tiers
+#[derive(Debug, Clone)]
+pub struct CacheTierStats {
+    /// Maximum cached entries
+    pub capacity: usize,
+    /// Adaptive target size of the recency tier
+    pub target_t1_size: usize,
+    /// Current write policy
+    pub write_policy: CacheWritePolicy,
+    /// Lookups that missed both tiers
+    pub misses: u64,
+    /// T1: entries seen once recently (ghost list B1)
+    pub recent: CacheTierCounters,
+    /// T2: entries seen more than once (ghost list B2)
+    pub frequent: CacheTierCounters,
+}
+
+/// Counters for a single ARC tier
+#[derive(Debug, Clone, Default)]
+pub struct CacheTierCounters {
+    /// Entries currently cached in the tier
+    pub entries: usize,
+    /// Evicted keys remembered in the tier's ghost list
+    pub ghost_entries: usize,
+    /// Lookups served from the tier
+    pub hits: u64,
+    /// Entries evicted from the tier
+    pub evictions: u64,
+    /// Re-inserts of keys found in the tier's ghost list
+    pub ghost_hits: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -701,4 +833,28 @@ mod tests {
 
         assert!(stats_after > stats_before);
     }
+
+    #[test]
+    fn test_store_cache_write_around() {
+        let dir = tempdir().unwrap();
+        let config = test_config(dir.path());
+
+        let store = MetadataStore::create(config).unwrap();
+        store.set_cache_write_policy(CacheWritePolicy::WriteAround);
+
+        let key = MetadataKey::block(7);
+        store.put(key.clone(), b"v1".to_vec()).unwrap();
+        assert!(!store.cache.contains(&key));
+
+        // Reads still populate, and a later write invalidates
+        assert_eq!(store.get(&key), Some(b"v1".to_vec()));
+        assert!(store.cache.contains(&key));
+        store.put(key.clone(), b"v2".to_vec()).unwrap();
+        assert!(!store.cache.contains(&key));
+        assert_eq!(store.get(&key), Some(b"v2".to_vec()));
+
+        store.flush_cache(true).unwrap();
+        assert_eq!(store.cache_tier_stats().recent.entries, 0);
+        assert_eq!(store.cache_tier_stats().frequent.entries, 0);
+    }
 }
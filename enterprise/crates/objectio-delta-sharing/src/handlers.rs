@@ -827,7 +827,7 @@ fn extract_file_lines(
         .and_then(serde_json::Value::as_i64)
         .unwrap_or(0);
 
-    let presigned_url = state.presign(bucket, key, Duration::from_secs(3600));
+    let presigned_url = state.presign(bucket, key, Duration::from_hours(1));
     let file_id = format!("manifest-list-{current_snapshot_id}");
 
     vec![FileLine {
@@ -979,7 +979,7 @@ mod tests {
             access_key_id: "AKID".into(),
             secret_access_key: "secret".into(),
             http: reqwest::Client::new(),
-            default_url_ttl: Duration::from_secs(900),
+            default_url_ttl: Duration::from_mins(15),
         }
     }
 
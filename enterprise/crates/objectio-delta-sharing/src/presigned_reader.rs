@@ -12,7 +12,7 @@ use serde::Deserialize;
 use std::time::Duration;
 
 /// Default lifetime of presigned URLs used to fetch `_delta_log/` files.
-pub const DEFAULT_LOG_URL_TTL: Duration = Duration::from_secs(900);
+pub const DEFAULT_LOG_URL_TTL: Duration = Duration::from_mins(15);
 
 /// Cap on how many list-objects pages we follow before bailing — prevents an
 /// adversarial bucket with millions of stale commit files from hanging us.
@@ -955,7 +955,7 @@ pub async fn commit_transaction(
     // Zip the meta-side response (authoritative committed location +
     // stored bytes) with the client-side metadata JSON we just built.
     let mut out = Vec::with_capacity(prepared.len());
-    for (p, (committed_location, _)) in prepared.into_iter().zip(committed.into_iter()) {
+    for (p, (committed_location, _)) in prepared.into_iter().zip(committed) {
         out.push(CommitTableResponse {
             metadata_location: committed_location,
             metadata: p.new_metadata,